@@ -11,6 +11,31 @@ mod m20220101_000007_create_upi_payments_table;
 mod m20220101_000008_create_cash_transactions_table;
 mod m20220101_000009_create_notifications_table;
 mod m20250325_082658_make_description_required;
+mod m20250402_101500_add_simplify_debts_to_groups;
+mod m20250405_093000_make_transaction_activity_optional;
+mod m20250408_114500_add_transaction_ref_to_upi_payments;
+mod m20250411_160000_add_confirmation_to_cash_transactions;
+mod m20250414_120000_add_batch_id_to_transactions;
+mod m20250418_100000_add_split_type_to_activities;
+mod m20250422_090000_add_payers_to_activities;
+mod m20250426_110000_add_currencies_and_exchange_rates;
+mod m20250430_100000_create_recurring_expenses_table;
+mod m20250503_120000_create_activity_items_table;
+mod m20250507_093000_create_activity_attachments_table;
+mod m20250510_140000_create_categories_table;
+mod m20250513_100000_create_activity_comments_table;
+mod m20250516_090000_create_activity_history_table;
+mod m20250520_100000_add_soft_delete_to_activities;
+mod m20250523_110000_add_archived_at_to_groups;
+mod m20250527_090000_add_role_to_group_members;
+mod m20250530_100000_create_group_invites_table;
+mod m20250530_103000_add_join_code_to_groups;
+mod m20250530_110000_add_unique_member_to_group_members;
+mod m20250603_090000_add_unique_pair_to_friend_collections;
+mod m20250606_090000_add_context_to_notifications;
+mod m20250610_090000_create_group_events_table;
+mod m20250613_090000_create_digest_preferences_table;
+mod m20250617_090000_add_unique_oauth_identity_to_users;
 
 pub struct Migrator;
 
@@ -28,6 +53,31 @@ impl MigratorTrait for Migrator {
             Box::new(m20220101_000008_create_cash_transactions_table::Migration),
             Box::new(m20220101_000009_create_notifications_table::Migration),
             Box::new(m20250325_082658_make_description_required::Migration),
+            Box::new(m20250402_101500_add_simplify_debts_to_groups::Migration),
+            Box::new(m20250405_093000_make_transaction_activity_optional::Migration),
+            Box::new(m20250408_114500_add_transaction_ref_to_upi_payments::Migration),
+            Box::new(m20250411_160000_add_confirmation_to_cash_transactions::Migration),
+            Box::new(m20250414_120000_add_batch_id_to_transactions::Migration),
+            Box::new(m20250418_100000_add_split_type_to_activities::Migration),
+            Box::new(m20250422_090000_add_payers_to_activities::Migration),
+            Box::new(m20250426_110000_add_currencies_and_exchange_rates::Migration),
+            Box::new(m20250430_100000_create_recurring_expenses_table::Migration),
+            Box::new(m20250503_120000_create_activity_items_table::Migration),
+            Box::new(m20250507_093000_create_activity_attachments_table::Migration),
+            Box::new(m20250510_140000_create_categories_table::Migration),
+            Box::new(m20250513_100000_create_activity_comments_table::Migration),
+            Box::new(m20250516_090000_create_activity_history_table::Migration),
+            Box::new(m20250520_100000_add_soft_delete_to_activities::Migration),
+            Box::new(m20250523_110000_add_archived_at_to_groups::Migration),
+            Box::new(m20250527_090000_add_role_to_group_members::Migration),
+            Box::new(m20250530_100000_create_group_invites_table::Migration),
+            Box::new(m20250530_103000_add_join_code_to_groups::Migration),
+            Box::new(m20250530_110000_add_unique_member_to_group_members::Migration),
+            Box::new(m20250603_090000_add_unique_pair_to_friend_collections::Migration),
+            Box::new(m20250606_090000_add_context_to_notifications::Migration),
+            Box::new(m20250610_090000_create_group_events_table::Migration),
+            Box::new(m20250613_090000_create_digest_preferences_table::Migration),
+            Box::new(m20250617_090000_add_unique_oauth_identity_to_users::Migration),
         ]
     }
 }
@@ -0,0 +1,41 @@
+use sea_orm_migration::prelude::*;
+use sea_query::Table;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Groups::Table)
+                    .add_column(
+                        ColumnDef::new(Groups::SimplifyDebts)
+                            .boolean()
+                            .not_null()
+                            .default(true),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Groups::Table)
+                    .drop_column(Groups::SimplifyDebts)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum Groups {
+    Table,
+    SimplifyDebts,
+}
@@ -0,0 +1,56 @@
+use sea_orm_migration::prelude::*;
+use sea_query::{Index, Table};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(UpiPayments::Table)
+                    .add_column(ColumnDef::new(UpiPayments::TransactionRef).string().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_upi_payments_transaction_ref")
+                    .table(UpiPayments::Table)
+                    .col(UpiPayments::TransactionRef)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_upi_payments_transaction_ref")
+                    .table(UpiPayments::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(UpiPayments::Table)
+                    .drop_column(UpiPayments::TransactionRef)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum UpiPayments {
+    Table,
+    TransactionRef,
+}
@@ -0,0 +1,43 @@
+use sea_orm_migration::prelude::*;
+use sea_query::Table;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(CashTransactions::Table)
+                    .add_column(
+                        ColumnDef::new(CashTransactions::RespondedAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .add_column(ColumnDef::new(CashTransactions::DisputeReason).string().null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(CashTransactions::Table)
+                    .drop_column(CashTransactions::RespondedAt)
+                    .drop_column(CashTransactions::DisputeReason)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum CashTransactions {
+    Table,
+    RespondedAt,
+    DisputeReason,
+}
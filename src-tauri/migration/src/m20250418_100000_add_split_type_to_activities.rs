@@ -0,0 +1,44 @@
+use sea_orm_migration::prelude::*;
+use sea_query::Table;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Activities::Table)
+                    .add_column(
+                        ColumnDef::new(Activities::SplitType)
+                            .string()
+                            .not_null()
+                            .default("exact"),
+                    )
+                    .add_column(ColumnDef::new(Activities::SplitValues).json().null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Activities::Table)
+                    .drop_column(Activities::SplitType)
+                    .drop_column(Activities::SplitValues)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum Activities {
+    Table,
+    SplitType,
+    SplitValues,
+}
@@ -0,0 +1,59 @@
+use sea_orm_migration::prelude::*;
+use sea_query::Table;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Activities::Table)
+                    .add_column(ColumnDef::new(Activities::PayerIds).json().null())
+                    .add_column(ColumnDef::new(Activities::PayerAmounts).json().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        // existing activities were paid in full by their single payer
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "UPDATE activities \
+                 SET payer_ids = json_build_array(paid_by_id), \
+                     payer_amounts = json_build_array(amount::text)",
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Activities::Table)
+                    .modify_column(ColumnDef::new(Activities::PayerIds).json().not_null())
+                    .modify_column(ColumnDef::new(Activities::PayerAmounts).json().not_null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Activities::Table)
+                    .drop_column(Activities::PayerIds)
+                    .drop_column(Activities::PayerAmounts)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum Activities {
+    Table,
+    PayerIds,
+    PayerAmounts,
+}
@@ -0,0 +1,130 @@
+use sea_orm_migration::prelude::*;
+use sea_query::{Index, Table};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Groups::Table)
+                    .add_column(
+                        ColumnDef::new(Groups::Currency)
+                            .string_len(3)
+                            .not_null()
+                            .default("INR"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Activities::Table)
+                    .add_column(
+                        ColumnDef::new(Activities::Currency)
+                            .string_len(3)
+                            .not_null()
+                            .default("INR"),
+                    )
+                    .add_column(
+                        ColumnDef::new(Activities::ExchangeRate)
+                            .decimal()
+                            .not_null()
+                            .default(1),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(ExchangeRates::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ExchangeRates::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(ExchangeRates::BaseCurrency).string_len(3).not_null())
+                    .col(ColumnDef::new(ExchangeRates::QuoteCurrency).string_len(3).not_null())
+                    .col(ColumnDef::new(ExchangeRates::Rate).decimal().not_null())
+                    .col(ColumnDef::new(ExchangeRates::RateDate).date().not_null())
+                    .col(
+                        ColumnDef::new(ExchangeRates::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_exchange_rates_pair_date")
+                    .table(ExchangeRates::Table)
+                    .col(ExchangeRates::BaseCurrency)
+                    .col(ExchangeRates::QuoteCurrency)
+                    .col(ExchangeRates::RateDate)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ExchangeRates::Table).to_owned())
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Activities::Table)
+                    .drop_column(Activities::Currency)
+                    .drop_column(Activities::ExchangeRate)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Groups::Table)
+                    .drop_column(Groups::Currency)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum Groups {
+    Table,
+    Currency,
+}
+
+#[derive(Iden)]
+enum Activities {
+    Table,
+    Currency,
+    ExchangeRate,
+}
+
+#[derive(Iden)]
+enum ExchangeRates {
+    Table,
+    Id,
+    BaseCurrency,
+    QuoteCurrency,
+    Rate,
+    RateDate,
+    CreatedAt,
+}
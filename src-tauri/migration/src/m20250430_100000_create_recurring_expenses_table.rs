@@ -0,0 +1,165 @@
+use sea_orm_migration::prelude::*;
+use sea_query::{Index, Table};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(RecurringExpenses::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(RecurringExpenses::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(RecurringExpenses::GroupId).uuid().not_null())
+                    .col(ColumnDef::new(RecurringExpenses::CreatorId).uuid().not_null())
+                    .col(ColumnDef::new(RecurringExpenses::Description).string().not_null())
+                    .col(ColumnDef::new(RecurringExpenses::Amount).decimal().not_null())
+                    .col(ColumnDef::new(RecurringExpenses::Currency).string_len(3).not_null())
+                    .col(ColumnDef::new(RecurringExpenses::PayerIds).json().not_null())
+                    .col(ColumnDef::new(RecurringExpenses::PayerAmounts).json().not_null())
+                    .col(ColumnDef::new(RecurringExpenses::SplitMembers).json().not_null())
+                    .col(ColumnDef::new(RecurringExpenses::SplitAmounts).json().not_null())
+                    .col(ColumnDef::new(RecurringExpenses::SplitType).string().not_null())
+                    .col(ColumnDef::new(RecurringExpenses::SplitValues).json().null())
+                    .col(ColumnDef::new(RecurringExpenses::ExpenseLogo).string().null())
+                    .col(ColumnDef::new(RecurringExpenses::Frequency).string().not_null())
+                    .col(ColumnDef::new(RecurringExpenses::CronExpression).string().null())
+                    .col(
+                        ColumnDef::new(RecurringExpenses::StartsAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(RecurringExpenses::EndsAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(RecurringExpenses::NextOccurrenceAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(RecurringExpenses::IsActive)
+                            .boolean()
+                            .not_null()
+                            .default(true),
+                    )
+                    .col(
+                        ColumnDef::new(RecurringExpenses::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(RecurringExpenses::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_recurring_expenses_next_occurrence_at")
+                    .table(RecurringExpenses::Table)
+                    .col(RecurringExpenses::NextOccurrenceAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Activities::Table)
+                    .add_column(ColumnDef::new(Activities::RecurringExpenseId).uuid().null())
+                    .add_column(
+                        ColumnDef::new(Activities::OccurrenceAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // one activity per template and occurrence, however often the scheduler retries
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_activities_recurring_occurrence")
+                    .table(Activities::Table)
+                    .col(Activities::RecurringExpenseId)
+                    .col(Activities::OccurrenceAt)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_activities_recurring_occurrence")
+                    .table(Activities::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Activities::Table)
+                    .drop_column(Activities::RecurringExpenseId)
+                    .drop_column(Activities::OccurrenceAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(RecurringExpenses::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum RecurringExpenses {
+    Table,
+    Id,
+    GroupId,
+    CreatorId,
+    Description,
+    Amount,
+    Currency,
+    PayerIds,
+    PayerAmounts,
+    SplitMembers,
+    SplitAmounts,
+    SplitType,
+    SplitValues,
+    ExpenseLogo,
+    Frequency,
+    CronExpression,
+    StartsAt,
+    EndsAt,
+    NextOccurrenceAt,
+    IsActive,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(Iden)]
+enum Activities {
+    Table,
+    RecurringExpenseId,
+    OccurrenceAt,
+}
@@ -0,0 +1,95 @@
+use sea_orm_migration::prelude::*;
+use sea_query::{Index, Table};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ActivityItems::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ActivityItems::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(ActivityItems::ActivityId).uuid().not_null())
+                    .col(ColumnDef::new(ActivityItems::Name).string().not_null())
+                    .col(ColumnDef::new(ActivityItems::UnitPrice).decimal().not_null())
+                    .col(ColumnDef::new(ActivityItems::Quantity).decimal().not_null())
+                    .col(ColumnDef::new(ActivityItems::AssignedMembers).json().not_null())
+                    .col(ColumnDef::new(ActivityItems::Position).integer().not_null())
+                    .col(
+                        ColumnDef::new(ActivityItems::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_activity_items_activity_id")
+                    .table(ActivityItems::Table)
+                    .col(ActivityItems::ActivityId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Activities::Table)
+                    .add_column(ColumnDef::new(Activities::Tax).decimal().null())
+                    .add_column(ColumnDef::new(Activities::ServiceCharge).decimal().null())
+                    .add_column(ColumnDef::new(Activities::Tip).decimal().null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Activities::Table)
+                    .drop_column(Activities::Tax)
+                    .drop_column(Activities::ServiceCharge)
+                    .drop_column(Activities::Tip)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(ActivityItems::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum ActivityItems {
+    Table,
+    Id,
+    ActivityId,
+    Name,
+    UnitPrice,
+    Quantity,
+    AssignedMembers,
+    Position,
+    CreatedAt,
+}
+
+#[derive(Iden)]
+enum Activities {
+    Table,
+    Tax,
+    ServiceCharge,
+    Tip,
+}
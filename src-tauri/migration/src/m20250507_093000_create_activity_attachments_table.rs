@@ -0,0 +1,82 @@
+use sea_orm_migration::prelude::*;
+use sea_query::{Index, Table};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ActivityAttachments::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ActivityAttachments::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(ActivityAttachments::ActivityId).uuid().not_null())
+                    .col(ColumnDef::new(ActivityAttachments::GroupId).uuid().not_null())
+                    .col(ColumnDef::new(ActivityAttachments::UploadedBy).uuid().not_null())
+                    .col(ColumnDef::new(ActivityAttachments::FileName).string().not_null())
+                    .col(ColumnDef::new(ActivityAttachments::ContentType).string().not_null())
+                    .col(ColumnDef::new(ActivityAttachments::SizeBytes).big_integer().not_null())
+                    .col(ColumnDef::new(ActivityAttachments::ContentHash).string_len(64).not_null())
+                    .col(ColumnDef::new(ActivityAttachments::StorageKey).string().not_null())
+                    .col(
+                        ColumnDef::new(ActivityAttachments::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // the same file attached twice to one activity is a single attachment
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_activity_attachments_activity_hash")
+                    .table(ActivityAttachments::Table)
+                    .col(ActivityAttachments::ActivityId)
+                    .col(ActivityAttachments::ContentHash)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_activity_attachments_content_hash")
+                    .table(ActivityAttachments::Table)
+                    .col(ActivityAttachments::ContentHash)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ActivityAttachments::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum ActivityAttachments {
+    Table,
+    Id,
+    ActivityId,
+    GroupId,
+    UploadedBy,
+    FileName,
+    ContentType,
+    SizeBytes,
+    ContentHash,
+    StorageKey,
+    CreatedAt,
+}
@@ -0,0 +1,161 @@
+use sea_orm_migration::prelude::*;
+use sea_query::{Index, Table};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+// built-in categories shared by every group: (id, name, logo, keywords)
+const BUILT_IN_CATEGORIES: &[(&str, &str, &str, &str)] = &[
+    ("5b0c6a4e-0d1f-4c55-9a0e-6c1f3f7a0001", "Food", "utensils",
+        r#"["restaurant","lunch","dinner","breakfast","cafe","coffee","pizza","burger","biryani","snacks","meal","swiggy","zomato","drinks","bar"]"#),
+    ("5b0c6a4e-0d1f-4c55-9a0e-6c1f3f7a0002", "Groceries", "shopping-basket",
+        r#"["grocery","groceries","supermarket","vegetables","fruits","milk","bigbasket","blinkit","zepto","dmart"]"#),
+    ("5b0c6a4e-0d1f-4c55-9a0e-6c1f3f7a0003", "Travel", "plane",
+        r#"["flight","hotel","airbnb","hostel","resort","trip","visa","train","travel","booking"]"#),
+    ("5b0c6a4e-0d1f-4c55-9a0e-6c1f3f7a0004", "Transport", "car",
+        r#"["uber","ola","rapido","taxi","cab","auto","metro","fuel","petrol","diesel","parking","toll"]"#),
+    ("5b0c6a4e-0d1f-4c55-9a0e-6c1f3f7a0005", "Rent", "home",
+        r#"["rent","lease","deposit","maintenance"]"#),
+    ("5b0c6a4e-0d1f-4c55-9a0e-6c1f3f7a0006", "Utilities", "bolt",
+        r#"["electricity","water","gas","internet","wifi","broadband","recharge","phone bill","mobile bill"]"#),
+    ("5b0c6a4e-0d1f-4c55-9a0e-6c1f3f7a0007", "Entertainment", "film",
+        r#"["movie","movies","netflix","spotify","prime","hotstar","concert","party","game","subscription"]"#),
+    ("5b0c6a4e-0d1f-4c55-9a0e-6c1f3f7a0008", "Shopping", "shopping-bag",
+        r#"["amazon","flipkart","myntra","clothes","shoes","shopping","gift"]"#),
+    ("5b0c6a4e-0d1f-4c55-9a0e-6c1f3f7a0009", "Health", "heart-pulse",
+        r#"["doctor","medicine","medicines","pharmacy","hospital","clinic","gym"]"#),
+    ("5b0c6a4e-0d1f-4c55-9a0e-6c1f3f7a0010", "Other", "receipt", r#"[]"#),
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Categories::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Categories::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    // null for built-in categories
+                    .col(ColumnDef::new(Categories::GroupId).uuid().null())
+                    .col(ColumnDef::new(Categories::Name).string().not_null())
+                    .col(ColumnDef::new(Categories::Logo).string().not_null())
+                    .col(ColumnDef::new(Categories::Keywords).json().not_null())
+                    .col(ColumnDef::new(Categories::CreatedBy).uuid().null())
+                    .col(
+                        ColumnDef::new(Categories::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Categories::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_categories_group_id")
+                    .table(Categories::Table)
+                    .col(Categories::GroupId)
+                    .to_owned(),
+            )
+            .await?;
+
+        let db = manager.get_connection();
+        for (id, name, logo, keywords) in BUILT_IN_CATEGORIES {
+            db.execute_unprepared(&format!(
+                "INSERT INTO categories (id, group_id, name, logo, keywords, created_by, created_at, updated_at) \
+                 VALUES ('{}', NULL, '{}', '{}', '{}', NULL, now(), now())",
+                id, name, logo, keywords
+            ))
+            .await?;
+        }
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Activities::Table)
+                    .add_column(ColumnDef::new(Activities::CategoryId).uuid().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_activities_category_id")
+                    .table(Activities::Table)
+                    .col(Activities::CategoryId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(RecurringExpenses::Table)
+                    .add_column(ColumnDef::new(RecurringExpenses::CategoryId).uuid().null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(RecurringExpenses::Table)
+                    .drop_column(RecurringExpenses::CategoryId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Activities::Table)
+                    .drop_column(Activities::CategoryId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(Categories::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum Categories {
+    Table,
+    Id,
+    GroupId,
+    Name,
+    Logo,
+    Keywords,
+    CreatedBy,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(Iden)]
+enum Activities {
+    Table,
+    CategoryId,
+}
+
+#[derive(Iden)]
+enum RecurringExpenses {
+    Table,
+    CategoryId,
+}
@@ -0,0 +1,71 @@
+use sea_orm_migration::prelude::*;
+use sea_query::{Index, Table};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ActivityComments::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ActivityComments::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(ActivityComments::ActivityId).uuid().not_null())
+                    .col(ColumnDef::new(ActivityComments::GroupId).uuid().not_null())
+                    .col(ColumnDef::new(ActivityComments::AuthorId).uuid().not_null())
+                    .col(ColumnDef::new(ActivityComments::Body).text().not_null())
+                    .col(ColumnDef::new(ActivityComments::EditedAt).timestamp_with_time_zone())
+                    .col(
+                        ColumnDef::new(ActivityComments::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ActivityComments::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // threads are always read in order for a single activity
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_activity_comments_activity_created")
+                    .table(ActivityComments::Table)
+                    .col(ActivityComments::ActivityId)
+                    .col(ActivityComments::CreatedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ActivityComments::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum ActivityComments {
+    Table,
+    Id,
+    ActivityId,
+    GroupId,
+    AuthorId,
+    Body,
+    EditedAt,
+    CreatedAt,
+    UpdatedAt,
+}
@@ -0,0 +1,85 @@
+use sea_orm_migration::prelude::*;
+use sea_query::{Index, Table};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ActivityHistory::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ActivityHistory::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(ActivityHistory::ActivityId).uuid().not_null())
+                    .col(ColumnDef::new(ActivityHistory::GroupId).uuid().not_null())
+                    .col(ColumnDef::new(ActivityHistory::Version).integer().not_null())
+                    .col(ColumnDef::new(ActivityHistory::Action).string().not_null())
+                    .col(ColumnDef::new(ActivityHistory::ActorId).uuid().not_null())
+                    .col(ColumnDef::new(ActivityHistory::Changes).json().not_null())
+                    .col(ColumnDef::new(ActivityHistory::Snapshot).json().not_null())
+                    .col(ColumnDef::new(ActivityHistory::Items).json().not_null())
+                    .col(ColumnDef::new(ActivityHistory::RevertedToVersion).integer())
+                    .col(
+                        ColumnDef::new(ActivityHistory::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // also stops two concurrent edits from both claiming the next version
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_activity_history_activity_version")
+                    .table(ActivityHistory::Table)
+                    .col(ActivityHistory::ActivityId)
+                    .col(ActivityHistory::Version)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_activity_history_group_created")
+                    .table(ActivityHistory::Table)
+                    .col(ActivityHistory::GroupId)
+                    .col(ActivityHistory::CreatedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ActivityHistory::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum ActivityHistory {
+    Table,
+    Id,
+    ActivityId,
+    GroupId,
+    Version,
+    Action,
+    ActorId,
+    Changes,
+    Snapshot,
+    Items,
+    RevertedToVersion,
+    CreatedAt,
+}
@@ -0,0 +1,59 @@
+use sea_orm_migration::prelude::*;
+use sea_query::{Index, Table};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Activities::Table)
+                    .add_column(ColumnDef::new(Activities::DeletedAt).timestamp_with_time_zone().null())
+                    .add_column(ColumnDef::new(Activities::DeletedBy).uuid().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        // the trash listing and the purge job both look activities up by deletion time
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_activities_deleted_at")
+                    .table(Activities::Table)
+                    .col(Activities::DeletedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_activities_deleted_at")
+                    .table(Activities::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Activities::Table)
+                    .drop_column(Activities::DeletedAt)
+                    .drop_column(Activities::DeletedBy)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum Activities {
+    Table,
+    DeletedAt,
+    DeletedBy,
+}
@@ -0,0 +1,54 @@
+use sea_orm_migration::prelude::*;
+use sea_query::Table;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(GroupMembers::Table)
+                    .add_column(
+                        ColumnDef::new(GroupMembers::Role)
+                            .string()
+                            .not_null()
+                            .default("member"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // admin-ness used to come from groups.creator_id, creators become owners
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "UPDATE group_members SET role = 'owner' \
+                 FROM groups \
+                 WHERE groups.id = group_members.group_id \
+                 AND groups.creator_id = group_members.member_id",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(GroupMembers::Table)
+                    .drop_column(GroupMembers::Role)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum GroupMembers {
+    Table,
+    Role,
+}
@@ -0,0 +1,88 @@
+use sea_orm_migration::prelude::*;
+use sea_query::{Index, Table};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(GroupInvites::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(GroupInvites::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(GroupInvites::GroupId).uuid().not_null())
+                    .col(ColumnDef::new(GroupInvites::Token).string().not_null())
+                    .col(ColumnDef::new(GroupInvites::CreatedBy).uuid().not_null())
+                    .col(
+                        ColumnDef::new(GroupInvites::ExpiresAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(GroupInvites::MaxUses).integer())
+                    .col(
+                        ColumnDef::new(GroupInvites::UseCount)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(ColumnDef::new(GroupInvites::RevokedAt).timestamp_with_time_zone())
+                    .col(
+                        ColumnDef::new(GroupInvites::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // invites are looked up by token when someone joins
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_group_invites_token")
+                    .table(GroupInvites::Table)
+                    .col(GroupInvites::Token)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_group_invites_group")
+                    .table(GroupInvites::Table)
+                    .col(GroupInvites::GroupId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(GroupInvites::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum GroupInvites {
+    Table,
+    Id,
+    GroupId,
+    Token,
+    CreatedBy,
+    ExpiresAt,
+    MaxUses,
+    UseCount,
+    RevokedAt,
+    CreatedAt,
+}
@@ -0,0 +1,53 @@
+use sea_orm_migration::prelude::*;
+use sea_query::Index;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // joins used to race, only the earliest of any duplicate memberships is kept
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "DELETE FROM group_members AS later \
+                 USING group_members AS earlier \
+                 WHERE later.group_id = earlier.group_id \
+                 AND later.member_id = earlier.member_id \
+                 AND (later.joined_at, later.id) > (earlier.joined_at, earlier.id)",
+            )
+            .await?;
+
+        // a member joins a group once, however many requests race to add them
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_group_members_group_member")
+                    .table(GroupMembers::Table)
+                    .col(GroupMembers::GroupId)
+                    .col(GroupMembers::MemberId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_group_members_group_member")
+                    .table(GroupMembers::Table)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum GroupMembers {
+    Table,
+    GroupId,
+    MemberId,
+}
@@ -0,0 +1,61 @@
+use sea_orm_migration::prelude::*;
+use sea_query::Index;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // one row per direction: a request, one side of a friendship or a block
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_friend_collections_user_friend")
+                    .table(FriendCollections::Table)
+                    .col(FriendCollections::UserId)
+                    .col(FriendCollections::FriendId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        // incoming requests and blocks are looked up from the other side
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_friend_collections_friend")
+                    .table(FriendCollections::Table)
+                    .col(FriendCollections::FriendId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_friend_collections_friend")
+                    .table(FriendCollections::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_friend_collections_user_friend")
+                    .table(FriendCollections::Table)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum FriendCollections {
+    Table,
+    UserId,
+    FriendId,
+}
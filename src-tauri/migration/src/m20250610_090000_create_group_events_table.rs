@@ -0,0 +1,75 @@
+use sea_orm_migration::prelude::*;
+use sea_query::{Index, Table};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(GroupEvents::Table)
+                    .if_not_exists()
+                    // sequential so clients can resume after the last id they saw
+                    .col(
+                        ColumnDef::new(GroupEvents::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(GroupEvents::EventType).string().not_null())
+                    .col(ColumnDef::new(GroupEvents::GroupId).uuid())
+                    .col(ColumnDef::new(GroupEvents::UserId).uuid())
+                    .col(ColumnDef::new(GroupEvents::Payload).json().not_null())
+                    .col(
+                        ColumnDef::new(GroupEvents::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_group_events_group_id")
+                    .table(GroupEvents::Table)
+                    .col(GroupEvents::GroupId)
+                    .col(GroupEvents::Id)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_group_events_user_id")
+                    .table(GroupEvents::Table)
+                    .col(GroupEvents::UserId)
+                    .col(GroupEvents::Id)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(GroupEvents::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum GroupEvents {
+    Table,
+    Id,
+    EventType,
+    GroupId,
+    UserId,
+    Payload,
+    CreatedAt,
+}
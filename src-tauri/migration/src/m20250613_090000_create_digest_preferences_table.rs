@@ -0,0 +1,59 @@
+use sea_orm_migration::prelude::*;
+use sea_query::Table;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // users without a row get no digests
+        manager
+            .create_table(
+                Table::create()
+                    .table(DigestPreferences::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(DigestPreferences::UserId)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(DigestPreferences::Frequency)
+                            .string()
+                            .not_null()
+                            .default("off"),
+                    )
+                    .col(ColumnDef::new(DigestPreferences::LastSentAt).timestamp_with_time_zone())
+                    .col(
+                        ColumnDef::new(DigestPreferences::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(DigestPreferences::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(DigestPreferences::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum DigestPreferences {
+    Table,
+    UserId,
+    Frequency,
+    LastSentAt,
+    CreatedAt,
+    UpdatedAt,
+}
@@ -0,0 +1,53 @@
+use sea_orm_migration::prelude::*;
+use sea_query::Index;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // oauth ids used to come from the client unchecked, so every existing one
+        // is marked unverified, which also keeps clashing ones out of the index;
+        // each account is linked again by verified email exactly once on its next
+        // sign-in, and accounts linked since are never taken over that way
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "UPDATE users \
+                 SET oauth_id = 'unverified:' || id::text",
+            )
+            .await?;
+
+        // users are looked up by the identity their provider vouches for
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_users_oauth_identity")
+                    .table(Users::Table)
+                    .col(Users::OauthProvider)
+                    .col(Users::OauthId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_users_oauth_identity")
+                    .table(Users::Table)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum Users {
+    Table,
+    OauthProvider,
+    OauthId,
+}
@@ -1,7 +1,14 @@
+use crate::controllers::activity_history_controller::record_activity_history;
+use crate::controllers::categories_controller::resolve_category;
+use crate::controllers::group_events_controller::record_group_event;
+use crate::controllers::notifications_controller::{notification_context, notify, NewNotification};
 use crate::custom_errors::app::AppError;
 use crate::entities::activities::{self, Entity as Activity};
+use crate::entities::{activity_items, categories, group_events};
+use crate::entities::sea_orm_active_enums::{GroupEventType, HistoryAction, NotificationType, SplitType};
 use crate::models::activities::{
-    ActivityRes, CreateActivityReq, DeleteActivityReq, UpdateActivityReq,GetActivitiesReq,
+    parse_payers, parse_splits, ActivityRes, CreateActivityReq, DeleteActivityReq, GetTrashReq, Itemization,
+    RestoreActivityReq, TrashedActivityRes, UpdateActivityReq,GetActivitiesReq,
 };
 use axum::{
     extract::{Extension, Json, Path},
@@ -13,71 +20,197 @@ use chrono::Utc;
 
 use crate::request_verifier::{
     activities::check_activity_exists_in_group,
-    groups::{check_group_exists, check_user_exists_in_group},
+    groups::{
+        check_group_exists, check_group_not_archived, check_user_can_write_in_group,
+        check_user_exists_in_group,
+        check_user_is_admin_in_group,
+    },
 };
+use crate::jobs::activity_purge::retention;
+use crate::utils::event_bus::EventBus;
+use crate::utils::exchange_rates::{find_rate, normalize_currency};
+use sea_orm::sea_query::{Expr, Func, SimpleExpr};
 use sea_orm::*;
 use serde_json::json;
 use uuid::Uuid;
 use dotenv::dotenv;
+use std::collections::HashMap;
 use std::env;
 
 pub async fn create_activity_handler(
     Extension(user_id): Extension<Uuid>,
     Extension(db): Extension<sea_orm::DatabaseConnection>,
+    Extension(bus): Extension<EventBus>,
     Json(mut payload): Json<CreateActivityReq>,
 ) -> Result<impl IntoResponse, AppError> {
     // println!("{}",user_id);
     payload.check()?;
     check_group_exists(&db, payload.group_id).await?;
-    check_user_exists_in_group(&db, payload.group_id, user_id).await?;
+    check_group_not_archived(&db, payload.group_id).await?;
+    check_user_can_write_in_group(&db, payload.group_id, user_id).await?;
+
+    let payers: Vec<(Uuid, rust_decimal::Decimal)> = match &payload.payers {
+        Some(payers) => payers.iter().map(|payer| (payer.payer_id, payer.amount)).collect(),
+        None => vec![(user_id, payload.amount)],
+    };
+    check_payers_in_group(&db, payload.group_id, &payers).await?;
+    check_split_members_in_group(&db, payload.group_id, &payload.split_members).await?;
+    let itemization = payload.itemization()?;
+
+    let (currency, exchange_rate) = resolve_currency(
+        &db,
+        payload.group_id,
+        payload.currency.as_deref(),
+        Utc::now().date_naive(),
+    )
+    .await?;
+
+    let category = resolve_category(&db, payload.group_id, payload.category_id, &payload.description).await?;
+    // an expense without its own logo shows its category's
+    let expense_logo = payload
+        .expense_logo
+        .or_else(|| category.as_ref().map(|category| category.logo.clone()));
 
     let new_activity = activities::ActiveModel {
         id: Set(Uuid::new_v4()),
         description: Set(payload.description),
-        paid_by_id: Set(user_id),
+        paid_by_id: Set(primary_payer(&payers, user_id)),
+        payer_ids: Set(json!(payers.iter().map(|(payer_id, _)| *payer_id).collect::<Vec<Uuid>>())),
+        payer_amounts: Set(json!(payers.iter().map(|(_, amount)| *amount).collect::<Vec<_>>())),
         group_id: Set(payload.group_id),
         time: Set(Utc::now().into()),
         amount: Set(payload.amount),
+        currency: Set(currency),
+        exchange_rate: Set(exchange_rate),
         split_members: Set(json!(payload.split_members)),
         split_amounts: Set(json!(payload.split_amounts)),
+        split_type: Set(payload.split_type),
+        split_values: Set(payload.split_values.map(|values| json!(values))),
+        tax: Set(itemization.as_ref().map(|itemization| itemization.tax)),
+        service_charge: Set(itemization.as_ref().map(|itemization| itemization.service_charge)),
+        tip: Set(itemization.as_ref().map(|itemization| itemization.tip)),
         user_involvement: Set(payload.split_members.contains(&user_id)),
-        expense_logo: Set(payload.expense_logo),
+        category_id: Set(category.map(|category| category.id)),
+        expense_logo: Set(expense_logo),
+        recurring_expense_id: Set(None),
+        occurrence_at: Set(None),
+        deleted_at: Set(None),
+        deleted_by: Set(None),
         created_at: Set(Utc::now().into()),
         updated_at: Set(Utc::now().into()),
     };
 
+    let txn = db
+        .begin()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
     let inserted = new_activity
-        .insert(&db)
+        .insert(&txn)
         .await
         .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    let items = match &itemization {
+        Some(itemization) => Some(replace_items(&txn, inserted.id, itemization).await?),
+        None => None,
+    };
 
-     update_group_total_expense(&db, payload.group_id).await?;
+    record_activity_history(
+        &txn,
+        HistoryAction::Created,
+        user_id,
+        None,
+        Some((&inserted, items.as_deref().unwrap_or_default())),
+        None,
+    )
+    .await?;
+    let mut events =
+        notify_activity_change(&txn, NotificationType::ExpenseAdded, user_id, &inserted, Vec::new()).await?;
+    update_group_total_expense(&txn, payload.group_id).await?;
+
+    let response = match items {
+        Some(items) => ActivityRes::from(inserted).with_items(items),
+        None => ActivityRes::from(inserted),
+    };
+    events.push(record_group_event(&txn, GroupEventType::ActivityCreated, payload.group_id, &response).await?);
 
-    Ok((StatusCode::CREATED, AxumJson(ActivityRes::from(inserted))))
+    txn.commit()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    bus.publish(events);
+
+    Ok((StatusCode::CREATED, AxumJson(response)))
 }
 
 pub async fn update_activity_handler(
     Extension(user_id): Extension<Uuid>,
     Extension(db): Extension<sea_orm::DatabaseConnection>,
+    Extension(bus): Extension<EventBus>,
     Json(payload): Json<UpdateActivityReq>,
 ) -> Result<impl IntoResponse, AppError> {
     payload.check()?;
     check_group_exists(&db, payload.group_id).await?;
-    check_user_exists_in_group(&db, payload.group_id, user_id).await?;
+    check_group_not_archived(&db, payload.group_id).await?;
+    check_user_can_write_in_group(&db, payload.group_id, user_id).await?;
     check_activity_exists_in_group(&db, payload.id, payload.group_id).await?;
 
     let activity = Activity::find_by_id(payload.id)
+        .filter(activities::Column::DeletedAt.is_null())
         .one(&db)
         .await
         .map_err(|e| AppError::DatabaseError(e.to_string()))?
         .ok_or(AppError::NotFound("Activity not found".to_string()))?;
 
-    if activity.paid_by_id != user_id {
+    if !is_payer(&activity, user_id)? {
         return Err(AppError::Unauthorized(
             "You are not authorized to update this activity".to_string(),
         ));
     }
 
+    let resolved_payers = payload.resolve_payers(&activity)?;
+    if let Some(payers) = &resolved_payers {
+        check_payers_in_group(&db, payload.group_id, payers).await?;
+    }
+
+    let resolved_split = if payload.changes_split() {
+        Some(payload.resolve_split(&activity)?)
+    } else {
+        None
+    };
+    if let Some(split) = &resolved_split {
+        check_split_members_in_group(&db, payload.group_id, &split.split_members).await?;
+    }
+
+    let resolved_currency = match &payload.currency {
+        // converted at the rate in force when the expense happened, not today's
+        Some(currency) => Some(
+            resolve_currency(
+                &db,
+                payload.group_id,
+                Some(&normalize_currency(currency)?),
+                activity.time.date_naive(),
+            )
+            .await?,
+        ),
+        None => None,
+    };
+
+    let resolved_category = match payload.category_id {
+        Some(Some(category_id)) => Some(
+            resolve_category(&db, payload.group_id, Some(category_id), &activity.description).await?,
+        ),
+        Some(None) => Some(None),
+        None => None,
+    };
+    let old_category_logo = match activity.category_id {
+        Some(category_id) if resolved_category.is_some() => categories::Entity::find_by_id(category_id)
+            .one(&db)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?
+            .map(|category| category.logo),
+        _ => None,
+    };
+
+    let before = activity.clone();
     let mut activity_model = activity.into_active_model();
     let old_activity = activity_model.clone();
 
@@ -89,13 +222,40 @@ pub async fn update_activity_handler(
         activity_model.amount = Set(amount);
     }
 
-    if let Some(split_members) = payload.split_members {
-        activity_model.split_members = Set(json!(split_members));
-        activity_model.user_involvement = Set(split_members.contains(&user_id));
+    if let Some((currency, exchange_rate)) = resolved_currency {
+        activity_model.currency = Set(currency);
+        activity_model.exchange_rate = Set(exchange_rate);
+    }
+
+    if let Some(payers) = resolved_payers {
+        activity_model.paid_by_id = Set(primary_payer(&payers, user_id));
+        activity_model.payer_ids = Set(json!(payers.iter().map(|(payer_id, _)| *payer_id).collect::<Vec<Uuid>>()));
+        activity_model.payer_amounts = Set(json!(payers.iter().map(|(_, amount)| *amount).collect::<Vec<_>>()));
     }
 
-    if let Some(split_amounts) = payload.split_amounts {
-        activity_model.split_amounts = Set(json!(split_amounts));
+    let itemization = match resolved_split {
+        Some(split) => {
+            activity_model.user_involvement = Set(split.split_members.contains(&user_id));
+            activity_model.split_members = Set(json!(split.split_members));
+            activity_model.split_amounts = Set(json!(split.split_amounts));
+            activity_model.split_type = Set(split.split_type);
+            activity_model.split_values = Set(split.split_values.map(|values| json!(values)));
+            activity_model.tax = Set(split.itemization.as_ref().map(|itemization| itemization.tax));
+            activity_model.service_charge =
+                Set(split.itemization.as_ref().map(|itemization| itemization.service_charge));
+            activity_model.tip = Set(split.itemization.as_ref().map(|itemization| itemization.tip));
+            Some(split.itemization)
+        }
+        None => None,
+    };
+
+    if let Some(category) = resolved_category {
+        // a logo that only came from the old category follows the new one
+        let current_logo = activity_model.expense_logo.clone().unwrap();
+        if payload.expense_logo.is_none() && (current_logo.is_none() || current_logo == old_category_logo) {
+            activity_model.expense_logo = Set(category.as_ref().map(|category| category.logo.clone()));
+        }
+        activity_model.category_id = Set(category.map(|category| category.id));
     }
 
     if let Some(expense_logo) = payload.expense_logo {
@@ -108,69 +268,251 @@ pub async fn update_activity_handler(
 
     activity_model.updated_at = Set(Utc::now().into());
 
+    let txn = db
+        .begin()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    let before_items = load_items(&txn, vec![before.id]).await?.remove(&before.id).unwrap_or_default();
+
     let updated = activity_model
-        .update(&db)
+        .update(&txn)
         .await
         .map_err(|e| AppError::DatabaseError(e.to_string()))?;
 
-    update_group_total_expense(&db, payload.group_id).await?;
+    // a recomputed split replaces the stored items, or drops them when it is no longer itemized
+    let items = match itemization {
+        Some(Some(itemization)) => replace_items(&txn, updated.id, &itemization).await?,
+        Some(None) => {
+            delete_items(&txn, updated.id).await?;
+            Vec::new()
+        }
+        None => before_items.clone(),
+    };
 
-    Ok((StatusCode::OK, AxumJson(ActivityRes::from(updated))))
+    record_activity_history(
+        &txn,
+        HistoryAction::Updated,
+        user_id,
+        Some((&before, &before_items)),
+        Some((&updated, &items)),
+        None,
+    )
+    .await?;
+    // people dropped from the split hear about it too
+    let mut events = notify_activity_change(
+        &txn,
+        NotificationType::ExpenseUpdated,
+        user_id,
+        &updated,
+        involved_users(&before)?,
+    )
+    .await?;
+    update_group_total_expense(&txn, payload.group_id).await?;
+
+    let response = if updated.split_type == SplitType::Itemized {
+        ActivityRes::from(updated).with_items(items)
+    } else {
+        ActivityRes::from(updated)
+    };
+    events.push(record_group_event(&txn, GroupEventType::ActivityUpdated, payload.group_id, &response).await?);
+
+    txn.commit()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    bus.publish(events);
+
+    Ok((StatusCode::OK, AxumJson(response)))
 }
 
 pub async fn delete_activity_handler(
     Extension(user_id): Extension<Uuid>,
     Extension(db): Extension<sea_orm::DatabaseConnection>,
+    Extension(bus): Extension<EventBus>,
     Json(payload): Json<DeleteActivityReq>,
 ) -> Result<impl IntoResponse, AppError> {
     payload.check()?;
     check_group_exists(&db, payload.group_id).await?;
+    check_group_not_archived(&db, payload.group_id).await?;
     check_activity_exists_in_group(&db, payload.activity_id, payload.group_id).await?;
-    check_user_exists_in_group(&db, payload.group_id, user_id).await?;
+    check_user_can_write_in_group(&db, payload.group_id, user_id).await?;
 
     let activity = Activity::find_by_id(payload.activity_id)
+        .filter(activities::Column::DeletedAt.is_null())
         .one(&db)
         .await
         .map_err(|e| AppError::DatabaseError(e.to_string()))?
         .ok_or(AppError::NotFound("Activity not found".to_string()))?;
-    if activity.paid_by_id != user_id {
+    if !is_payer(&activity, user_id)? {
         return Err(AppError::Unauthorized(
             "You are not authorized to delete this activity".to_string(),
         ));
     }
-    let activity_amount = activity.amount;
 
-    let delete_result = Activity::delete_by_id(payload.activity_id)
-        .exec(&db)
+    let txn = db
+        .begin()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    let items = load_items(&txn, vec![activity.id]).await?.remove(&activity.id).unwrap_or_default();
+    record_activity_history(
+        &txn,
+        HistoryAction::Deleted,
+        user_id,
+        Some((&activity, &items)),
+        None,
+        None,
+    )
+    .await?;
+    let mut events =
+        notify_activity_change(&txn, NotificationType::ExpenseDeleted, user_id, &activity, Vec::new()).await?;
+    events.push(
+        record_group_event(
+            &txn,
+            GroupEventType::ActivityDeleted,
+            payload.group_id,
+            json!({ "activity_id": activity.id }),
+        )
+        .await?,
+    );
+
+    // moved to the trash, items, comments and attachments stay until it is purged
+    let delete_result = Activity::update_many()
+        .col_expr(activities::Column::DeletedAt, Expr::value(Utc::now()))
+        .col_expr(activities::Column::DeletedBy, Expr::value(user_id))
+        .filter(activities::Column::Id.eq(payload.activity_id))
+        .filter(activities::Column::DeletedAt.is_null())
+        .exec(&txn)
         .await
         .map_err(|e| AppError::DatabaseError(e.to_string()))?;
 
     if delete_result.rows_affected == 0 {
         return Err(AppError::NotFound("Activity not found".to_string()));
     }
-    use crate::entities::groups::{self, Entity as Group};
-    
-    let group = Group::find_by_id(payload.group_id)
+
+    update_group_total_expense(&txn, payload.group_id).await?;
+
+    txn.commit()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    bus.publish(events);
+
+    Ok((StatusCode::OK, Json("Activity moved to trash")))
+}
+
+pub async fn get_trash_handler(
+    Extension(user_id): Extension<Uuid>,
+    Extension(db): Extension<sea_orm::DatabaseConnection>,
+    Json(payload): Json<GetTrashReq>,
+) -> Result<impl IntoResponse, AppError> {
+    payload.check()?;
+    check_group_exists(&db, payload.group_id).await?;
+    check_user_exists_in_group(&db, payload.group_id, user_id).await?;
+
+    let retention = retention()?;
+    let trashed = Activity::find()
+        .filter(activities::Column::GroupId.eq(payload.group_id))
+        .filter(activities::Column::DeletedAt.is_not_null())
+        .order_by_desc(activities::Column::DeletedAt)
+        .all(&db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    let itemized: Vec<Uuid> = trashed
+        .iter()
+        .filter(|activity| activity.split_type == SplitType::Itemized)
+        .map(|activity| activity.id)
+        .collect();
+    let mut items = load_items(&db, itemized).await?;
+
+    let trash: Vec<TrashedActivityRes> = trashed
+        .into_iter()
+        .map(|activity| {
+            let activity_items = items.remove(&activity.id);
+            TrashedActivityRes::new(activity, activity_items, retention)
+        })
+        .collect();
+
+    Ok((StatusCode::OK, AxumJson(trash)))
+}
+
+pub async fn restore_activity_handler(
+    Extension(user_id): Extension<Uuid>,
+    Extension(db): Extension<sea_orm::DatabaseConnection>,
+    Extension(bus): Extension<EventBus>,
+    Json(payload): Json<RestoreActivityReq>,
+) -> Result<impl IntoResponse, AppError> {
+    payload.check()?;
+    check_group_exists(&db, payload.group_id).await?;
+    check_group_not_archived(&db, payload.group_id).await?;
+    check_user_can_write_in_group(&db, payload.group_id, user_id).await?;
+
+    let activity = Activity::find_by_id(payload.activity_id)
+        .filter(activities::Column::GroupId.eq(payload.group_id))
+        .filter(activities::Column::DeletedAt.is_not_null())
         .one(&db)
         .await
         .map_err(|e| AppError::DatabaseError(e.to_string()))?
-        .ok_or(AppError::NotFound("Group not found".to_string()))?;
+        .ok_or(AppError::NotFound("Activity not found in trash".to_string()))?;
+
+    if !is_payer(&activity, user_id)? {
+        check_user_is_admin_in_group(&db, payload.group_id, user_id)
+            .await
+            .map_err(|_| {
+                AppError::Unauthorized("You are not authorized to restore this activity".to_string())
+            })?;
+    }
+    // the purge job may simply not have run yet
+    let retention = retention()?;
+    if activity
+        .deleted_at
+        .is_some_and(|deleted_at| deleted_at.with_timezone(&Utc) + retention < Utc::now())
+    {
+        return Err(AppError::ValidationError(
+            "The retention window for this activity has passed".to_string(),
+        ));
+    }
+    check_payers_in_group(&db, payload.group_id, &parse_payers(&activity)?).await?;
 
-     let current_total = group.total_expense;
-    
-    let mut group_model = group.into_active_model();
-    
-    let new_total = current_total - activity_amount;
-    group_model.total_expense = sea_orm::ActiveValue::Set(new_total);
-    group_model.updated_at = sea_orm::ActiveValue::Set(Utc::now().into());
-    
-    group_model
-        .update(&db)
+    let txn = db
+        .begin()
         .await
         .map_err(|e| AppError::DatabaseError(e.to_string()))?;
 
-        
-    Ok((StatusCode::OK, Json("Activity Deleted successfully")))
+    let items = load_items(&txn, vec![activity.id]).await?.remove(&activity.id).unwrap_or_default();
+    let mut activity_model = activity.clone().into_active_model();
+    activity_model.deleted_at = Set(None);
+    activity_model.deleted_by = Set(None);
+    activity_model.updated_at = Set(Utc::now().into());
+    let restored = activity_model
+        .update(&txn)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    record_activity_history(
+        &txn,
+        HistoryAction::Restored,
+        user_id,
+        Some((&activity, &items)),
+        Some((&restored, &items)),
+        None,
+    )
+    .await?;
+    update_group_total_expense(&txn, payload.group_id).await?;
+
+    let response = if restored.split_type == SplitType::Itemized {
+        ActivityRes::from(restored).with_items(items)
+    } else {
+        ActivityRes::from(restored)
+    };
+    let event = record_group_event(&txn, GroupEventType::ActivityRestored, payload.group_id, &response).await?;
+
+    txn.commit()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    bus.publish(vec![event]);
+
+    Ok((StatusCode::OK, AxumJson(response)))
 }
 
 
@@ -190,9 +532,13 @@ pub async fn get_all_activities_handler(
     .parse()
     .map_err(|_| AppError::ConfigError("Invalid PAGE_SIZE value".to_string()))?;
 
-    let paginator = activities::Entity::find()
-    .filter(activities::Column::GroupId.eq(payload.group_id))
-    .paginate(&db, page_size);
+    let mut query = activities::Entity::find()
+        .filter(activities::Column::GroupId.eq(payload.group_id))
+        .filter(activities::Column::DeletedAt.is_null());
+    if let Some(category_id) = payload.category_id {
+        query = query.filter(activities::Column::CategoryId.eq(category_id));
+    }
+    let paginator = query.paginate(&db, page_size);
 
     let mut all_activites = Vec::new();
     let mut page_stream = paginator;
@@ -206,9 +552,19 @@ pub async fn get_all_activities_handler(
         all_activites.extend(activities_page);
     }
 
+    let itemized: Vec<Uuid> = all_activites
+        .iter()
+        .filter(|activity| activity.split_type == SplitType::Itemized)
+        .map(|activity| activity.id)
+        .collect();
+    let mut items = load_items(&db, itemized).await?;
+
     let activity_responses: Vec<ActivityRes> = all_activites
         .into_iter()
-        .map(ActivityRes::from)
+        .map(|activity| match items.remove(&activity.id) {
+            Some(activity_items) => ActivityRes::from(activity).with_items(activity_items),
+            None => ActivityRes::from(activity),
+        })
         .collect();
 
     Ok((StatusCode::OK, AxumJson(activity_responses)))
@@ -216,15 +572,23 @@ pub async fn get_all_activities_handler(
 
 
 //helper
-async fn update_group_total_expense(
-    db: &DatabaseConnection,
+pub async fn update_group_total_expense<C: ConnectionTrait>(
+    db: &C,
     group_id: Uuid,
 ) -> Result<(), AppError> {
 
+    // every activity is summed in the group's currency
     let total: Option<rust_decimal::Decimal> = Activity::find()
         .filter(activities::Column::GroupId.eq(group_id))
+        .filter(activities::Column::DeletedAt.is_null())
         .select_only()
-        .column_as(activities::Column::Amount.sum(), "total_amount")
+        .column_as(
+            SimpleExpr::from(Func::sum(
+                Expr::col(activities::Column::Amount)
+                    .mul(Expr::col(activities::Column::ExchangeRate)),
+            )),
+            "total_amount",
+        )
         .into_tuple()
         .one(db)
         .await
@@ -232,9 +596,11 @@ async fn update_group_total_expense(
 
 
 
-    let total = total.unwrap_or_else(|| rust_decimal::Decimal::new(0, 0));
+    let total = total
+        .unwrap_or_else(|| rust_decimal::Decimal::new(0, 0))
+        .round_dp(2);
     
-    use crate::entities::groups::{self, Entity as Group};
+    use crate::entities::groups::Entity as Group;
     
     let group = Group::find_by_id(group_id)
         .one(db)
@@ -252,4 +618,167 @@ async fn update_group_total_expense(
         .map_err(|e| AppError::DatabaseError(e.to_string()))?;
 
     Ok(())
-}
\ No newline at end of file
+}
+
+pub fn is_payer(activity: &activities::Model, user_id: Uuid) -> Result<bool, AppError> {
+    if activity.paid_by_id == user_id {
+        return Ok(true);
+    }
+    Ok(parse_payers(activity)?
+        .iter()
+        .any(|(payer_id, _)| *payer_id == user_id))
+}
+
+/// Everyone who paid for or shares in `activity`.
+pub fn involved_users(activity: &activities::Model) -> Result<Vec<Uuid>, AppError> {
+    let mut user_ids = vec![activity.paid_by_id];
+    user_ids.extend(parse_payers(activity)?.into_iter().map(|(payer_id, _)| payer_id));
+    user_ids.extend(parse_splits(activity)?.into_iter().map(|(member_id, _)| member_id));
+    Ok(user_ids)
+}
+
+/// Tells everyone involved in `activity`, and anyone in `also_notify`, that
+/// `actor_id` added, edited or deleted it.
+pub async fn notify_activity_change<C: ConnectionTrait>(
+    db: &C,
+    type_: NotificationType,
+    actor_id: Uuid,
+    activity: &activities::Model,
+    also_notify: Vec<Uuid>,
+) -> Result<Vec<group_events::Model>, AppError> {
+    let (actor_name, group) = notification_context(db, actor_id, activity.group_id).await?;
+    let verb = match type_ {
+        NotificationType::ExpenseAdded => "added",
+        NotificationType::ExpenseUpdated => "edited",
+        _ => "deleted",
+    };
+
+    let mut recipients = involved_users(activity)?;
+    recipients.extend(also_notify);
+    notify(
+        db,
+        recipients,
+        NewNotification {
+            type_,
+            actor_id,
+            message: format!("{} {} \"{}\" in {}", actor_name, verb, activity.description, group.group_name),
+            group_id: Some(group.id),
+            activity_id: Some(activity.id),
+        },
+    )
+    .await
+}
+
+// the caller stays the primary payer whenever they paid something
+fn primary_payer(payers: &[(Uuid, rust_decimal::Decimal)], user_id: Uuid) -> Uuid {
+    payers
+        .iter()
+        .map(|(payer_id, _)| *payer_id)
+        .find(|payer_id| *payer_id == user_id)
+        .or_else(|| payers.first().map(|(payer_id, _)| *payer_id))
+        .unwrap_or(user_id)
+}
+
+pub async fn check_payers_in_group(
+    db: &DatabaseConnection,
+    group_id: Uuid,
+    payers: &[(Uuid, rust_decimal::Decimal)],
+) -> Result<(), AppError> {
+    for (payer_id, _) in payers {
+        check_user_exists_in_group(db, group_id, *payer_id)
+            .await
+            .map_err(|_| AppError::UserNotInGroup("Payer is not a member of this group".into()))?;
+    }
+    Ok(())
+}
+
+pub async fn check_split_members_in_group(
+    db: &DatabaseConnection,
+    group_id: Uuid,
+    split_members: &[Uuid],
+) -> Result<(), AppError> {
+    for member_id in split_members {
+        check_user_exists_in_group(db, group_id, *member_id)
+            .await
+            .map_err(|_| AppError::UserNotInGroup("Split member is not a member of this group".into()))?;
+    }
+    Ok(())
+}
+
+/// The activity's currency, defaulting to the group's, and the rate that
+/// converts it into the group's currency on the given day.
+pub async fn resolve_currency(
+    db: &DatabaseConnection,
+    group_id: Uuid,
+    currency: Option<&str>,
+    on: chrono::NaiveDate,
+) -> Result<(String, rust_decimal::Decimal), AppError> {
+    use crate::entities::groups::Entity as Group;
+
+    let group = Group::find_by_id(group_id)
+        .one(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?
+        .ok_or(AppError::NotFound("Group not found".to_string()))?;
+
+    let currency = currency.unwrap_or(&group.currency).to_string();
+    let exchange_rate = find_rate(db, &currency, &group.currency, on).await?;
+    Ok((currency, exchange_rate))
+}
+
+async fn replace_items<C: ConnectionTrait>(
+    db: &C,
+    activity_id: Uuid,
+    itemization: &Itemization,
+) -> Result<Vec<activity_items::Model>, AppError> {
+    delete_items(db, activity_id).await?;
+
+    let mut items = Vec::with_capacity(itemization.items.len());
+    for (position, item) in itemization.items.iter().enumerate() {
+        let inserted = activity_items::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            activity_id: Set(activity_id),
+            name: Set(item.name.trim().to_string()),
+            unit_price: Set(item.unit_price),
+            quantity: Set(item.quantity),
+            assigned_members: Set(json!(item.assigned_members)),
+            position: Set(position as i32),
+            created_at: Set(Utc::now().into()),
+        }
+        .insert(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        items.push(inserted);
+    }
+    Ok(items)
+}
+
+pub async fn delete_items<C: ConnectionTrait>(db: &C, activity_id: Uuid) -> Result<(), AppError> {
+    activity_items::Entity::delete_many()
+        .filter(activity_items::Column::ActivityId.eq(activity_id))
+        .exec(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    Ok(())
+}
+
+pub async fn load_items<C: ConnectionTrait>(
+    db: &C,
+    activity_ids: Vec<Uuid>,
+) -> Result<HashMap<Uuid, Vec<activity_items::Model>>, AppError> {
+    let mut items: HashMap<Uuid, Vec<activity_items::Model>> = HashMap::new();
+    if activity_ids.is_empty() {
+        return Ok(items);
+    }
+
+    let rows = activity_items::Entity::find()
+        .filter(activity_items::Column::ActivityId.is_in(activity_ids))
+        .order_by_asc(activity_items::Column::Position)
+        .all(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    for item in rows {
+        items.entry(item.activity_id).or_default().push(item);
+    }
+    Ok(items)
+}
@@ -0,0 +1,278 @@
+use chrono::Utc;
+use crate::controllers::activities_controller::{
+    check_payers_in_group, check_split_members_in_group, delete_items, involved_users, is_payer,
+    load_items, notify_activity_change, update_group_total_expense,
+};
+use crate::controllers::categories_controller::resolve_category;
+use crate::controllers::group_events_controller::record_group_event;
+use crate::custom_errors::app::AppError;
+use crate::entities::sea_orm_active_enums::{
+    GroupEventType, HistoryAction, NotificationType, SplitType,
+};
+use crate::entities::{activities, activity_history, activity_items};
+use crate::models::activities::{parse_payers, parse_splits, ActivityRes};
+use crate::models::activity_history::{
+    ActivityHistoryPageRes, ActivityHistoryRes, GetActivityHistoryReq, GetGroupHistoryReq,
+    RevertActivityReq,
+};
+use crate::request_verifier::groups::{
+    check_group_exists, check_group_not_archived, check_user_can_write_in_group,
+    check_user_exists_in_group, check_user_is_admin_in_group,
+};
+use crate::utils::activity_history::diff;
+use crate::utils::event_bus::EventBus;
+use axum::{
+    extract::{Extension, Json},
+    http::StatusCode,
+    response::IntoResponse,
+    Json as AxumJson,
+};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait,
+    IntoActiveModel, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Set, TransactionTrait,
+};
+use serde_json::json;
+use std::env;
+use uuid::Uuid;
+
+pub async fn get_activity_history_handler(
+    Extension(user_id): Extension<Uuid>,
+    Extension(db): Extension<DatabaseConnection>,
+    Json(payload): Json<GetActivityHistoryReq>,
+) -> Result<impl IntoResponse, AppError> {
+    payload.check()?;
+    check_group_exists(&db, payload.group_id).await?;
+    check_user_exists_in_group(&db, payload.group_id, user_id).await?;
+
+    // deleted activities keep their history, so this does not require the activity to exist
+    let entries: Vec<ActivityHistoryRes> = activity_history::Entity::find()
+        .filter(activity_history::Column::ActivityId.eq(payload.activity_id))
+        .filter(activity_history::Column::GroupId.eq(payload.group_id))
+        .order_by_asc(activity_history::Column::Version)
+        .all(&db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?
+        .into_iter()
+        .map(ActivityHistoryRes::from)
+        .collect();
+
+    if entries.is_empty() {
+        return Err(AppError::NotFound("No history found for this activity".to_string()));
+    }
+
+    Ok((StatusCode::OK, AxumJson(entries)))
+}
+
+pub async fn get_group_history_handler(
+    Extension(user_id): Extension<Uuid>,
+    Extension(db): Extension<DatabaseConnection>,
+    Json(payload): Json<GetGroupHistoryReq>,
+) -> Result<impl IntoResponse, AppError> {
+    payload.check()?;
+    check_group_exists(&db, payload.group_id).await?;
+    check_user_exists_in_group(&db, payload.group_id, user_id).await?;
+
+    let page_size: u64 = env::var("PAGE_SIZE")
+        .map_err(|_| AppError::ConfigError("PAGE_SIZE must be set".to_string()))?
+        .trim()
+        .parse()
+        .map_err(|_| AppError::ConfigError("Invalid PAGE_SIZE value".to_string()))?;
+
+    let paginator = activity_history::Entity::find()
+        .filter(activity_history::Column::GroupId.eq(payload.group_id))
+        .order_by_desc(activity_history::Column::CreatedAt)
+        .order_by_desc(activity_history::Column::Version)
+        .paginate(&db, page_size);
+
+    let totals = paginator
+        .num_items_and_pages()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    let entries = paginator
+        .fetch_page(payload.page)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?
+        .into_iter()
+        .map(ActivityHistoryRes::from)
+        .collect();
+
+    Ok((
+        StatusCode::OK,
+        AxumJson(ActivityHistoryPageRes {
+            entries,
+            page: payload.page,
+            total_pages: totals.number_of_pages,
+            total_entries: totals.number_of_items,
+        }),
+    ))
+}
+
+pub async fn revert_activity_handler(
+    Extension(user_id): Extension<Uuid>,
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(bus): Extension<EventBus>,
+    Json(payload): Json<RevertActivityReq>,
+) -> Result<impl IntoResponse, AppError> {
+    payload.check()?;
+    check_group_exists(&db, payload.group_id).await?;
+    check_group_not_archived(&db, payload.group_id).await?;
+    check_user_can_write_in_group(&db, payload.group_id, user_id).await?;
+
+    let entry = activity_history::Entity::find()
+        .filter(activity_history::Column::ActivityId.eq(payload.activity_id))
+        .filter(activity_history::Column::GroupId.eq(payload.group_id))
+        .filter(activity_history::Column::Version.eq(payload.version))
+        .one(&db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?
+        .ok_or(AppError::NotFound("Version not found".to_string()))?;
+    if entry.action == HistoryAction::Deleted {
+        return Err(AppError::ValidationError(
+            "Cannot revert to the deletion of an activity".to_string(),
+        ));
+    }
+
+    let target: activities::Model = serde_json::from_value(entry.snapshot)
+        .map_err(|e| AppError::DatabaseError(format!("Malformed history snapshot: {}", e)))?;
+    let target_items: Vec<activity_items::Model> = serde_json::from_value(entry.items)
+        .map_err(|e| AppError::DatabaseError(format!("Malformed history items: {}", e)))?;
+
+    // an activity in the trash has to be restored first, a purged one is gone for good
+    let current = activities::Entity::find_by_id(payload.activity_id)
+        .filter(activities::Column::DeletedAt.is_null())
+        .one(&db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?
+        .ok_or(AppError::NotFound("Activity not found".to_string()))?;
+
+    if !is_payer(&current, user_id)? {
+        check_user_is_admin_in_group(&db, payload.group_id, user_id)
+            .await
+            .map_err(|_| {
+                AppError::Unauthorized("You are not authorized to revert this activity".to_string())
+            })?;
+    }
+    // the group may have moved on since the snapshot, so it is checked like an update
+    check_payers_in_group(&db, payload.group_id, &parse_payers(&target)?).await?;
+    let split_members: Vec<Uuid> = parse_splits(&target)?.into_iter().map(|(member_id, _)| member_id).collect();
+    check_split_members_in_group(&db, payload.group_id, &split_members).await?;
+    if let Some(category_id) = target.category_id {
+        resolve_category(&db, payload.group_id, Some(category_id), &target.description).await?;
+    }
+
+    let txn = db
+        .begin()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    let current_items = load_items(&txn, vec![current.id]).await?.remove(&current.id).unwrap_or_default();
+
+    let mut restored = target;
+    restored.created_at = current.created_at;
+    restored.updated_at = Utc::now().into();
+    restored.deleted_at = None;
+    restored.deleted_by = None;
+    // the template link only ever changes when the template is deleted
+    restored.recurring_expense_id = current.recurring_expense_id;
+    restored.occurrence_at = current.occurrence_at;
+    if diff(Some((&current, &current_items)), Some((&restored, &target_items)))
+        .as_object()
+        .is_some_and(|changes| changes.is_empty())
+    {
+        return Err(AppError::ValidationError("No changes detected".to_string()));
+    }
+
+    let restored = restored
+        .into_active_model()
+        .reset_all()
+        .update(&txn)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    delete_items(&txn, restored.id).await?;
+    for item in &target_items {
+        item.clone()
+            .into_active_model()
+            .reset_all()
+            .insert(&txn)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    }
+
+    record_activity_history(
+        &txn,
+        HistoryAction::Reverted,
+        user_id,
+        Some((&current, &current_items)),
+        Some((&restored, &target_items)),
+        Some(payload.version),
+    )
+    .await?;
+    let mut events = notify_activity_change(
+        &txn,
+        NotificationType::ExpenseUpdated,
+        user_id,
+        &restored,
+        involved_users(&current)?,
+    )
+    .await?;
+    update_group_total_expense(&txn, payload.group_id).await?;
+
+    let response = if restored.split_type == SplitType::Itemized {
+        ActivityRes::from(restored).with_items(target_items)
+    } else {
+        ActivityRes::from(restored)
+    };
+    events.push(record_group_event(&txn, GroupEventType::ActivityUpdated, payload.group_id, &response).await?);
+
+    txn.commit()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    bus.publish(events);
+
+    Ok((StatusCode::OK, AxumJson(response)))
+}
+
+/// Appends the next version to an activity's history. Runs inside the same
+/// transaction as the change itself so the two can never disagree.
+pub async fn record_activity_history<C: ConnectionTrait>(
+    db: &C,
+    action: HistoryAction,
+    actor_id: Uuid,
+    before: Option<(&activities::Model, &[activity_items::Model])>,
+    after: Option<(&activities::Model, &[activity_items::Model])>,
+    reverted_to_version: Option<i32>,
+) -> Result<(), AppError> {
+    let (activity, items) = after
+        .or(before)
+        .ok_or(AppError::InternalServerError)?;
+
+    let last_version: Option<i32> = activity_history::Entity::find()
+        .filter(activity_history::Column::ActivityId.eq(activity.id))
+        .select_only()
+        .column(activity_history::Column::Version)
+        .order_by_desc(activity_history::Column::Version)
+        .into_tuple()
+        .one(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    activity_history::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        activity_id: Set(activity.id),
+        group_id: Set(activity.group_id),
+        version: Set(last_version.unwrap_or(0) + 1),
+        action: Set(action),
+        actor_id: Set(actor_id),
+        changes: Set(diff(before, after)),
+        snapshot: Set(json!(activity)),
+        items: Set(json!(items)),
+        reverted_to_version: Set(reverted_to_version),
+        created_at: Set(Utc::now().into()),
+    }
+    .insert(db)
+    .await
+    .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    Ok(())
+}
@@ -0,0 +1,269 @@
+use chrono::Utc;
+use crate::custom_errors::app::AppError;
+use crate::entities::activity_attachments;
+use crate::models::attachments::{
+    AttachmentIdReq, AttachmentRes, GetAttachmentsReq, UploadAttachmentReq,
+};
+use crate::request_verifier::{
+    activities::check_activity_exists_in_group,
+    groups::{
+        check_group_exists, check_group_not_archived, check_user_can_write_in_group,
+        check_user_exists_in_group,
+        check_user_is_admin_in_group,
+    },
+};
+use crate::utils::attachments::{content_hash, detect_content_type, max_attachment_bytes, storage_key};
+use crate::utils::blob_store::SharedBlobStore;
+use axum::{
+    body::Bytes,
+    extract::{Extension, Json, Query},
+    http::{header, HeaderMap, StatusCode},
+    response::IntoResponse,
+    Json as AxumJson,
+};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, DbBackend, EntityTrait,
+    PaginatorTrait, QueryFilter, QueryOrder, Set, Statement, TransactionTrait,
+};
+use uuid::Uuid;
+
+pub async fn upload_attachment_handler(
+    Extension(user_id): Extension<Uuid>,
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(store): Extension<SharedBlobStore>,
+    Query(mut payload): Query<UploadAttachmentReq>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<impl IntoResponse, AppError> {
+    payload.check()?;
+    check_group_exists(&db, payload.group_id).await?;
+    check_group_not_archived(&db, payload.group_id).await?;
+    check_user_can_write_in_group(&db, payload.group_id, user_id).await?;
+    check_activity_exists_in_group(&db, payload.activity_id, payload.group_id).await?;
+
+    if body.is_empty() {
+        return Err(AppError::ValidationError("Attachment cannot be empty".into()));
+    }
+    let max_bytes = max_attachment_bytes()?;
+    if body.len() > max_bytes {
+        return Err(AppError::PayloadTooLarge(format!(
+            "Attachments can be at most {} bytes",
+            max_bytes
+        )));
+    }
+
+    let content_type = detect_content_type(&body).ok_or_else(|| {
+        AppError::ValidationError("Only JPEG, PNG and WebP images or PDF files can be attached".into())
+    })?;
+    let declared = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.split(';').next().unwrap_or_default().trim().to_ascii_lowercase());
+    if let Some(declared) = declared {
+        if declared != content_type && declared != "application/octet-stream" {
+            return Err(AppError::ValidationError(
+                "File contents do not match the declared content type".into(),
+            ));
+        }
+    }
+
+    let hash = content_hash(&body);
+    if let Some(existing) = activity_attachments::Entity::find()
+        .filter(activity_attachments::Column::ActivityId.eq(payload.activity_id))
+        .filter(activity_attachments::Column::ContentHash.eq(hash.clone()))
+        .one(&db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?
+    {
+        return Ok((StatusCode::OK, AxumJson(AttachmentRes::from(existing))));
+    }
+
+    let txn = db
+        .begin()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    // held until the row below is committed, so the blob cannot be released in between
+    lock_blob(&txn, &hash).await?;
+
+    let key = storage_key(&hash);
+    if !store.exists(&key).await? {
+        store.put(&key, body.to_vec()).await?;
+    }
+
+    let attachment = activity_attachments::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        activity_id: Set(payload.activity_id),
+        group_id: Set(payload.group_id),
+        uploaded_by: Set(user_id),
+        file_name: Set(payload.file_name),
+        content_type: Set(content_type.to_string()),
+        size_bytes: Set(body.len() as i64),
+        content_hash: Set(hash),
+        storage_key: Set(key),
+        created_at: Set(Utc::now().into()),
+    }
+    .insert(&txn)
+    .await
+    .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    txn.commit()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    Ok((StatusCode::CREATED, AxumJson(AttachmentRes::from(attachment))))
+}
+
+pub async fn get_attachments_handler(
+    Extension(user_id): Extension<Uuid>,
+    Extension(db): Extension<DatabaseConnection>,
+    Json(payload): Json<GetAttachmentsReq>,
+) -> Result<impl IntoResponse, AppError> {
+    payload.check()?;
+    check_group_exists(&db, payload.group_id).await?;
+    check_user_exists_in_group(&db, payload.group_id, user_id).await?;
+    check_activity_exists_in_group(&db, payload.activity_id, payload.group_id).await?;
+
+    let attachments: Vec<AttachmentRes> = activity_attachments::Entity::find()
+        .filter(activity_attachments::Column::ActivityId.eq(payload.activity_id))
+        .order_by_asc(activity_attachments::Column::CreatedAt)
+        .all(&db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?
+        .into_iter()
+        .map(AttachmentRes::from)
+        .collect();
+
+    Ok((StatusCode::OK, AxumJson(attachments)))
+}
+
+pub async fn download_attachment_handler(
+    Extension(user_id): Extension<Uuid>,
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(store): Extension<SharedBlobStore>,
+    Query(payload): Query<AttachmentIdReq>,
+) -> Result<impl IntoResponse, AppError> {
+    payload.check()?;
+    let attachment = find_attachment(&db, payload.attachment_id).await?;
+    check_user_exists_in_group(&db, attachment.group_id, user_id).await?;
+
+    let bytes = store.get(&attachment.storage_key).await?;
+    let file_name = attachment.file_name.replace(['"', '\r', '\n'], "_");
+
+    Ok((
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, attachment.content_type),
+            (header::CONTENT_DISPOSITION, format!("inline; filename=\"{}\"", file_name)),
+            (header::X_CONTENT_TYPE_OPTIONS, "nosniff".to_string()),
+        ],
+        bytes,
+    ))
+}
+
+pub async fn delete_attachment_handler(
+    Extension(user_id): Extension<Uuid>,
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(store): Extension<SharedBlobStore>,
+    Json(payload): Json<AttachmentIdReq>,
+) -> Result<impl IntoResponse, AppError> {
+    payload.check()?;
+    let attachment = find_attachment(&db, payload.attachment_id).await?;
+    check_user_can_write_in_group(&db, attachment.group_id, user_id).await?;
+    check_group_not_archived(&db, attachment.group_id).await?;
+
+    if attachment.uploaded_by != user_id {
+        check_user_is_admin_in_group(&db, attachment.group_id, user_id)
+            .await
+            .map_err(|_| {
+                AppError::Unauthorized("You are not authorized to delete this attachment".to_string())
+            })?;
+    }
+
+    activity_attachments::Entity::delete_by_id(attachment.id)
+        .exec(&db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    release_blobs(&db, &store, vec![attachment.content_hash]).await;
+
+    Ok((StatusCode::OK, Json("Attachment deleted successfully")))
+}
+
+/// Removes an activity's attachment rows and returns their content hashes so
+/// the blobs can be released once the surrounding transaction has committed.
+pub async fn delete_activity_attachments<C: ConnectionTrait>(
+    db: &C,
+    activity_id: Uuid,
+) -> Result<Vec<String>, AppError> {
+    let hashes = activity_attachments::Entity::find()
+        .filter(activity_attachments::Column::ActivityId.eq(activity_id))
+        .all(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?
+        .into_iter()
+        .map(|attachment| attachment.content_hash)
+        .collect();
+
+    activity_attachments::Entity::delete_many()
+        .filter(activity_attachments::Column::ActivityId.eq(activity_id))
+        .exec(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    Ok(hashes)
+}
+
+/// Deletes the blobs no attachment refers to anymore. Failures are only logged:
+/// the attachment rows are already gone and an orphaned blob is harmless.
+pub async fn release_blobs(db: &DatabaseConnection, store: &SharedBlobStore, hashes: Vec<String>) {
+    for hash in hashes {
+        if let Err(err) = release_blob(db, store, &hash).await {
+            eprintln!("Failed to release attachment blob {}: {}", hash, err);
+        }
+    }
+}
+
+async fn release_blob(db: &DatabaseConnection, store: &SharedBlobStore, hash: &str) -> Result<(), AppError> {
+    let txn = db
+        .begin()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    // an upload of the same content waits here, so it cannot reuse the blob
+    // between the count and the delete
+    lock_blob(&txn, hash).await?;
+
+    let still_used = activity_attachments::Entity::find()
+        .filter(activity_attachments::Column::ContentHash.eq(hash))
+        .count(&txn)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    if still_used == 0 {
+        store.delete(&storage_key(hash)).await?;
+    }
+
+    txn.commit()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))
+}
+
+/// Serializes uploads and releases of one blob until the transaction ends.
+async fn lock_blob<C: ConnectionTrait>(db: &C, hash: &str) -> Result<(), AppError> {
+    db.execute(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        "SELECT pg_advisory_xact_lock(hashtext($1))",
+        [hash.into()],
+    ))
+    .await
+    .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    Ok(())
+}
+
+async fn find_attachment(
+    db: &DatabaseConnection,
+    attachment_id: Uuid,
+) -> Result<activity_attachments::Model, AppError> {
+    activity_attachments::Entity::find_by_id(attachment_id)
+        .one(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?
+        .ok_or(AppError::NotFound("Attachment not found".to_string()))
+}
@@ -3,6 +3,7 @@ use crate::models::auth::{AuthReq, AuthRes, AuthOutput};
 use crate::entities::users::{self, ActiveModel as UserActiveModel};
 use crate::custom_errors::app::AppError;
 use crate::utils::jwt_token::generate_jwt;
+use crate::utils::oauth::{SharedOAuthVerifier, VerifiedIdentity};
 use axum::{
     extract::{Json, Extension},
     response::IntoResponse,
@@ -12,13 +13,26 @@ use axum::{
 use sea_orm::{EntityTrait, ActiveModelTrait, Set, QueryFilter, ColumnTrait};
 use uuid::Uuid;
 
+// set by the migrations on accounts whose oauth id was never verified
+const UNVERIFIED_OAUTH_ID_PREFIX: &str = "unverified:";
+
+/// Signs in with an ID token from one of the configured providers, creating the
+/// account on first sign-in. Accounts are found by the provider's subject; an
+/// account from before ids were verified is picked up once by its verified email,
+/// after that the email no longer links anything.
 pub async fn auth_handler(
     Extension(db): Extension<sea_orm::DatabaseConnection>, 
+    Extension(verifier): Extension<SharedOAuthVerifier>,
     Json(payload): Json<AuthReq>
 ) -> Result<impl IntoResponse, AppError> {
     payload.check()?;
+    let identity = verifier
+        .verify_id_token(payload.oauth_provider.trim(), &payload.id_token)
+        .await?;
+
     let existing_user = users::Entity::find()
-        .filter(users::Column::Email.eq(payload.email.clone()))
+        .filter(users::Column::OauthProvider.eq(identity.provider.clone()))
+        .filter(users::Column::OauthId.eq(identity.subject.clone()))
         .one(&db)
         .await
         .map_err(|e| AppError::DatabaseError(e.to_string()))?;
@@ -33,20 +47,49 @@ pub async fn auth_handler(
                 .map_err(|e| AppError::DatabaseError(e.to_string()))?
         },
         None => {
-            let new_user = users::ActiveModel {
-                id: Set(Uuid::new_v4()),
-                oauth_provider: Set(payload.oauth_provider),
-                oauth_id: Set(payload.oauth_id),
-                username: Set(payload.username),
-                email: Set(payload.email),
-                upi_id: Set(payload.upi_id),
-                created_at: Set(Utc::now().into()),
-                updated_at: Set(Utc::now().into()),
+            let email = match identity.email.clone() {
+                Some(email) if identity.email_verified => email,
+                _ => {
+                    return Err(AppError::Unauthorized(
+                        "The provider has not verified an email for this account".into(),
+                    ))
+                }
             };
-            new_user
-                .insert(&db)
+
+            let legacy_user = users::Entity::find()
+                .filter(users::Column::Email.eq(email.clone()))
+                .one(&db)
                 .await
-                .map_err(|e| AppError::DatabaseError(e.to_string()))?
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+            match legacy_user {
+                // only accounts the migration marked as unverified, one already
+                // linked to a verified identity is never handed to another
+                Some(user)
+                    if user.oauth_provider == identity.provider
+                        && user.oauth_id.starts_with(UNVERIFIED_OAUTH_ID_PREFIX) =>
+                {
+                    let mut active_user: users::ActiveModel = user.into();
+                    active_user.oauth_id = Set(identity.subject);
+                    active_user.updated_at = Set(Utc::now().into());
+                    active_user
+                        .update(&db)
+                        .await
+                        .map_err(|e| AppError::DatabaseError(e.to_string()))?
+                }
+                Some(user) if user.oauth_provider == identity.provider => {
+                    return Err(AppError::DuplicateError(
+                        "An account with this email is linked to another sign-in".into(),
+                    ))
+                }
+                Some(user) => {
+                    return Err(AppError::DuplicateError(format!(
+                        "An account with this email signs in with {}",
+                        user.oauth_provider
+                    )))
+                }
+                None => create_user(&db, &payload, identity, email).await?,
+            }
         }
     };
 
@@ -60,3 +103,26 @@ pub async fn auth_handler(
 
     Ok((StatusCode::OK, AxumJson(auth_response)))
 }
+
+async fn create_user(
+    db: &sea_orm::DatabaseConnection,
+    payload: &AuthReq,
+    identity: VerifiedIdentity,
+    email: String,
+) -> Result<users::Model, AppError> {
+    let (username, upi_id) = payload.check_signup(identity.name.as_deref())?;
+    let new_user = users::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        oauth_provider: Set(identity.provider),
+        oauth_id: Set(identity.subject),
+        username: Set(username),
+        email: Set(email),
+        upi_id: Set(upi_id),
+        created_at: Set(Utc::now().into()),
+        updated_at: Set(Utc::now().into()),
+    };
+    new_user
+        .insert(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))
+}
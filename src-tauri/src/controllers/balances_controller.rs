@@ -0,0 +1,195 @@
+use crate::custom_errors::app::AppError;
+use crate::entities::{activities, group_members, groups, transactions};
+use crate::entities::sea_orm_active_enums::TransactionStatus;
+use crate::models::activities::{parse_payers, parse_splits};
+use crate::models::balances::{
+    DebtRes, FriendBalanceRes, FriendGroupBalanceRes, GetGroupBalancesReq, GetSettlementPlanReq,
+    GroupBalancesRes, MemberBalanceRes, SettlementPlanRes, SuggestedPaymentRes,
+};
+use crate::request_verifier::groups::{check_group_exists, check_user_exists_in_group};
+use crate::utils::ledger::Ledger;
+use crate::utils::settlement_planner::simplify_debts;
+use axum::{
+    extract::{Extension, Json},
+    http::StatusCode,
+    response::IntoResponse,
+    Json as AxumJson,
+};
+use rust_decimal::Decimal;
+use sea_orm::{ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter};
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+pub async fn get_group_balances_handler(
+    Extension(user_id): Extension<Uuid>,
+    Extension(db): Extension<sea_orm::DatabaseConnection>,
+    Json(payload): Json<GetGroupBalancesReq>,
+) -> Result<impl IntoResponse, AppError> {
+    payload.check()?;
+    check_group_exists(&db, payload.group_id).await?;
+    check_user_exists_in_group(&db, payload.group_id, user_id).await?;
+
+    let ledger = load_group_ledger(&db, payload.group_id).await?;
+
+    let balances = ledger
+        .member_balances()
+        .into_iter()
+        .map(MemberBalanceRes::from)
+        .collect();
+    let pairwise = ledger
+        .pairwise_debts()
+        .into_iter()
+        .map(DebtRes::from)
+        .collect();
+
+    Ok((
+        StatusCode::OK,
+        AxumJson(GroupBalancesRes::new(payload.group_id, balances, pairwise)),
+    ))
+}
+
+pub async fn get_settlement_plan_handler(
+    Extension(user_id): Extension<Uuid>,
+    Extension(db): Extension<sea_orm::DatabaseConnection>,
+    Json(payload): Json<GetSettlementPlanReq>,
+) -> Result<impl IntoResponse, AppError> {
+    payload.check()?;
+    check_group_exists(&db, payload.group_id).await?;
+    check_user_exists_in_group(&db, payload.group_id, user_id).await?;
+
+    let group = groups::Entity::find_by_id(payload.group_id)
+        .one(&db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?
+        .ok_or_else(|| AppError::NotFound("Group not found".into()))?;
+    let simplified = payload.simplify.unwrap_or(group.simplify_debts);
+
+    let ledger = load_group_ledger(&db, payload.group_id).await?;
+
+    let payments: Vec<SuggestedPaymentRes> = if simplified {
+        let net_balances: Vec<(Uuid, Decimal)> = ledger
+            .member_balances()
+            .into_iter()
+            .map(|balance| (balance.member_id, balance.net))
+            .collect();
+        simplify_debts(&net_balances)
+            .into_iter()
+            .map(SuggestedPaymentRes::from)
+            .collect()
+    } else {
+        ledger
+            .pairwise_debts()
+            .into_iter()
+            .map(SuggestedPaymentRes::from)
+            .collect()
+    };
+
+    Ok((
+        StatusCode::OK,
+        AxumJson(SettlementPlanRes::new(payload.group_id, simplified, payments)),
+    ))
+}
+
+pub async fn get_friend_balances_handler(
+    Extension(user_id): Extension<Uuid>,
+    Extension(db): Extension<sea_orm::DatabaseConnection>,
+) -> Result<impl IntoResponse, AppError> {
+    let friend_balances: Vec<FriendBalanceRes> = load_friend_balances(&db, user_id)
+        .await?
+        .into_iter()
+        .map(|(friend_id, groups)| {
+            let groups = groups
+                .into_iter()
+                .map(|(group_id, amount)| FriendGroupBalanceRes {
+                    group_id,
+                    amount: amount.round_dp(2),
+                })
+                .collect();
+            FriendBalanceRes::new(friend_id, groups)
+        })
+        .collect();
+
+    Ok((StatusCode::OK, AxumJson(friend_balances)))
+}
+
+/// Direct balances between `user_id` and everyone they share a group with,
+/// keyed by friend and then by group. Positive amounts are owed to `user_id`.
+pub async fn load_friend_balances<C: ConnectionTrait>(
+    db: &C,
+    user_id: Uuid,
+) -> Result<BTreeMap<Uuid, Vec<(Uuid, Decimal)>>, AppError> {
+    let memberships = group_members::Entity::find()
+        .filter(group_members::Column::MemberId.eq(user_id))
+        .all(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    let mut balances: BTreeMap<Uuid, Vec<(Uuid, Decimal)>> = BTreeMap::new();
+    for membership in memberships {
+        let ledger = load_group_ledger(db, membership.group_id).await?;
+        for debt in ledger.pairwise_debts() {
+            let (friend_id, amount) = if debt.creditor_id == user_id {
+                (debt.debtor_id, debt.amount)
+            } else if debt.debtor_id == user_id {
+                (debt.creditor_id, -debt.amount)
+            } else {
+                continue;
+            };
+            balances
+                .entry(friend_id)
+                .or_default()
+                .push((membership.group_id, amount));
+        }
+    }
+
+    Ok(balances)
+}
+
+/// Replays every activity and settled payment of a group into a fresh `Ledger`.
+pub async fn load_group_ledger<C: ConnectionTrait>(
+    db: &C,
+    group_id: Uuid,
+) -> Result<Ledger, AppError> {
+    let mut ledger = Ledger::new();
+
+    let members = group_members::Entity::find()
+        .filter(group_members::Column::GroupId.eq(group_id))
+        .all(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    for member in members {
+        ledger.add_member(member.member_id);
+    }
+
+    let group_activities = activities::Entity::find()
+        .filter(activities::Column::GroupId.eq(group_id))
+        .filter(activities::Column::DeletedAt.is_null())
+        .all(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    for activity in group_activities {
+        // balances are kept in the group's currency
+        let rate = activity.exchange_rate;
+        let payers: Vec<(Uuid, Decimal)> = parse_payers(&activity)?
+            .into_iter()
+            .map(|(payer_id, amount)| (payer_id, amount * rate))
+            .collect();
+        let splits: Vec<(Uuid, Decimal)> = parse_splits(&activity)?
+            .into_iter()
+            .map(|(member_id, amount)| (member_id, amount * rate))
+            .collect();
+        ledger.record_expense(&payers, &splits);
+    }
+
+    let settlements = transactions::Entity::find()
+        .filter(transactions::Column::GroupId.eq(group_id))
+        .filter(transactions::Column::Status.is_in(TransactionStatus::settled()))
+        .all(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    for settlement in settlements {
+        ledger.record_settlement(settlement.payer_id, settlement.receiver_id, settlement.amount);
+    }
+
+    Ok(ledger)
+}
@@ -0,0 +1,193 @@
+use chrono::Utc;
+use crate::custom_errors::app::AppError;
+use crate::entities::{activities, categories, recurring_expenses};
+use crate::models::categories::{
+    CategoryRes, CreateCategoryReq, DeleteCategoryReq, GetCategoriesReq, SuggestCategoryReq,
+};
+use crate::request_verifier::groups::{
+    check_group_exists, check_group_not_archived, check_user_can_write_in_group,
+    check_user_exists_in_group, check_user_is_admin_in_group,
+};
+use crate::utils::categorizer::suggest_category;
+use axum::{
+    extract::{Extension, Json},
+    http::StatusCode,
+    response::IntoResponse,
+    Json as AxumJson,
+};
+use sea_orm::sea_query::{Condition, Expr};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter,
+    QueryOrder, Set, TransactionTrait,
+};
+use serde_json::json;
+use uuid::Uuid;
+
+const DEFAULT_CATEGORY_LOGO: &str = "tag";
+
+pub async fn create_category_handler(
+    Extension(user_id): Extension<Uuid>,
+    Extension(db): Extension<DatabaseConnection>,
+    Json(mut payload): Json<CreateCategoryReq>,
+) -> Result<impl IntoResponse, AppError> {
+    payload.check()?;
+    check_group_exists(&db, payload.group_id).await?;
+    check_group_not_archived(&db, payload.group_id).await?;
+    check_user_can_write_in_group(&db, payload.group_id, user_id).await?;
+
+    let existing = load_group_categories(&db, payload.group_id).await?;
+    if existing
+        .iter()
+        .any(|category| category.name.eq_ignore_ascii_case(&payload.name))
+    {
+        return Err(AppError::DuplicateError(
+            "A category with this name already exists".into(),
+        ));
+    }
+
+    let category = categories::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        group_id: Set(Some(payload.group_id)),
+        name: Set(payload.name),
+        logo: Set(payload.logo.unwrap_or_else(|| DEFAULT_CATEGORY_LOGO.to_string())),
+        keywords: Set(json!(payload.keywords)),
+        created_by: Set(Some(user_id)),
+        created_at: Set(Utc::now().into()),
+        updated_at: Set(Utc::now().into()),
+    }
+    .insert(&db)
+    .await
+    .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    Ok((StatusCode::CREATED, AxumJson(CategoryRes::from(category))))
+}
+
+pub async fn get_categories_handler(
+    Extension(user_id): Extension<Uuid>,
+    Extension(db): Extension<DatabaseConnection>,
+    Json(payload): Json<GetCategoriesReq>,
+) -> Result<impl IntoResponse, AppError> {
+    payload.check()?;
+    check_group_exists(&db, payload.group_id).await?;
+    check_user_exists_in_group(&db, payload.group_id, user_id).await?;
+
+    let categories: Vec<CategoryRes> = load_group_categories(&db, payload.group_id)
+        .await?
+        .into_iter()
+        .map(CategoryRes::from)
+        .collect();
+
+    Ok((StatusCode::OK, AxumJson(categories)))
+}
+
+pub async fn delete_category_handler(
+    Extension(user_id): Extension<Uuid>,
+    Extension(db): Extension<DatabaseConnection>,
+    Json(payload): Json<DeleteCategoryReq>,
+) -> Result<impl IntoResponse, AppError> {
+    payload.check()?;
+    check_group_exists(&db, payload.group_id).await?;
+    check_group_not_archived(&db, payload.group_id).await?;
+    check_user_can_write_in_group(&db, payload.group_id, user_id).await?;
+
+    let category = categories::Entity::find_by_id(payload.category_id)
+        .filter(categories::Column::GroupId.eq(payload.group_id))
+        .one(&db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?
+        .ok_or(AppError::NotFound("Category not found".to_string()))?;
+
+    if category.created_by != Some(user_id) {
+        check_user_is_admin_in_group(&db, payload.group_id, user_id)
+            .await
+            .map_err(|_| {
+                AppError::Unauthorized("You are not authorized to delete this category".to_string())
+            })?;
+    }
+
+    let txn = db
+        .begin()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    // expenses keep their logo but lose the category
+    activities::Entity::update_many()
+        .col_expr(activities::Column::CategoryId, Expr::value(Option::<Uuid>::None))
+        .filter(activities::Column::CategoryId.eq(category.id))
+        .exec(&txn)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    recurring_expenses::Entity::update_many()
+        .col_expr(recurring_expenses::Column::CategoryId, Expr::value(Option::<Uuid>::None))
+        .filter(recurring_expenses::Column::CategoryId.eq(category.id))
+        .exec(&txn)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    categories::Entity::delete_by_id(category.id)
+        .exec(&txn)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    txn.commit()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    Ok((StatusCode::OK, Json("Category deleted successfully")))
+}
+
+pub async fn suggest_category_handler(
+    Extension(user_id): Extension<Uuid>,
+    Extension(db): Extension<DatabaseConnection>,
+    Json(payload): Json<SuggestCategoryReq>,
+) -> Result<impl IntoResponse, AppError> {
+    payload.check()?;
+    check_group_exists(&db, payload.group_id).await?;
+    check_user_exists_in_group(&db, payload.group_id, user_id).await?;
+
+    let categories = load_group_categories(&db, payload.group_id).await?;
+    let suggestion = suggest_category(&payload.description, &categories)
+        .cloned()
+        .map(CategoryRes::from);
+
+    Ok((StatusCode::OK, AxumJson(suggestion)))
+}
+
+/// Built-in categories followed by the group's own ones.
+pub async fn load_group_categories<C: ConnectionTrait>(
+    db: &C,
+    group_id: Uuid,
+) -> Result<Vec<categories::Model>, AppError> {
+    let mut categories = categories::Entity::find()
+        .filter(
+            Condition::any()
+                .add(categories::Column::GroupId.is_null())
+                .add(categories::Column::GroupId.eq(group_id)),
+        )
+        .order_by_asc(categories::Column::Name)
+        .all(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    // stable, so each half stays sorted by name
+    categories.sort_by_key(|category| category.group_id.is_some());
+    Ok(categories)
+}
+
+/// The category an expense in `group_id` ends up with: the one asked for, which
+/// has to be built-in or belong to the group, or else one suggested from the description.
+pub async fn resolve_category<C: ConnectionTrait>(
+    db: &C,
+    group_id: Uuid,
+    category_id: Option<Uuid>,
+    description: &str,
+) -> Result<Option<categories::Model>, AppError> {
+    let categories = load_group_categories(db, group_id).await?;
+    match category_id {
+        Some(category_id) => categories
+            .into_iter()
+            .find(|category| category.id == category_id)
+            .map(Some)
+            .ok_or(AppError::NotFound("Category not found".to_string())),
+        None => Ok(suggest_category(description, &categories).cloned()),
+    }
+}
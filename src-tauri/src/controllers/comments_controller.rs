@@ -0,0 +1,183 @@
+use chrono::Utc;
+use crate::custom_errors::app::AppError;
+use crate::entities::activity_comments;
+use crate::models::comments::{
+    CommentRes, CommentsPageRes, CreateCommentReq, DeleteCommentReq, GetCommentsReq,
+    UpdateCommentReq,
+};
+use crate::request_verifier::{
+    activities::check_activity_exists_in_group,
+    groups::{
+        check_group_exists, check_group_not_archived, check_user_can_write_in_group,
+        check_user_exists_in_group,
+        check_user_is_admin_in_group,
+    },
+};
+use axum::{
+    extract::{Extension, Json},
+    http::StatusCode,
+    response::IntoResponse,
+    Json as AxumJson,
+};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait,
+    IntoActiveModel, PaginatorTrait, QueryFilter, QueryOrder, Set,
+};
+use std::env;
+use uuid::Uuid;
+
+pub async fn create_comment_handler(
+    Extension(user_id): Extension<Uuid>,
+    Extension(db): Extension<DatabaseConnection>,
+    Json(mut payload): Json<CreateCommentReq>,
+) -> Result<impl IntoResponse, AppError> {
+    payload.check()?;
+    check_group_exists(&db, payload.group_id).await?;
+    check_group_not_archived(&db, payload.group_id).await?;
+    check_user_can_write_in_group(&db, payload.group_id, user_id).await?;
+    check_activity_exists_in_group(&db, payload.activity_id, payload.group_id).await?;
+
+    let comment = activity_comments::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        activity_id: Set(payload.activity_id),
+        group_id: Set(payload.group_id),
+        author_id: Set(user_id),
+        body: Set(payload.body),
+        edited_at: Set(None),
+        created_at: Set(Utc::now().into()),
+        updated_at: Set(Utc::now().into()),
+    }
+    .insert(&db)
+    .await
+    .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    Ok((StatusCode::CREATED, AxumJson(CommentRes::from(comment))))
+}
+
+pub async fn get_comments_handler(
+    Extension(user_id): Extension<Uuid>,
+    Extension(db): Extension<DatabaseConnection>,
+    Json(payload): Json<GetCommentsReq>,
+) -> Result<impl IntoResponse, AppError> {
+    payload.check()?;
+    check_group_exists(&db, payload.group_id).await?;
+    check_user_exists_in_group(&db, payload.group_id, user_id).await?;
+    check_activity_exists_in_group(&db, payload.activity_id, payload.group_id).await?;
+
+    let page_size: u64 = env::var("PAGE_SIZE")
+        .map_err(|_| AppError::ConfigError("PAGE_SIZE must be set".to_string()))?
+        .trim()
+        .parse()
+        .map_err(|_| AppError::ConfigError("Invalid PAGE_SIZE value".to_string()))?;
+
+    // oldest first so a thread reads top to bottom
+    let paginator = activity_comments::Entity::find()
+        .filter(activity_comments::Column::ActivityId.eq(payload.activity_id))
+        .order_by_asc(activity_comments::Column::CreatedAt)
+        .order_by_asc(activity_comments::Column::Id)
+        .paginate(&db, page_size);
+
+    let totals = paginator
+        .num_items_and_pages()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    let comments = paginator
+        .fetch_page(payload.page)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?
+        .into_iter()
+        .map(CommentRes::from)
+        .collect();
+
+    Ok((
+        StatusCode::OK,
+        AxumJson(CommentsPageRes {
+            comments,
+            page: payload.page,
+            total_pages: totals.number_of_pages,
+            total_comments: totals.number_of_items,
+        }),
+    ))
+}
+
+pub async fn update_comment_handler(
+    Extension(user_id): Extension<Uuid>,
+    Extension(db): Extension<DatabaseConnection>,
+    Json(mut payload): Json<UpdateCommentReq>,
+) -> Result<impl IntoResponse, AppError> {
+    payload.check()?;
+    let comment = find_comment(&db, payload.comment_id).await?;
+    check_user_can_write_in_group(&db, comment.group_id, user_id).await?;
+    check_group_not_archived(&db, comment.group_id).await?;
+
+    // admins can remove comments but never reword them
+    if comment.author_id != user_id {
+        return Err(AppError::Unauthorized(
+            "You are not authorized to edit this comment".to_string(),
+        ));
+    }
+    if comment.body == payload.body {
+        return Err(AppError::ValidationError("No changes detected".to_string()));
+    }
+
+    let mut comment_model = comment.into_active_model();
+    comment_model.body = Set(payload.body);
+    comment_model.edited_at = Set(Some(Utc::now().into()));
+    comment_model.updated_at = Set(Utc::now().into());
+    let updated = comment_model
+        .update(&db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    Ok((StatusCode::OK, AxumJson(CommentRes::from(updated))))
+}
+
+pub async fn delete_comment_handler(
+    Extension(user_id): Extension<Uuid>,
+    Extension(db): Extension<DatabaseConnection>,
+    Json(payload): Json<DeleteCommentReq>,
+) -> Result<impl IntoResponse, AppError> {
+    payload.check()?;
+    let comment = find_comment(&db, payload.comment_id).await?;
+    check_user_can_write_in_group(&db, comment.group_id, user_id).await?;
+    check_group_not_archived(&db, comment.group_id).await?;
+
+    if comment.author_id != user_id {
+        check_user_is_admin_in_group(&db, comment.group_id, user_id)
+            .await
+            .map_err(|_| {
+                AppError::Unauthorized("You are not authorized to delete this comment".to_string())
+            })?;
+    }
+
+    activity_comments::Entity::delete_by_id(comment.id)
+        .exec(&db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    Ok((StatusCode::OK, Json("Comment deleted successfully")))
+}
+
+/// Removes every comment on an activity, run alongside deleting the activity.
+pub async fn delete_activity_comments<C: ConnectionTrait>(
+    db: &C,
+    activity_id: Uuid,
+) -> Result<(), AppError> {
+    activity_comments::Entity::delete_many()
+        .filter(activity_comments::Column::ActivityId.eq(activity_id))
+        .exec(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    Ok(())
+}
+
+async fn find_comment(
+    db: &DatabaseConnection,
+    comment_id: Uuid,
+) -> Result<activity_comments::Model, AppError> {
+    activity_comments::Entity::find_by_id(comment_id)
+        .one(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?
+        .ok_or(AppError::NotFound("Comment not found".to_string()))
+}
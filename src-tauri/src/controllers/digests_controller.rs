@@ -0,0 +1,251 @@
+use chrono::{DateTime, Utc};
+use crate::controllers::balances_controller::load_group_ledger;
+use crate::custom_errors::app::AppError;
+use crate::entities::sea_orm_active_enums::{DigestFrequency, TransactionStatus};
+use crate::entities::{activities, digest_preferences, group_members, groups, transactions, users};
+use crate::models::activities::parse_splits;
+use crate::models::digests::{
+    DigestExpenseRes, DigestGroupRes, DigestPreferenceRes, DigestRes, DigestSettlementRes,
+    PreviewDigestReq, UpdateDigestPreferenceReq,
+};
+use crate::utils::digests::period_start;
+use axum::{
+    extract::{Extension, Json},
+    http::StatusCode,
+    response::IntoResponse,
+    Json as AxumJson,
+};
+use rust_decimal::Decimal;
+use sea_orm::sea_query::Condition;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait,
+    IntoActiveModel, QueryFilter, QueryOrder, Set,
+};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+pub async fn get_digest_preference_handler(
+    Extension(user_id): Extension<Uuid>,
+    Extension(db): Extension<DatabaseConnection>,
+) -> Result<impl IntoResponse, AppError> {
+    let preference = digest_preferences::Entity::find_by_id(user_id)
+        .one(&db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?
+        .map(DigestPreferenceRes::from)
+        .unwrap_or_default();
+
+    Ok((StatusCode::OK, AxumJson(preference)))
+}
+
+/// Opting in starts the first period now, so the first digest does not reach
+/// back to before the user asked for it. Switching between weekly and monthly
+/// keeps the current period going.
+pub async fn update_digest_preference_handler(
+    Extension(user_id): Extension<Uuid>,
+    Extension(db): Extension<DatabaseConnection>,
+    Json(payload): Json<UpdateDigestPreferenceReq>,
+) -> Result<impl IntoResponse, AppError> {
+    let now = Utc::now();
+    let existing = digest_preferences::Entity::find_by_id(user_id)
+        .one(&db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    let preference = match existing {
+        Some(existing) => {
+            let opting_in = existing.frequency == DigestFrequency::Off
+                && payload.frequency != DigestFrequency::Off;
+            let mut preference = existing.into_active_model();
+            preference.frequency = Set(payload.frequency);
+            if opting_in {
+                preference.last_sent_at = Set(Some(now.into()));
+            }
+            preference.updated_at = Set(now.into());
+            preference
+                .update(&db)
+                .await
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?
+        }
+        None => digest_preferences::ActiveModel {
+            user_id: Set(user_id),
+            frequency: Set(payload.frequency),
+            last_sent_at: Set(Some(now.into())),
+            created_at: Set(now.into()),
+            updated_at: Set(now.into()),
+        }
+        .insert(&db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?,
+    };
+
+    Ok((StatusCode::OK, AxumJson(DigestPreferenceRes::from(preference))))
+}
+
+/// The digest the caller would get if it were sent now, without sending it.
+pub async fn preview_digest_handler(
+    Extension(user_id): Extension<Uuid>,
+    Extension(db): Extension<DatabaseConnection>,
+    Json(payload): Json<PreviewDigestReq>,
+) -> Result<impl IntoResponse, AppError> {
+    payload.check()?;
+
+    let frequency = match payload.frequency {
+        Some(frequency) => frequency,
+        None => digest_preferences::Entity::find_by_id(user_id)
+            .one(&db)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?
+            .map(|preference| preference.frequency)
+            .unwrap_or(DigestFrequency::Off),
+    };
+    let now = Utc::now();
+    let start = period_start(frequency, now).ok_or_else(|| {
+        AppError::ValidationError("Digests are turned off, pick a frequency to preview".into())
+    })?;
+
+    let user = users::Entity::find_by_id(user_id)
+        .one(&db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?
+        .ok_or_else(|| AppError::NotFound("User not found".into()))?;
+
+    let digest = build_digest(&db, &user, frequency, start, now).await?;
+    Ok((StatusCode::OK, AxumJson(digest)))
+}
+
+/// Collects what `user` should hear about for the period from `start` to `end`:
+/// expenses added to their groups, where they stand in each group, and
+/// settlements they are part of that still wait for a confirmation.
+pub async fn build_digest<C: ConnectionTrait>(
+    db: &C,
+    user: &users::Model,
+    frequency: DigestFrequency,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Result<DigestRes, AppError> {
+    let group_ids: Vec<Uuid> = group_members::Entity::find()
+        .filter(group_members::Column::MemberId.eq(user.id))
+        .all(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?
+        .into_iter()
+        .map(|membership| membership.group_id)
+        .collect();
+
+    let user_groups = groups::Entity::find()
+        .filter(groups::Column::Id.is_in(group_ids.clone()))
+        .order_by_asc(groups::Column::GroupName)
+        .all(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    let group_names: HashMap<Uuid, String> = user_groups
+        .iter()
+        .map(|group| (group.id, group.group_name.clone()))
+        .collect();
+
+    let mut balances = Vec::new();
+    for group in &user_groups {
+        let ledger = load_group_ledger(db, group.id).await?;
+        let net = ledger
+            .member_balances()
+            .into_iter()
+            .find(|balance| balance.member_id == user.id)
+            .map(|balance| balance.net)
+            .unwrap_or_default();
+        balances.push(DigestGroupRes {
+            group_id: group.id,
+            group_name: group.group_name.clone(),
+            currency: group.currency.clone(),
+            net: net.round_dp(2),
+        });
+    }
+
+    let new_activities = activities::Entity::find()
+        .filter(activities::Column::GroupId.is_in(group_ids.clone()))
+        .filter(activities::Column::DeletedAt.is_null())
+        .filter(activities::Column::CreatedAt.gte(start))
+        .filter(activities::Column::CreatedAt.lt(end))
+        .order_by_asc(activities::Column::CreatedAt)
+        .all(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    let pending = transactions::Entity::find()
+        .filter(transactions::Column::GroupId.is_in(group_ids))
+        .filter(transactions::Column::Status.eq(TransactionStatus::Pending))
+        .filter(
+            Condition::any()
+                .add(transactions::Column::PayerId.eq(user.id))
+                .add(transactions::Column::ReceiverId.eq(user.id)),
+        )
+        .order_by_asc(transactions::Column::Time)
+        .all(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    let mut user_ids: Vec<Uuid> = new_activities.iter().map(|activity| activity.paid_by_id).collect();
+    user_ids.extend(pending.iter().flat_map(|settlement| [settlement.payer_id, settlement.receiver_id]));
+    let usernames: HashMap<Uuid, String> = users::Entity::find()
+        .filter(users::Column::Id.is_in(user_ids))
+        .all(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?
+        .into_iter()
+        .map(|other| (other.id, other.username))
+        .collect();
+    let username = |id: &Uuid| usernames.get(id).cloned().unwrap_or_default();
+    let group_name = |id: &Uuid| group_names.get(id).cloned().unwrap_or_default();
+
+    let mut new_expenses = Vec::with_capacity(new_activities.len());
+    for activity in new_activities {
+        let your_share = parse_splits(&activity)?
+            .into_iter()
+            .filter(|(member_id, _)| *member_id == user.id)
+            .map(|(_, share)| share)
+            .sum::<Decimal>();
+        new_expenses.push(DigestExpenseRes {
+            activity_id: activity.id,
+            group_id: activity.group_id,
+            group_name: group_name(&activity.group_id),
+            description: activity.description,
+            amount: activity.amount,
+            currency: activity.currency,
+            paid_by_id: activity.paid_by_id,
+            paid_by_name: username(&activity.paid_by_id),
+            your_share: your_share.round_dp(2),
+            time: activity.time,
+        });
+    }
+
+    let pending_settlements = pending
+        .into_iter()
+        .map(|settlement| {
+            let awaiting_you = settlement.receiver_id == user.id;
+            let counterparty_id = if awaiting_you { settlement.payer_id } else { settlement.receiver_id };
+            DigestSettlementRes {
+                id: settlement.id,
+                group_id: settlement.group_id,
+                group_name: group_name(&settlement.group_id),
+                payer_id: settlement.payer_id,
+                receiver_id: settlement.receiver_id,
+                counterparty_name: username(&counterparty_id),
+                amount: settlement.amount.round_dp(2),
+                method: settlement.method,
+                awaiting_you,
+            }
+        })
+        .collect();
+
+    Ok(DigestRes {
+        user_id: user.id,
+        username: user.username.clone(),
+        email: user.email.clone(),
+        frequency,
+        period_start: start.into(),
+        period_end: end.into(),
+        new_expenses,
+        groups: balances,
+        pending_settlements,
+    })
+}
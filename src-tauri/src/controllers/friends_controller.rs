@@ -0,0 +1,344 @@
+use chrono::Utc;
+use crate::custom_errors::app::AppError;
+use crate::entities::sea_orm_active_enums::FriendStatus;
+use crate::entities::{friend_collections, users};
+use crate::models::friends::{
+    FriendIdReq, FriendRequestRes, FriendRes, FriendsRes, RespondFriendRequestReq, SendFriendRequestReq,
+};
+use crate::request_verifier::friends::check_not_blocked_by;
+use axum::{
+    extract::{Extension, Json},
+    http::StatusCode,
+    response::IntoResponse,
+    Json as AxumJson,
+};
+use sea_orm::sea_query::{Condition, Expr, Func};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter,
+    QueryOrder, Set, TransactionTrait,
+};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Sends a friend request, or accepts theirs if they already sent one.
+pub async fn send_friend_request_handler(
+    Extension(user_id): Extension<Uuid>,
+    Extension(db): Extension<DatabaseConnection>,
+    Json(mut payload): Json<SendFriendRequestReq>,
+) -> Result<impl IntoResponse, AppError> {
+    payload.check()?;
+    let friend = find_user(&db, &payload).await?;
+    if friend.id == user_id {
+        return Err(AppError::ValidationError("You cannot befriend yourself".into()));
+    }
+    check_not_blocked_by(&db, user_id, friend.id).await?;
+
+    let outgoing = find_relation(&db, user_id, friend.id).await?;
+    match outgoing.map(|relation| relation.status) {
+        Some(FriendStatus::Accepted) => {
+            return Err(AppError::DuplicateError("You are already friends".into()))
+        }
+        Some(FriendStatus::Pending) => {
+            return Err(AppError::DuplicateError("Friend request already sent".into()))
+        }
+        Some(FriendStatus::Blocked) => {
+            return Err(AppError::ValidationError(
+                "Unblock this user before sending a friend request".into(),
+            ))
+        }
+        None => {}
+    }
+
+    let incoming = find_relation(&db, friend.id, user_id).await?;
+    if incoming.map(|relation| relation.status) == Some(FriendStatus::Pending) {
+        accept_request(&db, friend.id, user_id).await?;
+        return Ok((StatusCode::OK, Json("Friend request accepted")));
+    }
+
+    set_status(&db, user_id, friend.id, FriendStatus::Pending).await?;
+
+    Ok((StatusCode::CREATED, Json("Friend request sent successfully")))
+}
+
+pub async fn respond_friend_request_handler(
+    Extension(user_id): Extension<Uuid>,
+    Extension(db): Extension<DatabaseConnection>,
+    Json(payload): Json<RespondFriendRequestReq>,
+) -> Result<impl IntoResponse, AppError> {
+    payload.check()?;
+
+    let incoming = find_relation(&db, payload.friend_id, user_id).await?;
+    if incoming.map(|relation| relation.status) != Some(FriendStatus::Pending) {
+        return Err(AppError::NotFound("Friend request not found".into()));
+    }
+
+    if payload.accept {
+        accept_request(&db, payload.friend_id, user_id).await?;
+        return Ok((StatusCode::OK, Json("Friend request accepted")));
+    }
+
+    delete_relation(&db, payload.friend_id, user_id).await?;
+
+    Ok((StatusCode::OK, Json("Friend request declined")))
+}
+
+/// Ends a friendship or withdraws a request in either direction. Blocks are
+/// left alone, those are undone with unblock.
+pub async fn remove_friend_handler(
+    Extension(user_id): Extension<Uuid>,
+    Extension(db): Extension<DatabaseConnection>,
+    Json(payload): Json<FriendIdReq>,
+) -> Result<impl IntoResponse, AppError> {
+    payload.check()?;
+
+    let res = friend_collections::Entity::delete_many()
+        .filter(between(user_id, payload.friend_id))
+        .filter(friend_collections::Column::Status.ne(FriendStatus::Blocked))
+        .exec(&db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    if res.rows_affected == 0 {
+        return Err(AppError::NotFound("Friend not found".into()));
+    }
+
+    Ok((StatusCode::OK, Json("Friend removed successfully")))
+}
+
+/// Blocking ends any friendship or request between the two users and stops
+/// the blocked user from sending requests or adding the blocker to groups.
+pub async fn block_user_handler(
+    Extension(user_id): Extension<Uuid>,
+    Extension(db): Extension<DatabaseConnection>,
+    Json(payload): Json<FriendIdReq>,
+) -> Result<impl IntoResponse, AppError> {
+    payload.check()?;
+    if payload.friend_id == user_id {
+        return Err(AppError::ValidationError("You cannot block yourself".into()));
+    }
+    users::Entity::find_by_id(payload.friend_id)
+        .one(&db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?
+        .ok_or(AppError::NotFound("User not found".to_string()))?;
+
+    let txn = db
+        .begin()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    // their own block on us, if any, stays in place
+    friend_collections::Entity::delete_many()
+        .filter(friend_collections::Column::UserId.eq(payload.friend_id))
+        .filter(friend_collections::Column::FriendId.eq(user_id))
+        .filter(friend_collections::Column::Status.ne(FriendStatus::Blocked))
+        .exec(&txn)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    set_status(&txn, user_id, payload.friend_id, FriendStatus::Blocked).await?;
+
+    txn.commit()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    Ok((StatusCode::OK, Json("User blocked successfully")))
+}
+
+pub async fn unblock_user_handler(
+    Extension(user_id): Extension<Uuid>,
+    Extension(db): Extension<DatabaseConnection>,
+    Json(payload): Json<FriendIdReq>,
+) -> Result<impl IntoResponse, AppError> {
+    payload.check()?;
+
+    let res = friend_collections::Entity::delete_many()
+        .filter(friend_collections::Column::UserId.eq(user_id))
+        .filter(friend_collections::Column::FriendId.eq(payload.friend_id))
+        .filter(friend_collections::Column::Status.eq(FriendStatus::Blocked))
+        .exec(&db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    if res.rows_affected == 0 {
+        return Err(AppError::NotFound("User is not blocked".into()));
+    }
+
+    Ok((StatusCode::OK, Json("User unblocked successfully")))
+}
+
+pub async fn get_friends_handler(
+    Extension(user_id): Extension<Uuid>,
+    Extension(db): Extension<DatabaseConnection>,
+) -> Result<impl IntoResponse, AppError> {
+    let relations = friend_collections::Entity::find()
+        .filter(
+            Condition::any()
+                .add(friend_collections::Column::UserId.eq(user_id))
+                .add(
+                    Condition::all()
+                        .add(friend_collections::Column::FriendId.eq(user_id))
+                        .add(friend_collections::Column::Status.eq(FriendStatus::Pending)),
+                ),
+        )
+        .order_by_desc(friend_collections::Column::UpdatedAt)
+        .all(&db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    let other = |relation: &friend_collections::Model| {
+        if relation.user_id == user_id {
+            relation.friend_id
+        } else {
+            relation.user_id
+        }
+    };
+    let users_by_id: HashMap<Uuid, users::Model> = users::Entity::find()
+        .filter(users::Column::Id.is_in(relations.iter().map(other).collect::<Vec<_>>()))
+        .all(&db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?
+        .into_iter()
+        .map(|user| (user.id, user))
+        .collect();
+
+    let mut res = FriendsRes {
+        friends: Vec::new(),
+        incoming_requests: Vec::new(),
+        outgoing_requests: Vec::new(),
+        blocked: Vec::new(),
+    };
+    for relation in relations {
+        let user = match users_by_id.get(&other(&relation)) {
+            Some(user) => user.clone(),
+            None => continue,
+        };
+        match (relation.user_id == user_id, relation.status) {
+            (true, FriendStatus::Accepted) => res.friends.push(FriendRes::from((relation, user))),
+            (true, FriendStatus::Pending) => res.outgoing_requests.push(FriendRequestRes::from((relation, user))),
+            (true, FriendStatus::Blocked) => res.blocked.push(FriendRequestRes::from((relation, user))),
+            (false, _) => res.incoming_requests.push(FriendRequestRes::from((relation, user))),
+        }
+    }
+
+    Ok((StatusCode::OK, AxumJson(res)))
+}
+
+async fn find_user(
+    db: &DatabaseConnection,
+    payload: &SendFriendRequestReq,
+) -> Result<users::Model, AppError> {
+    let mut query = users::Entity::find();
+    if let Some(friend_id) = payload.friend_id {
+        query = query.filter(users::Column::Id.eq(friend_id));
+    } else if let Some(email) = &payload.email {
+        query = query.filter(
+            Expr::expr(Func::lower(Expr::col(users::Column::Email))).eq(email.clone()),
+        );
+    } else if let Some(username) = &payload.username {
+        query = query.filter(users::Column::Username.eq(username.clone()));
+    }
+
+    let mut matches = query
+        .all(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    match matches.len() {
+        0 => Err(AppError::NotFound("User not found".to_string())),
+        1 => Ok(matches.remove(0)),
+        _ => Err(AppError::ValidationError(
+            "Several users match, add them by email instead".into(),
+        )),
+    }
+}
+
+/// Turns the pending request from `sender_id` into a friendship both ways.
+async fn accept_request(
+    db: &DatabaseConnection,
+    sender_id: Uuid,
+    receiver_id: Uuid,
+) -> Result<(), AppError> {
+    let txn = db
+        .begin()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    set_status(&txn, sender_id, receiver_id, FriendStatus::Accepted).await?;
+    set_status(&txn, receiver_id, sender_id, FriendStatus::Accepted).await?;
+    txn.commit()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))
+}
+
+async fn find_relation<C: ConnectionTrait>(
+    db: &C,
+    user_id: Uuid,
+    friend_id: Uuid,
+) -> Result<Option<friend_collections::Model>, AppError> {
+    friend_collections::Entity::find()
+        .filter(friend_collections::Column::UserId.eq(user_id))
+        .filter(friend_collections::Column::FriendId.eq(friend_id))
+        .one(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))
+}
+
+async fn set_status<C: ConnectionTrait>(
+    db: &C,
+    user_id: Uuid,
+    friend_id: Uuid,
+    status: FriendStatus,
+) -> Result<(), AppError> {
+    match find_relation(db, user_id, friend_id).await? {
+        Some(relation) => {
+            let mut relation: friend_collections::ActiveModel = relation.into();
+            relation.status = Set(status);
+            relation.updated_at = Set(Utc::now().into());
+            relation
+                .update(db)
+                .await
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+        None => {
+            friend_collections::ActiveModel {
+                id: Set(Uuid::new_v4()),
+                user_id: Set(user_id),
+                friend_id: Set(friend_id),
+                status: Set(status),
+                created_at: Set(Utc::now().into()),
+                updated_at: Set(Utc::now().into()),
+            }
+            .insert(db)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+    }
+    Ok(())
+}
+
+async fn delete_relation<C: ConnectionTrait>(
+    db: &C,
+    user_id: Uuid,
+    friend_id: Uuid,
+) -> Result<(), AppError> {
+    friend_collections::Entity::delete_many()
+        .filter(friend_collections::Column::UserId.eq(user_id))
+        .filter(friend_collections::Column::FriendId.eq(friend_id))
+        .exec(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    Ok(())
+}
+
+/// Rows in either direction between two users.
+fn between(user_id: Uuid, friend_id: Uuid) -> Condition {
+    Condition::any()
+        .add(
+            Condition::all()
+                .add(friend_collections::Column::UserId.eq(user_id))
+                .add(friend_collections::Column::FriendId.eq(friend_id)),
+        )
+        .add(
+            Condition::all()
+                .add(friend_collections::Column::UserId.eq(friend_id))
+                .add(friend_collections::Column::FriendId.eq(user_id)),
+        )
+}
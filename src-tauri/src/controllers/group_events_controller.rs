@@ -0,0 +1,363 @@
+use chrono::{DateTime, Utc};
+use crate::custom_errors::app::AppError;
+use crate::entities::sea_orm_active_enums::GroupEventType;
+use crate::entities::{group_events, group_members};
+use crate::models::group_events::StreamEventsReq;
+use crate::request_verifier::groups::{check_group_exists, check_user_exists_in_group};
+use crate::utils::event_bus::EventBus;
+use axum::{
+    extract::{Extension, Query},
+    http::HeaderMap,
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse,
+    },
+};
+use sea_orm::sea_query::Condition;
+use sea_orm::{
+    ActiveEnum, ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait,
+    QueryFilter, QueryOrder, QuerySelect, Set,
+};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet, VecDeque};
+use std::convert::Infallible;
+use tokio::sync::broadcast::{error::RecvError, Receiver};
+use uuid::Uuid;
+
+const CATCH_UP_PAGE_SIZE: u64 = 500;
+// a transaction open longer than this may commit an event the stream has
+// already moved past
+const LATE_COMMIT_GRACE_SECONDS: i64 = 30;
+
+/// Server-sent events for the caller's groups and notifications. Clients that
+/// reconnect with `Last-Event-ID` first get what they missed from the event
+/// log, then live events.
+///
+/// Ids come from a sequence but events are committed out of order, so the SSE
+/// id is not the event's own id (that one is in the data) but the point up to
+/// which nothing was skipped. Resuming from it may repeat a few events and never
+/// loses one that committed late.
+pub async fn stream_events_handler(
+    Extension(user_id): Extension<Uuid>,
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(bus): Extension<EventBus>,
+    headers: HeaderMap,
+    Query(payload): Query<StreamEventsReq>,
+) -> Result<impl IntoResponse, AppError> {
+    payload.check()?;
+
+    let group_ids: HashSet<Uuid> = match payload.group_id {
+        Some(group_id) => {
+            check_group_exists(&db, group_id).await?;
+            check_user_exists_in_group(&db, group_id, user_id).await?;
+            HashSet::from([group_id])
+        }
+        None => group_members::Entity::find()
+            .filter(group_members::Column::MemberId.eq(user_id))
+            .select_only()
+            .column(group_members::Column::GroupId)
+            .into_tuple::<Uuid>()
+            .all(&db)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?
+            .into_iter()
+            .collect(),
+    };
+
+    let last_event_id = headers
+        .get("Last-Event-ID")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<i64>().ok())
+        .or(payload.last_event_id);
+
+    // subscribed before catching up so nothing committed in between is lost
+    let receiver = bus.subscribe();
+    let cursor = match last_event_id {
+        Some(last_event_id) => last_event_id,
+        None => group_events::Entity::find()
+            .order_by_desc(group_events::Column::Id)
+            .one(&db)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?
+            .map(|event| event.id)
+            .unwrap_or(0),
+    };
+
+    let stream = EventStream {
+        receiver,
+        db,
+        user_id,
+        group_ids,
+        all_groups: payload.group_id.is_none(),
+        queue: VecDeque::new(),
+        delivered: Delivered::new(cursor),
+        read_to: cursor,
+        catching_up: last_event_id.is_some(),
+    };
+    let events = futures::stream::unfold(stream, |mut stream| async move {
+        stream
+            .next_event()
+            .await
+            .map(|event| (Ok::<_, Infallible>(event), stream))
+    });
+
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
+
+/// Records an event for every member of `group_id`. Publish the returned event
+/// on the bus once the surrounding transaction has committed.
+pub async fn record_group_event<C: ConnectionTrait>(
+    db: &C,
+    event_type: GroupEventType,
+    group_id: Uuid,
+    payload: impl Serialize,
+) -> Result<group_events::Model, AppError> {
+    record_event(db, event_type, Some(group_id), None, payload).await
+}
+
+/// Records an event only `user_id` receives.
+pub async fn record_user_event<C: ConnectionTrait>(
+    db: &C,
+    event_type: GroupEventType,
+    user_id: Uuid,
+    payload: impl Serialize,
+) -> Result<group_events::Model, AppError> {
+    record_event(db, event_type, None, Some(user_id), payload).await
+}
+
+async fn record_event<C: ConnectionTrait>(
+    db: &C,
+    event_type: GroupEventType,
+    group_id: Option<Uuid>,
+    user_id: Option<Uuid>,
+    payload: impl Serialize,
+) -> Result<group_events::Model, AppError> {
+    let payload = serde_json::to_value(payload).map_err(|_| AppError::InternalServerError)?;
+    group_events::ActiveModel {
+        event_type: Set(event_type),
+        group_id: Set(group_id),
+        user_id: Set(user_id),
+        payload: Set(payload),
+        created_at: Set(Utc::now().into()),
+        ..Default::default()
+    }
+    .insert(db)
+    .await
+    .map_err(|e| AppError::DatabaseError(e.to_string()))
+}
+
+struct EventStream {
+    receiver: Receiver<group_events::Model>,
+    db: DatabaseConnection,
+    user_id: Uuid,
+    group_ids: HashSet<Uuid>,
+    // follows the user into groups they join while connected
+    all_groups: bool,
+    queue: VecDeque<group_events::Model>,
+    delivered: Delivered,
+    // highest id read from the log, catching up continues after it
+    read_to: i64,
+    catching_up: bool,
+}
+
+/// Which event ids a stream is done with. Live events and log pages overlap and
+/// arrive out of order, this is what keeps each event to a single delivery.
+struct Delivered {
+    // every id up to here was delivered, filtered out or given up on
+    through: i64,
+    // ids above `through` already handled, with when they were recorded
+    ahead: BTreeMap<i64, DateTime<Utc>>,
+}
+
+impl Delivered {
+    fn new(through: i64) -> Self {
+        Self {
+            through,
+            ahead: BTreeMap::new(),
+        }
+    }
+
+    /// Marks `id` as handled. Returns false when it already was.
+    fn mark(&mut self, id: i64, created_at: DateTime<Utc>, now: DateTime<Utc>) -> bool {
+        if id <= self.through || self.ahead.contains_key(&id) {
+            return false;
+        }
+        self.ahead.insert(id, created_at);
+
+        // a missing id below an event recorded this long ago belongs to a
+        // transaction that rolled back or to a group this stream does not follow
+        let grace = chrono::Duration::seconds(LATE_COMMIT_GRACE_SECONDS);
+        while let Some((&next, &recorded_at)) = self.ahead.iter().next() {
+            if next != self.through + 1 && now - recorded_at < grace {
+                break;
+            }
+            self.through = next;
+            self.ahead.remove(&next);
+        }
+        true
+    }
+}
+
+impl EventStream {
+    async fn next_event(&mut self) -> Option<Event> {
+        loop {
+            if let Some(event) = self.queue.pop_front() {
+                if !self.delivered.mark(event.id, event.created_at.into(), Utc::now()) {
+                    continue;
+                }
+                if !self.admit(&event) {
+                    continue;
+                }
+                match Event::default()
+                    .id(self.delivered.through.to_string())
+                    .event(event.event_type.to_value())
+                    .json_data(&event)
+                {
+                    Ok(sse_event) => return Some(sse_event),
+                    Err(err) => {
+                        eprintln!("Failed to encode group event {}: {}", event.id, err);
+                        continue;
+                    }
+                }
+            }
+
+            if self.catching_up {
+                match self.load_page().await {
+                    Ok(page) => {
+                        self.catching_up = page.len() as u64 == CATCH_UP_PAGE_SIZE;
+                        if let Some(last) = page.last() {
+                            self.read_to = self.read_to.max(last.id);
+                        }
+                        self.queue.extend(page);
+                        continue;
+                    }
+                    Err(err) => {
+                        eprintln!("Failed to load missed group events: {}", err);
+                        return None;
+                    }
+                }
+            }
+
+            match self.receiver.recv().await {
+                // repeats of what the log already gave are dropped when dequeued
+                Ok(event) => self.queue.push_back(event),
+                // fell behind the channel, the log still has everything after `through`
+                Err(RecvError::Lagged(_)) => {
+                    self.read_to = self.delivered.through;
+                    self.catching_up = true;
+                }
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    }
+
+    /// Whether the user gets `event`, keeping track of the groups they join and leave.
+    fn admit(&mut self, event: &group_events::Model) -> bool {
+        if let Some(user_id) = event.user_id {
+            return self.all_groups && user_id == self.user_id;
+        }
+        let group_id = match event.group_id {
+            Some(group_id) => group_id,
+            None => return false,
+        };
+
+        let about_user = member_id(event) == Some(self.user_id);
+        if about_user && event.event_type == GroupEventType::MemberJoined && self.all_groups {
+            self.group_ids.insert(group_id);
+        }
+        let visible = self.group_ids.contains(&group_id);
+        // they still hear that they left, nothing after that
+        if about_user && event.event_type == GroupEventType::MemberLeft {
+            self.group_ids.remove(&group_id);
+        }
+        visible
+    }
+
+    async fn load_page(&self) -> Result<Vec<group_events::Model>, AppError> {
+        let mut audience = Condition::any();
+        if !self.group_ids.is_empty() {
+            audience = audience.add(
+                group_events::Column::GroupId.is_in(self.group_ids.iter().copied().collect::<Vec<_>>()),
+            );
+        }
+        if self.all_groups {
+            audience = audience.add(group_events::Column::UserId.eq(self.user_id));
+        }
+        if audience.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        group_events::Entity::find()
+            .filter(group_events::Column::Id.gt(self.read_to))
+            .filter(audience)
+            .order_by_asc(group_events::Column::Id)
+            .limit(CATCH_UP_PAGE_SIZE)
+            .all(&self.db)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+}
+
+fn member_id(event: &group_events::Model) -> Option<Uuid> {
+    event
+        .payload
+        .get("member_id")
+        .and_then(|member_id| member_id.as_str())
+        .and_then(|member_id| Uuid::parse_str(member_id).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(seconds: i64) -> DateTime<Utc> {
+        DateTime::<Utc>::from_timestamp(1_750_000_000 + seconds, 0).unwrap()
+    }
+
+    #[test]
+    fn contiguous_ids_move_the_resume_point() {
+        let mut delivered = Delivered::new(10);
+        assert!(delivered.mark(11, at(0), at(0)));
+        assert!(delivered.mark(12, at(0), at(0)));
+        assert_eq!(delivered.through, 12);
+
+        assert!(!delivered.mark(12, at(0), at(1)));
+        assert!(!delivered.mark(9, at(0), at(1)));
+    }
+
+    #[test]
+    fn a_late_commit_below_the_highest_id_is_still_delivered() {
+        let mut delivered = Delivered::new(10);
+        assert!(delivered.mark(12, at(0), at(1)));
+        // 11 may still be in flight, so clients resume from before it
+        assert_eq!(delivered.through, 10);
+
+        assert!(delivered.mark(11, at(0), at(2)));
+        assert_eq!(delivered.through, 12);
+        assert!(!delivered.mark(11, at(0), at(3)));
+    }
+
+    #[test]
+    fn gaps_are_given_up_on_after_the_grace_period() {
+        let mut delivered = Delivered::new(10);
+        assert!(delivered.mark(12, at(0), at(1)));
+        assert_eq!(delivered.through, 10);
+
+        assert!(delivered.mark(14, at(5), at(LATE_COMMIT_GRACE_SECONDS + 1)));
+        // 12 is old enough to skip 11, 14 is not old enough to skip 13
+        assert_eq!(delivered.through, 12);
+        assert!(delivered.mark(13, at(5), at(LATE_COMMIT_GRACE_SECONDS + 2)));
+        assert_eq!(delivered.through, 14);
+    }
+
+    #[test]
+    fn old_log_pages_do_not_wait_for_gaps() {
+        let mut delivered = Delivered::new(0);
+        let now = at(3600);
+        for id in [3, 4, 9, 20] {
+            assert!(delivered.mark(id, at(0), now));
+        }
+        assert_eq!(delivered.through, 20);
+        assert!(delivered.ahead.is_empty());
+    }
+}
@@ -0,0 +1,333 @@
+use chrono::Utc;
+use crate::controllers::group_events_controller::record_group_event;
+use crate::custom_errors::app::AppError;
+use crate::entities::sea_orm_active_enums::{GroupEventType, GroupRole};
+use crate::entities::{group_invites, group_members, groups};
+use crate::models::group_invites::{
+    CreateInviteReq, GetInvitesReq, InviteRes, JoinCodeReq, JoinCodeRes, JoinGroupReq,
+    RevokeInviteReq,
+};
+use crate::models::group_members::GroupMember;
+use crate::models::groups::GroupRes;
+use crate::request_verifier::groups::{
+    check_group_exists, check_group_not_archived, check_user_can_write_in_group,
+    check_user_exists_in_group, check_user_is_admin_in_group,
+};
+use crate::utils::event_bus::EventBus;
+use crate::utils::invites::{default_invite_ttl, generate_invite_token, generate_join_code};
+use axum::{
+    extract::{Extension, Json},
+    http::StatusCode,
+    response::IntoResponse,
+    Json as AxumJson,
+};
+use sea_orm::sea_query::{Condition, Expr, OnConflict};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, IntoActiveModel,
+    PaginatorTrait, QueryFilter, QueryOrder, Set, TransactionTrait,
+};
+use uuid::Uuid;
+
+// a fresh code colliding with an existing one is rare, doing so repeatedly means something is off
+const JOIN_CODE_ATTEMPTS: usize = 5;
+
+/// Anyone who can add members can hand out invite links.
+pub async fn create_invite_handler(
+    Extension(user_id): Extension<Uuid>,
+    Extension(db): Extension<DatabaseConnection>,
+    Json(payload): Json<CreateInviteReq>,
+) -> Result<impl IntoResponse, AppError> {
+    payload.check()?;
+    check_group_exists(&db, payload.group_id).await?;
+    check_group_not_archived(&db, payload.group_id).await?;
+    check_user_can_write_in_group(&db, payload.group_id, user_id).await?;
+
+    let ttl = match payload.expires_in_hours {
+        Some(hours) => chrono::Duration::hours(hours),
+        None => default_invite_ttl()?,
+    };
+
+    let invite = group_invites::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        group_id: Set(payload.group_id),
+        token: Set(generate_invite_token()),
+        created_by: Set(user_id),
+        expires_at: Set((Utc::now() + ttl).into()),
+        max_uses: Set(payload.max_uses),
+        use_count: Set(0),
+        revoked_at: Set(None),
+        created_at: Set(Utc::now().into()),
+    }
+    .insert(&db)
+    .await
+    .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    Ok((StatusCode::CREATED, AxumJson(InviteRes::from(invite))))
+}
+
+/// Invites that can still be used: not revoked, not expired and not used up.
+pub async fn get_invites_handler(
+    Extension(user_id): Extension<Uuid>,
+    Extension(db): Extension<DatabaseConnection>,
+    Json(payload): Json<GetInvitesReq>,
+) -> Result<impl IntoResponse, AppError> {
+    payload.check()?;
+    check_group_exists(&db, payload.group_id).await?;
+    check_user_is_admin_in_group(&db, payload.group_id, user_id).await?;
+
+    let invites: Vec<InviteRes> = group_invites::Entity::find()
+        .filter(group_invites::Column::GroupId.eq(payload.group_id))
+        .filter(usable_invite())
+        .order_by_desc(group_invites::Column::CreatedAt)
+        .all(&db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?
+        .into_iter()
+        .map(InviteRes::from)
+        .collect();
+
+    Ok((StatusCode::OK, AxumJson(invites)))
+}
+
+pub async fn revoke_invite_handler(
+    Extension(user_id): Extension<Uuid>,
+    Extension(db): Extension<DatabaseConnection>,
+    Json(payload): Json<RevokeInviteReq>,
+) -> Result<impl IntoResponse, AppError> {
+    payload.check()?;
+
+    let invite = group_invites::Entity::find_by_id(payload.invite_id)
+        .filter(group_invites::Column::RevokedAt.is_null())
+        .one(&db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?
+        .ok_or(AppError::NotFound("Invite not found".to_string()))?;
+    check_user_exists_in_group(&db, invite.group_id, user_id).await?;
+
+    if invite.created_by != user_id {
+        check_user_is_admin_in_group(&db, invite.group_id, user_id)
+            .await
+            .map_err(|_| {
+                AppError::Unauthorized("You are not authorized to revoke this invite".to_string())
+            })?;
+    }
+
+    let mut invite_model: group_invites::ActiveModel = invite.into();
+    invite_model.revoked_at = Set(Some(Utc::now().into()));
+    invite_model
+        .update(&db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    Ok((StatusCode::OK, Json("Invite revoked successfully")))
+}
+
+/// Adds the caller to the group behind an invite token or join code. Joining
+/// through an invite uses it up, so the token is only counted when the
+/// membership is actually created.
+pub async fn join_group_handler(
+    Extension(user_id): Extension<Uuid>,
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(bus): Extension<EventBus>,
+    Json(mut payload): Json<JoinGroupReq>,
+) -> Result<impl IntoResponse, AppError> {
+    payload.check()?;
+
+    let (group, invite) = match (&payload.token, &payload.join_code) {
+        (Some(token), _) => {
+            let invite = group_invites::Entity::find()
+                .filter(group_invites::Column::Token.eq(token.clone()))
+                .filter(usable_invite())
+                .one(&db)
+                .await
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?
+                .ok_or(AppError::NotFound("Invite not found or no longer valid".to_string()))?;
+            let group = groups::Entity::find_by_id(invite.group_id)
+                .one(&db)
+                .await
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?
+                .ok_or(AppError::NotFound("Group not found".to_string()))?;
+            (group, Some(invite))
+        }
+        (None, Some(join_code)) => {
+            let group = groups::Entity::find()
+                .filter(groups::Column::JoinCode.eq(join_code.clone()))
+                .one(&db)
+                .await
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?
+                .ok_or(AppError::NotFound("No group uses this join code".to_string()))?;
+            (group, None)
+        }
+        (None, None) => unreachable!("checked by JoinGroupReq::check"),
+    };
+
+    check_group_not_archived(&db, group.id).await?;
+    if check_user_exists_in_group(&db, group.id, user_id).await.is_ok() {
+        return Err(AppError::DuplicateError(
+            "You are already a member of this group".into(),
+        ));
+    }
+
+    let txn = db
+        .begin()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    let member = group_members::Model {
+        id: Uuid::new_v4(),
+        group_id: group.id,
+        member_id: user_id,
+        role: GroupRole::Member,
+        joined_at: Utc::now().into(),
+    };
+    // the check above can race with a second join, say a double click, which
+    // must neither add the member twice nor use the invite up twice
+    let inserted = group_members::Entity::insert(member.clone().into_active_model().reset_all())
+        .on_conflict(
+            OnConflict::columns([group_members::Column::GroupId, group_members::Column::MemberId])
+                .do_nothing()
+                .to_owned(),
+        )
+        .exec_without_returning(&txn)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    if inserted == 0 {
+        return Err(AppError::DuplicateError(
+            "You are already a member of this group".into(),
+        ));
+    }
+
+    if let Some(invite) = invite {
+        // conditional so two people racing for the last use cannot both get in
+        let res = group_invites::Entity::update_many()
+            .col_expr(
+                group_invites::Column::UseCount,
+                Expr::col(group_invites::Column::UseCount).add(1),
+            )
+            .filter(group_invites::Column::Id.eq(invite.id))
+            .filter(usable_invite())
+            .exec(&txn)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        if res.rows_affected == 0 {
+            return Err(AppError::NotFound("Invite not found or no longer valid".to_string()));
+        }
+    }
+
+    let event = record_group_event(&txn, GroupEventType::MemberJoined, group.id, GroupMember::from(member)).await?;
+
+    // the response names the group's owner as its admin, not the new member
+    let admins = group_members::Entity::find()
+        .filter(group_members::Column::GroupId.eq(group.id))
+        .filter(group_members::Column::Role.is_in([GroupRole::Owner, GroupRole::Admin]))
+        .order_by_asc(group_members::Column::JoinedAt)
+        .all(&txn)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    let admin = admins
+        .iter()
+        .find(|admin| admin.role == GroupRole::Owner)
+        .or(admins.first())
+        .cloned()
+        .ok_or(AppError::InternalServerError)?;
+
+    txn.commit()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    bus.publish(vec![event]);
+
+    Ok((StatusCode::OK, AxumJson(GroupRes::from((group, admin)))))
+}
+
+/// The group's join code, created the first time it is asked for.
+pub async fn get_join_code_handler(
+    Extension(user_id): Extension<Uuid>,
+    Extension(db): Extension<DatabaseConnection>,
+    Json(payload): Json<JoinCodeReq>,
+) -> Result<impl IntoResponse, AppError> {
+    payload.check()?;
+    check_group_exists(&db, payload.group_id).await?;
+    check_user_can_write_in_group(&db, payload.group_id, user_id).await?;
+
+    let group = find_group(&db, payload.group_id).await?;
+    let join_code = match group.join_code {
+        Some(join_code) => join_code,
+        None => {
+            let join_code = unused_join_code(&db).await?;
+            // someone else may have just created one, theirs wins
+            groups::Entity::update_many()
+                .col_expr(groups::Column::JoinCode, Expr::value(join_code))
+                .filter(groups::Column::Id.eq(payload.group_id))
+                .filter(groups::Column::JoinCode.is_null())
+                .exec(&db)
+                .await
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            find_group(&db, payload.group_id)
+                .await?
+                .join_code
+                .ok_or(AppError::InternalServerError)?
+        }
+    };
+
+    Ok((StatusCode::OK, AxumJson(JoinCodeRes { group_id: payload.group_id, join_code })))
+}
+
+/// Replaces the join code, so the old one stops working.
+pub async fn reset_join_code_handler(
+    Extension(user_id): Extension<Uuid>,
+    Extension(db): Extension<DatabaseConnection>,
+    Json(payload): Json<JoinCodeReq>,
+) -> Result<impl IntoResponse, AppError> {
+    payload.check()?;
+    check_group_exists(&db, payload.group_id).await?;
+    check_user_is_admin_in_group(&db, payload.group_id, user_id).await?;
+
+    let join_code = unused_join_code(&db).await?;
+    groups::Entity::update_many()
+        .col_expr(groups::Column::JoinCode, Expr::value(join_code.clone()))
+        .col_expr(groups::Column::UpdatedAt, Expr::value(Utc::now()))
+        .filter(groups::Column::Id.eq(payload.group_id))
+        .exec(&db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    Ok((StatusCode::OK, AxumJson(JoinCodeRes { group_id: payload.group_id, join_code })))
+}
+
+/// A join code no group is using yet.
+pub async fn unused_join_code<C: ConnectionTrait>(db: &C) -> Result<String, AppError> {
+    for _ in 0..JOIN_CODE_ATTEMPTS {
+        let join_code = generate_join_code();
+        let taken = groups::Entity::find()
+            .filter(groups::Column::JoinCode.eq(join_code.clone()))
+            .count(db)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        if taken == 0 {
+            return Ok(join_code);
+        }
+    }
+    Err(AppError::InternalServerError)
+}
+
+fn usable_invite() -> Condition {
+    Condition::all()
+        .add(group_invites::Column::RevokedAt.is_null())
+        .add(group_invites::Column::ExpiresAt.gt(Utc::now()))
+        .add(
+            Condition::any()
+                .add(group_invites::Column::MaxUses.is_null())
+                .add(
+                    Expr::col(group_invites::Column::UseCount)
+                        .lt(Expr::col(group_invites::Column::MaxUses)),
+                ),
+        )
+}
+
+async fn find_group(db: &DatabaseConnection, group_id: Uuid) -> Result<groups::Model, AppError> {
+    groups::Entity::find_by_id(group_id)
+        .one(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?
+        .ok_or(AppError::NotFound("Group not found".to_string()))
+}
@@ -1,27 +1,44 @@
 use chrono::Utc;
 use crate::entities::groups;
-use crate::models::group_members::{AddGroupMemberReq, AddGroupMemberRes,RemoveGroupMemberReq};
+use crate::controllers::group_events_controller::record_group_event;
+use crate::controllers::notifications_controller::{notification_context, notify, NewNotification};
+use crate::entities::sea_orm_active_enums::{GroupEventType, GroupRole, NotificationType};
+use crate::utils::event_bus::EventBus;
+use crate::models::group_members::{
+    AddGroupMemberReq, AddGroupMemberRes, GroupMember, RemoveGroupMemberReq, UpdateMemberRoleReq,
+};
 use crate::entities::group_members::{self, ActiveModel};
 use crate::custom_errors::app::AppError;
 use axum::{
     extract::{Json, Extension},
     response::IntoResponse,
-    http::StatusCode
+    http::StatusCode,
+    Json as AxumJson,
 };
 use crate::request_verifier::{
     groups::{
         check_group_exists,
+        check_group_not_archived,
+        check_user_can_write_in_group,
         check_user_exists_in_group,
-        check_user_is_admin_in_group
+        check_user_is_admin_in_group,
+        find_member_role,
     },
-    group_members::check_group_member_exists_in_group 
+    group_members::check_group_member_exists_in_group,
+    friends::check_not_blocked_by,
+};
+use sea_orm::sea_query::{Expr, OnConflict};
+use serde_json::json;
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, QueryOrder, QuerySelect, Set,
+    TransactionTrait,
 };
-use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, QueryOrder, QuerySelect, Set};
 use uuid::Uuid;
 
 pub async fn add_member_to_group(
     Extension(user_id): Extension<Uuid>,
     Extension(db): Extension<sea_orm::DatabaseConnection>,
+    Extension(bus): Extension<EventBus>,
     Json(payload): Json<AddGroupMemberReq>,
 ) -> Result<impl IntoResponse, AppError> {
     payload.check()?;
@@ -30,7 +47,9 @@ pub async fn add_member_to_group(
         return Err(err);
     }
 
-    if let Err(err) = check_user_exists_in_group(&db, payload.group_id, user_id).await {
+    check_group_not_archived(&db, payload.group_id).await?;
+
+    if let Err(err) = check_user_can_write_in_group(&db, payload.group_id, user_id).await {
         return Err(err);
     }
 
@@ -40,10 +59,14 @@ pub async fn add_member_to_group(
             .await
             .is_err()
         {
+            // people who blocked the caller cannot be put into groups by them
+            check_not_blocked_by(&db, user_id, member_id).await?;
+
             new_members.push(group_members::ActiveModel {
                 id: Set(Uuid::new_v4()),
                 group_id: Set(payload.group_id),
                 member_id: Set(member_id),
+                role: Set(GroupRole::Member),
                 joined_at: Set(Utc::now().into()),
             });
         }
@@ -55,7 +78,14 @@ pub async fn add_member_to_group(
         .collect();
 
     
+    // someone joining at the same moment keeps their membership, only the rows
+    // actually inserted are reported below
     let _insert_result = group_members::Entity::insert_many(new_members)
+        .on_conflict(
+            OnConflict::columns([group_members::Column::GroupId, group_members::Column::MemberId])
+                .do_nothing()
+                .to_owned(),
+        )
         .on_empty_do_nothing()
         .exec(&db)
         .await
@@ -68,6 +98,31 @@ pub async fn add_member_to_group(
         .await
         .map_err(|e| AppError::DatabaseError(e.to_string()))?;
 
+    let mut events = Vec::new();
+    for member in &inserted_models {
+        events.push(
+            record_group_event(&db, GroupEventType::MemberJoined, payload.group_id, GroupMember::from(member.clone()))
+                .await?,
+        );
+    }
+
+    if !inserted_models.is_empty() {
+        let (actor_name, group) = notification_context(&db, user_id, payload.group_id).await?;
+        events.extend(notify(
+            &db,
+            inserted_models.iter().map(|member| member.member_id),
+            NewNotification {
+                type_: NotificationType::AddedToGroup,
+                actor_id: user_id,
+                message: format!("{} added you to {}", actor_name, group.group_name),
+                group_id: Some(group.id),
+                activity_id: None,
+            },
+        )
+        .await?);
+    }
+    bus.publish(events);
+
     Ok((StatusCode::OK, Json(AddGroupMemberRes::from(inserted_models))))
 }
 
@@ -75,93 +130,215 @@ pub async fn add_member_to_group(
 pub async fn remove_group_member(
     Extension(user_id): Extension<Uuid>,
     Extension(db): Extension<sea_orm::DatabaseConnection>,
+    Extension(bus): Extension<EventBus>,
     Json(payload): Json<RemoveGroupMemberReq>,
 ) -> Result<impl IntoResponse, AppError> {
     payload.check()?;
     
     check_group_exists(&db, payload.group_id).await?;
-    
-    let group = groups::Entity::find_by_id(payload.group_id)
-        .one(&db)
+    check_group_not_archived(&db, payload.group_id).await?;
+
+    let caller_role = find_member_role(&db, payload.group_id, user_id).await?;
+    let member_role = find_member_role(&db, payload.group_id, payload.member_id)
         .await
-        .map_err(|e| AppError::DatabaseError(e.to_string()))?
-        .ok_or_else(|| AppError::NotFound("Group not found".into()))?;
-    
-    if group.creator_id != user_id {
-        return Err(AppError::Unauthorized("Only group admin can remove members".into()));
+        .map_err(|_| AppError::NotFound("Group member not found".into()))?;
+
+    // anyone can leave, removing someone else depends on both roles
+    if payload.member_id != user_id {
+        if !caller_role.is_admin() {
+            return Err(AppError::Unauthorized("Only group admins can remove members".into()));
+        }
+        if member_role == GroupRole::Owner {
+            return Err(AppError::Unauthorized("The group owner cannot be removed".into()));
+        }
+        if member_role == GroupRole::Admin && caller_role != GroupRole::Owner {
+            return Err(AppError::Unauthorized("Only the group owner can remove admins".into()));
+        }
     }
 
-    // Check if the member being removed is the admin
-    if payload.member_id == user_id {
-        // Find the next oldest member to make admin
-        let next_admin = group_members::Entity::find()
+    let txn = db
+        .begin()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    // an owner leaving hands the group over to the oldest admin, or failing that the oldest member
+    let mut successor = None;
+    if member_role == GroupRole::Owner {
+        successor = group_members::Entity::find()
             .filter(group_members::Column::GroupId.eq(payload.group_id))
-            .filter(group_members::Column::MemberId.ne(user_id))
+            .filter(group_members::Column::MemberId.ne(payload.member_id))
             .order_by_asc(group_members::Column::JoinedAt)
-            .one(&db)
-            .await
-            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
-        
-        // If there's at least one other member, make them the admin
-        if let Some(new_admin) = next_admin {
-            let mut group_model: groups::ActiveModel = group.into();
-            
-            // println!("New admin assigned: {:?}", new_admin.member_id);
-            
-            group_model.creator_id = Set(new_admin.member_id);
-            group_model.updated_at = Set(Utc::now().into());
-            
-
-            let _updated_group = group_model
-                .update(&db)
-                .await
-                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
-            
-            // Now remove the admin from the group members
-            let res = group_members::Entity::delete_many()
-                .filter(group_members::Column::GroupId.eq(payload.group_id))
-                .filter(group_members::Column::MemberId.eq(user_id))
-                .exec(&db)
-                .await
-                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
-            
-            if res.rows_affected == 0 {
-                return Err(AppError::NotFound("Member not found in group".into()));
-            }
-            
-            return Ok((StatusCode::OK, Json("Admin privileges transferred and removed from group successfully")));
-        } else {
-
-            // ye uncomment tab karna agar group bhi delete karna hai for last member 
-            // for last member delete group
-            // let _delete_group = groups::Entity::delete_by_id(payload.group_id)
-            // .exec(&db)
-            // .await
-            // .map_err(|e| AppError::DatabaseError(e.to_string()))?;
-        
-        // Remove the last member
-        let res = group_members::Entity::delete_many()
-            .filter(group_members::Column::GroupId.eq(payload.group_id))
-            .filter(group_members::Column::MemberId.eq(user_id))
-            .exec(&db)
-            .await
-            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
-        
-        return Ok((StatusCode::OK, Json("Last member has left group")));
-            }
-    } else {
-        // If not admin, simply remove the member
-        let res = group_members::Entity::delete_many()
-            .filter(group_members::Column::GroupId.eq(payload.group_id))
-            .filter(group_members::Column::MemberId.eq(payload.member_id))
-            .exec(&db)
+            .all(&txn)
             .await
-            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
-        
-        if res.rows_affected == 0 {
-            return Err(AppError::NotFound("Group member not found".into()));
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?
+            .into_iter()
+            .min_by_key(|member| succession_rank(member.role));
+
+        if let Some(successor) = &successor {
+            transfer_ownership(&txn, payload.group_id, successor.member_id).await?;
         }
     }
-    
+
+    let res = group_members::Entity::delete_many()
+        .filter(group_members::Column::GroupId.eq(payload.group_id))
+        .filter(group_members::Column::MemberId.eq(payload.member_id))
+        .exec(&txn)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    if res.rows_affected == 0 {
+        return Err(AppError::NotFound("Group member not found".into()));
+    }
+
+    let event = record_group_event(
+        &txn,
+        GroupEventType::MemberLeft,
+        payload.group_id,
+        json!({
+            "member_id": payload.member_id,
+            "removed_by": user_id,
+            "new_owner_id": successor.as_ref().map(|member| member.member_id),
+        }),
+    )
+    .await?;
+
+    txn.commit()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    bus.publish(vec![event]);
+
+    if member_role == GroupRole::Owner {
+        if successor.is_some() {
+            return Ok((StatusCode::OK, Json("Ownership transferred and removed from group successfully")));
+        }
+        return Ok((StatusCode::OK, Json("Last member has left group")));
+    }
+
     Ok((StatusCode::OK, Json("Removed from group successfully")))
-}
\ No newline at end of file
+}
+
+/// Admins can make other members admins, members or viewers. Changing an admin's
+/// role is left to the owner, and making someone the owner hands the group over:
+/// the previous owner stays on as an admin.
+pub async fn update_member_role_handler(
+    Extension(user_id): Extension<Uuid>,
+    Extension(db): Extension<sea_orm::DatabaseConnection>,
+    Extension(bus): Extension<EventBus>,
+    Json(payload): Json<UpdateMemberRoleReq>,
+) -> Result<impl IntoResponse, AppError> {
+    payload.check()?;
+    check_group_exists(&db, payload.group_id).await?;
+    check_group_not_archived(&db, payload.group_id).await?;
+    check_user_is_admin_in_group(&db, payload.group_id, user_id).await?;
+
+    if payload.member_id == user_id {
+        return Err(AppError::ValidationError("You cannot change your own role".into()));
+    }
+
+    let caller_role = find_member_role(&db, payload.group_id, user_id).await?;
+    let member_role = find_member_role(&db, payload.group_id, payload.member_id)
+        .await
+        .map_err(|_| AppError::NotFound("Group member not found".into()))?;
+
+    if member_role == GroupRole::Owner {
+        return Err(AppError::Unauthorized(
+            "The owner's role only changes when ownership is transferred".into(),
+        ));
+    }
+    if (payload.role == GroupRole::Owner || member_role == GroupRole::Admin)
+        && caller_role != GroupRole::Owner
+    {
+        return Err(AppError::Unauthorized(
+            "Only the group owner can transfer ownership or change an admin's role".into(),
+        ));
+    }
+
+    let txn = db
+        .begin()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    if payload.role == GroupRole::Owner {
+        set_member_role(&txn, payload.group_id, user_id, GroupRole::Admin).await?;
+        transfer_ownership(&txn, payload.group_id, payload.member_id).await?;
+    } else {
+        set_member_role(&txn, payload.group_id, payload.member_id, payload.role).await?;
+    }
+
+    let mut events = Vec::new();
+    if payload.role == GroupRole::Owner || (payload.role.is_admin() && !member_role.is_admin()) {
+        let (actor_name, group) = notification_context(&txn, user_id, payload.group_id).await?;
+        let title = if payload.role == GroupRole::Owner { "the owner" } else { "an admin" };
+        events = notify(
+            &txn,
+            [payload.member_id],
+            NewNotification {
+                type_: NotificationType::PromotedToAdmin,
+                actor_id: user_id,
+                message: format!("{} made you {} of {}", actor_name, title, group.group_name),
+                group_id: Some(group.id),
+                activity_id: None,
+            },
+        )
+        .await?;
+    }
+
+    txn.commit()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    bus.publish(events);
+
+    let member = group_members::Entity::find()
+        .filter(group_members::Column::GroupId.eq(payload.group_id))
+        .filter(group_members::Column::MemberId.eq(payload.member_id))
+        .one(&db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?
+        .ok_or_else(|| AppError::NotFound("Group member not found".into()))?;
+
+    Ok((StatusCode::OK, AxumJson(GroupMember::from(member))))
+}
+
+/// Makes `member_id` the owner, keeping `groups.creator_id` pointing at them.
+/// The caller is responsible for what happens to the previous owner.
+async fn transfer_ownership<C: ConnectionTrait>(
+    db: &C,
+    group_id: Uuid,
+    member_id: Uuid,
+) -> Result<(), AppError> {
+    set_member_role(db, group_id, member_id, GroupRole::Owner).await?;
+
+    groups::Entity::update_many()
+        .col_expr(groups::Column::CreatorId, Expr::value(member_id))
+        .col_expr(groups::Column::UpdatedAt, Expr::value(Utc::now()))
+        .filter(groups::Column::Id.eq(group_id))
+        .exec(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    Ok(())
+}
+
+async fn set_member_role<C: ConnectionTrait>(
+    db: &C,
+    group_id: Uuid,
+    member_id: Uuid,
+    role: GroupRole,
+) -> Result<(), AppError> {
+    group_members::Entity::update_many()
+        .col_expr(group_members::Column::Role, Expr::value(role))
+        .filter(group_members::Column::GroupId.eq(group_id))
+        .filter(group_members::Column::MemberId.eq(member_id))
+        .exec(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    Ok(())
+}
+
+fn succession_rank(role: GroupRole) -> u8 {
+    match role {
+        GroupRole::Owner | GroupRole::Admin => 0,
+        GroupRole::Member => 1,
+        GroupRole::Viewer => 2,
+    }
+}
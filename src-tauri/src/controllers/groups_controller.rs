@@ -1,10 +1,27 @@
 use chrono::Utc;
 use crate::entities::group_members;
-use crate::models::groups::{GroupRes,CreateGroupReq};
+use crate::controllers::attachments_controller::release_blobs;
+use crate::controllers::balances_controller::load_group_ledger;
+use crate::controllers::group_invites_controller::unused_join_code;
+use crate::entities::{
+    activities, activity_attachments, activity_comments, activity_history, activity_items,
+    cash_transactions, categories, group_events, group_invites, notifications, recurring_expenses,
+    transactions, upi_payments,
+};
+use crate::models::groups::{
+    ArchiveGroupReq, DeleteGroupReq, GetGroupsReq, GroupRes, CreateGroupReq, UpdateGroupSettingsReq,
+};
+use crate::request_verifier::groups::{
+    check_group_exists, check_group_not_archived, check_user_is_admin_in_group,
+    check_user_is_owner_of_group,
+};
+use crate::utils::blob_store::SharedBlobStore;
 use crate::entities::groups::{self, ActiveModel};
+use crate::entities::sea_orm_active_enums::GroupRole;
 use crate::custom_errors::app::AppError;
+use crate::utils::exchange_rates::DEFAULT_CURRENCY;
 use axum::{
-    extract::{Json, Extension},
+    extract::{Json, Extension, Query},
     response::IntoResponse,
     http::StatusCode,
     Json as AxumJson,
@@ -29,6 +46,10 @@ pub async fn create_group_handler(
         group_name: Set(payload.group_name),
         auto_logo: Set(payload.auto_logo),
         total_expense: Set(rust_decimal::Decimal::new(0, 0)),
+        simplify_debts: Set(true),
+        currency: Set(payload.currency.unwrap_or_else(|| DEFAULT_CURRENCY.to_string())),
+        archived_at: Set(None),
+        join_code: Set(Some(unused_join_code(&db).await?)),
         created_at: Set(Utc::now().into()),
         updated_at: Set(Utc::now().into()),
     };
@@ -42,6 +63,7 @@ pub async fn create_group_handler(
         id: Set(Uuid::new_v4()),
         group_id: Set(inserted.id),
         member_id: Set(user_id),
+        role: Set(GroupRole::Owner),
         joined_at: Set(Utc::now().into()),
     };
 
@@ -60,6 +82,7 @@ pub async fn create_group_handler(
 pub async fn get_all_groups_handler(
     Extension(user_id): Extension<Uuid>,
     Extension(db): Extension<sea_orm::DatabaseConnection>,
+    Query(payload): Query<GetGroupsReq>,
 ) -> Result<impl IntoResponse, AppError> {
     // Retrieve PAGE_SIZE from environment variables and parse it safely
     let page_size: u64 = env::var("PAGE_SIZE")
@@ -68,10 +91,22 @@ pub async fn get_all_groups_handler(
         .parse()
         .map_err(|_| AppError::ConfigError("Invalid PAGE_SIZE value".to_string()))?;
 
-   
-    let paginator = groups::Entity::find()
-        .filter(groups::Column::CreatorId.eq(user_id))
-        .paginate(&db, page_size);
+    // every group the caller belongs to, not only the ones they created
+    let group_ids: Vec<Uuid> = group_members::Entity::find()
+        .filter(group_members::Column::MemberId.eq(user_id))
+        .all(&db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?
+        .into_iter()
+        .map(|membership| membership.group_id)
+        .collect();
+
+    let mut query = groups::Entity::find()
+        .filter(groups::Column::Id.is_in(group_ids));
+    if !payload.include_archived {
+        query = query.filter(groups::Column::ArchivedAt.is_null());
+    }
+    let paginator = query.paginate(&db, page_size);
 
     let mut all_groups = Vec::new();
     let mut page_stream = paginator;
@@ -87,3 +122,243 @@ pub async fn get_all_groups_handler(
     Ok((StatusCode::OK, AxumJson(all_groups)))
 }
 
+pub async fn update_group_settings_handler(
+    Extension(user_id): Extension<Uuid>,
+    Extension(db): Extension<sea_orm::DatabaseConnection>,
+    Json(payload): Json<UpdateGroupSettingsReq>
+) -> Result<impl IntoResponse, AppError> {
+    payload.check()?;
+    check_group_exists(&db, payload.group_id).await?;
+    check_group_not_archived(&db, payload.group_id).await?;
+    check_user_is_admin_in_group(&db, payload.group_id, user_id).await?;
+
+    let group = groups::Entity::find_by_id(payload.group_id)
+        .one(&db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?
+        .ok_or_else(|| AppError::NotFound("Group not found".into()))?;
+
+    let mut group_model: groups::ActiveModel = group.into();
+
+    if let Some(simplify_debts) = payload.simplify_debts {
+        group_model.simplify_debts = Set(simplify_debts);
+    }
+    group_model.updated_at = Set(Utc::now().into());
+
+    let updated = group_model
+        .update(&db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    Ok((StatusCode::OK, AxumJson(updated)))
+}
+
+pub async fn archive_group_handler(
+    Extension(user_id): Extension<Uuid>,
+    Extension(db): Extension<sea_orm::DatabaseConnection>,
+    Json(payload): Json<ArchiveGroupReq>,
+) -> Result<impl IntoResponse, AppError> {
+    payload.check()?;
+    check_group_exists(&db, payload.group_id).await?;
+    check_user_is_admin_in_group(&db, payload.group_id, user_id).await?;
+    check_group_not_archived(&db, payload.group_id).await?;
+
+    let updated = set_archived(&db, payload.group_id, true).await?;
+
+    Ok((StatusCode::OK, AxumJson(updated)))
+}
+
+pub async fn unarchive_group_handler(
+    Extension(user_id): Extension<Uuid>,
+    Extension(db): Extension<sea_orm::DatabaseConnection>,
+    Json(payload): Json<ArchiveGroupReq>,
+) -> Result<impl IntoResponse, AppError> {
+    payload.check()?;
+    check_group_exists(&db, payload.group_id).await?;
+    check_user_is_admin_in_group(&db, payload.group_id, user_id).await?;
+
+    let updated = set_archived(&db, payload.group_id, false).await?;
+
+    Ok((StatusCode::OK, AxumJson(updated)))
+}
+
+pub async fn delete_group_handler(
+    Extension(user_id): Extension<Uuid>,
+    Extension(db): Extension<sea_orm::DatabaseConnection>,
+    Extension(store): Extension<SharedBlobStore>,
+    Json(payload): Json<DeleteGroupReq>,
+) -> Result<impl IntoResponse, AppError> {
+    payload.check()?;
+    check_group_exists(&db, payload.group_id).await?;
+    check_user_is_owner_of_group(&db, payload.group_id, user_id).await?;
+
+    if !payload.force {
+        let unsettled = load_group_ledger(&db, payload.group_id)
+            .await?
+            .member_balances()
+            .into_iter()
+            .filter(|balance| !balance.net.round_dp(2).is_zero())
+            .count();
+        if unsettled > 0 {
+            return Err(AppError::ValidationError(format!(
+                "{} members still have unsettled balances, settle up or force the deletion",
+                unsettled
+            )));
+        }
+    }
+
+    let txn = db
+        .begin()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    let attachment_hashes = delete_group_cascade(&txn, payload.group_id).await?;
+    txn.commit()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    release_blobs(&db, &store, attachment_hashes).await;
+
+    Ok((StatusCode::OK, Json("Group deleted successfully")))
+}
+
+async fn set_archived(
+    db: &sea_orm::DatabaseConnection,
+    group_id: Uuid,
+    archived: bool,
+) -> Result<groups::Model, AppError> {
+    let group = groups::Entity::find_by_id(group_id)
+        .one(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?
+        .ok_or_else(|| AppError::NotFound("Group not found".into()))?;
+    if group.archived_at.is_some() == archived {
+        return Err(AppError::ValidationError(if archived {
+            "Group is already archived".into()
+        } else {
+            "Group is not archived".into()
+        }));
+    }
+
+    let mut group_model: groups::ActiveModel = group.into();
+    group_model.archived_at = Set(archived.then(|| Utc::now().into()));
+    group_model.updated_at = Set(Utc::now().into());
+    group_model
+        .update(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))
+}
+
+/// Removes a group and everything that belongs to it: members, activities
+/// (trashed ones included) with their items, comments, attachments and history,
+/// settlements with their UPI and cash records, recurring expenses, invites,
+/// notifications, the group's event log and its own categories. Returns the attachment hashes whose blobs can be
+/// released once the transaction has committed.
+async fn delete_group_cascade<C: ConnectionTrait>(
+    db: &C,
+    group_id: Uuid,
+) -> Result<Vec<String>, AppError> {
+    let activity_ids: Vec<Uuid> = activities::Entity::find()
+        .filter(activities::Column::GroupId.eq(group_id))
+        .select_only()
+        .column(activities::Column::Id)
+        .into_tuple()
+        .all(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    let transaction_ids: Vec<Uuid> = transactions::Entity::find()
+        .filter(transactions::Column::GroupId.eq(group_id))
+        .select_only()
+        .column(transactions::Column::Id)
+        .into_tuple()
+        .all(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    let attachment_hashes: Vec<String> = activity_attachments::Entity::find()
+        .filter(activity_attachments::Column::GroupId.eq(group_id))
+        .select_only()
+        .column(activity_attachments::Column::ContentHash)
+        .into_tuple()
+        .all(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    // children first, parents last
+    activity_items::Entity::delete_many()
+        .filter(activity_items::Column::ActivityId.is_in(activity_ids))
+        .exec(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    activity_comments::Entity::delete_many()
+        .filter(activity_comments::Column::GroupId.eq(group_id))
+        .exec(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    activity_attachments::Entity::delete_many()
+        .filter(activity_attachments::Column::GroupId.eq(group_id))
+        .exec(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    activity_history::Entity::delete_many()
+        .filter(activity_history::Column::GroupId.eq(group_id))
+        .exec(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    activities::Entity::delete_many()
+        .filter(activities::Column::GroupId.eq(group_id))
+        .exec(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    upi_payments::Entity::delete_many()
+        .filter(upi_payments::Column::TransactionId.is_in(transaction_ids.clone()))
+        .exec(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    cash_transactions::Entity::delete_many()
+        .filter(cash_transactions::Column::TransactionId.is_in(transaction_ids))
+        .exec(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    transactions::Entity::delete_many()
+        .filter(transactions::Column::GroupId.eq(group_id))
+        .exec(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    recurring_expenses::Entity::delete_many()
+        .filter(recurring_expenses::Column::GroupId.eq(group_id))
+        .exec(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    categories::Entity::delete_many()
+        .filter(categories::Column::GroupId.eq(group_id))
+        .exec(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    group_invites::Entity::delete_many()
+        .filter(group_invites::Column::GroupId.eq(group_id))
+        .exec(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    notifications::Entity::delete_many()
+        .filter(notifications::Column::GroupId.eq(group_id))
+        .exec(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    group_events::Entity::delete_many()
+        .filter(group_events::Column::GroupId.eq(group_id))
+        .exec(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    group_members::Entity::delete_many()
+        .filter(group_members::Column::GroupId.eq(group_id))
+        .exec(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    groups::Entity::delete_by_id(group_id)
+        .exec(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    Ok(attachment_hashes)
+}
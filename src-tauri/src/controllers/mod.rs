@@ -2,4 +2,16 @@ pub mod auth_controller;
 pub mod user_controller;
 pub mod activities_controller;
 pub mod groups_controller;
-pub mod group_members_controller;
\ No newline at end of file
+pub mod group_members_controller;
+pub mod balances_controller;
+pub mod settlements_controller;
+pub mod recurring_expenses_controller;
+pub mod attachments_controller;
+pub mod categories_controller;
+pub mod comments_controller;
+pub mod activity_history_controller;
+pub mod group_invites_controller;
+pub mod friends_controller;
+pub mod notifications_controller;
+pub mod group_events_controller;
+pub mod digests_controller;
\ No newline at end of file
@@ -0,0 +1,229 @@
+use chrono::Utc;
+use crate::custom_errors::app::AppError;
+use crate::controllers::group_events_controller::record_user_event;
+use crate::entities::sea_orm_active_enums::{GroupEventType, NotificationType};
+use crate::entities::{group_events, groups, notifications, users};
+use crate::models::notifications::{
+    DeleteNotificationReq, GetNotificationsReq, MarkNotificationsReadReq, NotificationRes,
+    NotificationsPageRes,
+};
+use axum::{
+    extract::{Extension, Json},
+    http::StatusCode,
+    response::IntoResponse,
+    Json as AxumJson,
+};
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, IntoActiveModel, PaginatorTrait,
+    QueryFilter, QueryOrder,
+};
+use std::env;
+use uuid::Uuid;
+
+/// A notification about to be sent to everyone affected by something `actor_id` did.
+pub struct NewNotification {
+    pub type_: NotificationType,
+    pub actor_id: Uuid,
+    pub message: String,
+    pub group_id: Option<Uuid>,
+    pub activity_id: Option<Uuid>,
+}
+
+pub async fn get_notifications_handler(
+    Extension(user_id): Extension<Uuid>,
+    Extension(db): Extension<DatabaseConnection>,
+    Json(payload): Json<GetNotificationsReq>,
+) -> Result<impl IntoResponse, AppError> {
+    let page_size: u64 = env::var("PAGE_SIZE")
+        .map_err(|_| AppError::ConfigError("PAGE_SIZE must be set".to_string()))?
+        .trim()
+        .parse()
+        .map_err(|_| AppError::ConfigError("Invalid PAGE_SIZE value".to_string()))?;
+
+    let mut query = notifications::Entity::find()
+        .filter(notifications::Column::UserId.eq(user_id));
+    if payload.unread_only {
+        query = query.filter(notifications::Column::Read.eq(false));
+    }
+    let paginator = query
+        .order_by_desc(notifications::Column::CreatedAt)
+        .order_by_desc(notifications::Column::Id)
+        .paginate(&db, page_size);
+
+    let totals = paginator
+        .num_items_and_pages()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    let notifications = paginator
+        .fetch_page(payload.page)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?
+        .into_iter()
+        .map(NotificationRes::from)
+        .collect();
+    let unread_count = notifications::Entity::find()
+        .filter(notifications::Column::UserId.eq(user_id))
+        .filter(notifications::Column::Read.eq(false))
+        .count(&db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    Ok((
+        StatusCode::OK,
+        AxumJson(NotificationsPageRes {
+            notifications,
+            page: payload.page,
+            total_pages: totals.number_of_pages,
+            total_notifications: totals.number_of_items,
+            unread_count,
+        }),
+    ))
+}
+
+pub async fn mark_notifications_read_handler(
+    Extension(user_id): Extension<Uuid>,
+    Extension(db): Extension<DatabaseConnection>,
+    Json(payload): Json<MarkNotificationsReadReq>,
+) -> Result<impl IntoResponse, AppError> {
+    payload.check()?;
+
+    // ids of other users' notifications are ignored rather than reported
+    mark_read(&db, user_id, Some(payload.notification_ids)).await?;
+
+    Ok((StatusCode::OK, Json("Notifications marked as read")))
+}
+
+pub async fn mark_all_notifications_read_handler(
+    Extension(user_id): Extension<Uuid>,
+    Extension(db): Extension<DatabaseConnection>,
+) -> Result<impl IntoResponse, AppError> {
+    mark_read(&db, user_id, None).await?;
+
+    Ok((StatusCode::OK, Json("All notifications marked as read")))
+}
+
+pub async fn delete_notification_handler(
+    Extension(user_id): Extension<Uuid>,
+    Extension(db): Extension<DatabaseConnection>,
+    Json(payload): Json<DeleteNotificationReq>,
+) -> Result<impl IntoResponse, AppError> {
+    payload.check()?;
+
+    let res = notifications::Entity::delete_many()
+        .filter(notifications::Column::Id.eq(payload.notification_id))
+        .filter(notifications::Column::UserId.eq(user_id))
+        .exec(&db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    if res.rows_affected == 0 {
+        return Err(AppError::NotFound("Notification not found".to_string()));
+    }
+
+    Ok((StatusCode::OK, Json("Notification deleted successfully")))
+}
+
+/// Sends `notification` to each recipient once. The actor is left out, nobody
+/// needs to hear about what they did themselves. Returns the events to publish
+/// once the surrounding transaction has committed.
+pub async fn notify<C: ConnectionTrait>(
+    db: &C,
+    recipients: impl IntoIterator<Item = Uuid>,
+    notification: NewNotification,
+) -> Result<Vec<group_events::Model>, AppError> {
+    let recipient_ids = recipients_besides(notification.actor_id, recipients);
+    let now = Utc::now();
+    let rows: Vec<notifications::Model> = recipient_ids
+        .into_iter()
+        .map(|recipient_id| notifications::Model {
+            id: Uuid::new_v4(),
+            user_id: recipient_id,
+            type_: notification.type_,
+            message: notification.message.clone(),
+            read: false,
+            actor_id: Some(notification.actor_id),
+            group_id: notification.group_id,
+            activity_id: notification.activity_id,
+            created_at: now.into(),
+            updated_at: now.into(),
+        })
+        .collect();
+
+    notifications::Entity::insert_many(rows.iter().cloned().map(IntoActiveModel::into_active_model))
+        .on_empty_do_nothing()
+        .exec(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    let mut events = Vec::with_capacity(rows.len());
+    for row in rows {
+        events.push(
+            record_user_event(db, GroupEventType::NotificationCreated, row.user_id, NotificationRes::from(row))
+                .await?,
+        );
+    }
+    Ok(events)
+}
+
+/// Each recipient once, in the order given, without the actor.
+fn recipients_besides(actor_id: Uuid, recipients: impl IntoIterator<Item = Uuid>) -> Vec<Uuid> {
+    let mut recipient_ids: Vec<Uuid> = Vec::new();
+    for recipient_id in recipients {
+        if recipient_id != actor_id && !recipient_ids.contains(&recipient_id) {
+            recipient_ids.push(recipient_id);
+        }
+    }
+    recipient_ids
+}
+
+/// The actor's username and the group, for writing notification messages.
+pub async fn notification_context<C: ConnectionTrait>(
+    db: &C,
+    actor_id: Uuid,
+    group_id: Uuid,
+) -> Result<(String, groups::Model), AppError> {
+    let actor = users::Entity::find_by_id(actor_id)
+        .one(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?
+        .ok_or(AppError::NotFound("User not found".to_string()))?;
+    let group = groups::Entity::find_by_id(group_id)
+        .one(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?
+        .ok_or(AppError::NotFound("Group not found".to_string()))?;
+    Ok((actor.username, group))
+}
+
+async fn mark_read(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    notification_ids: Option<Vec<Uuid>>,
+) -> Result<(), AppError> {
+    let mut update = notifications::Entity::update_many()
+        .col_expr(notifications::Column::Read, Expr::value(true))
+        .col_expr(notifications::Column::UpdatedAt, Expr::value(Utc::now()))
+        .filter(notifications::Column::UserId.eq(user_id))
+        .filter(notifications::Column::Read.eq(false));
+    if let Some(notification_ids) = notification_ids {
+        update = update.filter(notifications::Column::Id.is_in(notification_ids));
+    }
+    update
+        .exec(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_actor_is_not_told_about_their_own_doing() {
+        let (actor, a, b) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        assert_eq!(recipients_besides(actor, [a, actor, b, a]), vec![a, b]);
+        assert!(recipients_besides(actor, [actor]).is_empty());
+    }
+}
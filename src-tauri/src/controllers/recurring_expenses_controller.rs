@@ -0,0 +1,260 @@
+use chrono::Utc;
+use crate::controllers::activities_controller::{check_payers_in_group, resolve_currency};
+use crate::controllers::categories_controller::resolve_category;
+use crate::custom_errors::app::AppError;
+use crate::entities::{activities, recurring_expenses};
+use crate::jobs::recurring_expenses::materialize;
+use crate::models::recurring_expenses::{
+    CreateRecurringExpenseReq, DeleteRecurringExpenseReq, GetRecurringExpensesReq,
+    RecurringExpenseRes, UpdateRecurringExpenseReq,
+};
+use crate::request_verifier::groups::{
+    check_group_exists, check_group_not_archived, check_user_can_write_in_group,
+    check_user_exists_in_group, check_user_is_admin_in_group,
+};
+use crate::utils::event_bus::EventBus;
+use crate::utils::recurrence::{first_occurrence, next_occurrence};
+use axum::{
+    extract::{Extension, Json},
+    http::StatusCode,
+    response::IntoResponse,
+    Json as AxumJson,
+};
+use rust_decimal::Decimal;
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, IntoActiveModel, QueryFilter,
+    QueryOrder, Set, TransactionTrait,
+};
+use serde_json::json;
+use uuid::Uuid;
+
+pub async fn create_recurring_expense_handler(
+    Extension(user_id): Extension<Uuid>,
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(bus): Extension<EventBus>,
+    Json(mut payload): Json<CreateRecurringExpenseReq>,
+) -> Result<impl IntoResponse, AppError> {
+    payload.check()?;
+    let expense = payload.expense;
+    check_group_exists(&db, expense.group_id).await?;
+    check_group_not_archived(&db, expense.group_id).await?;
+    check_user_can_write_in_group(&db, expense.group_id, user_id).await?;
+
+    let payers: Vec<(Uuid, Decimal)> = match &expense.payers {
+        Some(payers) => payers.iter().map(|payer| (payer.payer_id, payer.amount)).collect(),
+        None => vec![(user_id, expense.amount)],
+    };
+    check_payers_in_group(&db, expense.group_id, &payers).await?;
+
+    let now = Utc::now();
+    let starts_at = payload
+        .starts_at
+        .map(|starts_at| starts_at.with_timezone(&Utc))
+        .unwrap_or(now);
+    // fails early when the currency cannot be converted into the group's
+    let (currency, _) = resolve_currency(
+        &db,
+        expense.group_id,
+        expense.currency.as_deref(),
+        starts_at.date_naive(),
+    )
+    .await?;
+
+    let category = resolve_category(&db, expense.group_id, expense.category_id, &expense.description).await?;
+    let expense_logo = expense
+        .expense_logo
+        .or_else(|| category.as_ref().map(|category| category.logo.clone()));
+
+    let next = first_occurrence(payload.frequency, payload.cron_expression.as_deref(), starts_at)?
+        .filter(|first| payload.ends_at.map_or(true, |ends_at| *first <= ends_at));
+
+    let template = recurring_expenses::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        group_id: Set(expense.group_id),
+        creator_id: Set(user_id),
+        description: Set(expense.description),
+        amount: Set(expense.amount),
+        currency: Set(currency),
+        payer_ids: Set(json!(payers.iter().map(|(payer_id, _)| *payer_id).collect::<Vec<Uuid>>())),
+        payer_amounts: Set(json!(payers.iter().map(|(_, amount)| *amount).collect::<Vec<_>>())),
+        split_members: Set(json!(expense.split_members)),
+        split_amounts: Set(json!(expense.split_amounts)),
+        split_type: Set(expense.split_type),
+        split_values: Set(expense.split_values.map(|values| json!(values))),
+        category_id: Set(category.map(|category| category.id)),
+        expense_logo: Set(expense_logo),
+        frequency: Set(payload.frequency),
+        cron_expression: Set(payload.cron_expression),
+        starts_at: Set(starts_at.into()),
+        ends_at: Set(payload.ends_at),
+        next_occurrence_at: Set(next.map(Into::into)),
+        is_active: Set(true),
+        created_at: Set(now.into()),
+        updated_at: Set(now.into()),
+    }
+    .insert(&db)
+    .await
+    .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    // a schedule starting today or in the past shows up right away, the scheduler retries on failure
+    let template_id = template.id;
+    if let Err(err) = materialize(&db, &bus, template, now).await {
+        eprintln!("Recurring expense {} failed: {}", template_id, err);
+    }
+
+    let template = recurring_expenses::Entity::find_by_id(template_id)
+        .one(&db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?
+        .ok_or(AppError::NotFound("Recurring expense not found".to_string()))?;
+
+    Ok((StatusCode::CREATED, AxumJson(RecurringExpenseRes::from(template))))
+}
+
+pub async fn get_recurring_expenses_handler(
+    Extension(user_id): Extension<Uuid>,
+    Extension(db): Extension<DatabaseConnection>,
+    Json(payload): Json<GetRecurringExpensesReq>,
+) -> Result<impl IntoResponse, AppError> {
+    payload.check()?;
+    check_group_exists(&db, payload.group_id).await?;
+    check_user_exists_in_group(&db, payload.group_id, user_id).await?;
+
+    let templates: Vec<RecurringExpenseRes> = recurring_expenses::Entity::find()
+        .filter(recurring_expenses::Column::GroupId.eq(payload.group_id))
+        .order_by_asc(recurring_expenses::Column::CreatedAt)
+        .all(&db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?
+        .into_iter()
+        .map(RecurringExpenseRes::from)
+        .collect();
+
+    Ok((StatusCode::OK, AxumJson(templates)))
+}
+
+pub async fn update_recurring_expense_handler(
+    Extension(user_id): Extension<Uuid>,
+    Extension(db): Extension<DatabaseConnection>,
+    Json(payload): Json<UpdateRecurringExpenseReq>,
+) -> Result<impl IntoResponse, AppError> {
+    payload.check()?;
+    check_group_exists(&db, payload.group_id).await?;
+    check_group_not_archived(&db, payload.group_id).await?;
+    check_user_can_write_in_group(&db, payload.group_id, user_id).await?;
+
+    let template = find_template(&db, payload.id, payload.group_id, user_id).await?;
+
+    let now = Utc::now();
+    let starts_at = template.starts_at.with_timezone(&Utc);
+    let ends_at = match payload.ends_at {
+        Some(ends_at) => ends_at,
+        None => template.ends_at,
+    };
+    if ends_at.is_some_and(|ends_at| ends_at < template.starts_at) {
+        return Err(AppError::ValidationError(
+            "End date cannot be before the start date".into(),
+        ));
+    }
+
+    let resuming = payload.is_active == Some(true) && !template.is_active;
+    let reopening = payload.ends_at.is_some() && template.next_occurrence_at.is_none();
+    // occurrences that fell due while paused or after the old end date are skipped, not caught up
+    let mut next = if resuming || reopening {
+        next_occurrence(
+            template.frequency,
+            template.cron_expression.as_deref(),
+            starts_at,
+            now.max(starts_at),
+        )?
+    } else {
+        template.next_occurrence_at.map(|next| next.with_timezone(&Utc))
+    };
+    if let (Some(occurrence), Some(ends_at)) = (next, ends_at) {
+        if occurrence > ends_at {
+            next = None;
+        }
+    }
+
+    let mut template_model = template.into_active_model();
+    if let Some(description) = payload.description {
+        template_model.description = Set(description);
+    }
+    if let Some(is_active) = payload.is_active {
+        template_model.is_active = Set(is_active);
+    }
+    template_model.ends_at = Set(ends_at);
+    template_model.next_occurrence_at = Set(next.map(Into::into));
+    template_model.updated_at = Set(now.into());
+
+    let updated = template_model
+        .update(&db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    Ok((StatusCode::OK, AxumJson(RecurringExpenseRes::from(updated))))
+}
+
+pub async fn delete_recurring_expense_handler(
+    Extension(user_id): Extension<Uuid>,
+    Extension(db): Extension<DatabaseConnection>,
+    Json(payload): Json<DeleteRecurringExpenseReq>,
+) -> Result<impl IntoResponse, AppError> {
+    payload.check()?;
+    check_group_exists(&db, payload.group_id).await?;
+    check_group_not_archived(&db, payload.group_id).await?;
+    check_user_can_write_in_group(&db, payload.group_id, user_id).await?;
+
+    let template = find_template(&db, payload.id, payload.group_id, user_id).await?;
+
+    let txn = db
+        .begin()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    // activities already created stay in the group as regular expenses
+    activities::Entity::update_many()
+        .col_expr(activities::Column::RecurringExpenseId, Expr::value(Option::<Uuid>::None))
+        .filter(activities::Column::RecurringExpenseId.eq(template.id))
+        .exec(&txn)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    recurring_expenses::Entity::delete_by_id(template.id)
+        .exec(&txn)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    txn.commit()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    Ok((StatusCode::OK, Json("Recurring expense deleted successfully")))
+}
+
+// only the creator or a group admin may change a recurring expense
+async fn find_template(
+    db: &DatabaseConnection,
+    id: Uuid,
+    group_id: Uuid,
+    user_id: Uuid,
+) -> Result<recurring_expenses::Model, AppError> {
+    let template = recurring_expenses::Entity::find_by_id(id)
+        .filter(recurring_expenses::Column::GroupId.eq(group_id))
+        .one(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?
+        .ok_or(AppError::NotFound("Recurring expense not found".to_string()))?;
+
+    if template.creator_id != user_id {
+        check_user_is_admin_in_group(db, group_id, user_id)
+            .await
+            .map_err(|_| {
+                AppError::Unauthorized(
+                    "You are not authorized to change this recurring expense".to_string(),
+                )
+            })?;
+    }
+    Ok(template)
+}
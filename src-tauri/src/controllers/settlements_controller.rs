@@ -0,0 +1,881 @@
+use chrono::Utc;
+use crate::custom_errors::app::AppError;
+use crate::entities::sea_orm_active_enums::{
+    GroupEventType, NotificationType, PaymentMethod, TransactionStatus, UpiPaymentStatus,
+};
+use crate::entities::{cash_transactions, group_events, groups, transactions, upi_payments, users};
+use crate::controllers::balances_controller::load_friend_balances;
+use crate::controllers::group_events_controller::record_group_event;
+use crate::controllers::notifications_controller::{notification_context, notify, NewNotification};
+use crate::models::settlements::{
+    ConfirmCashSettlementReq, CreateSettlementReq, CreateUpiIntentReq, DisputeCashSettlementReq,
+    GetSettlementsReq, GetUpiQrReq, QrFormat, RefreshUpiStatusReq, SettleAllReq, SettlementRes, UpiCallbackReq, UpiIntentRes,
+};
+use crate::request_verifier::{
+    activities::check_activity_exists_in_group,
+    groups::{check_group_exists, check_user_exists_in_group},
+};
+use crate::utils::upi::{
+    generate_transaction_ref, is_valid_vpa, render_qr_png, render_qr_svg, UpiIntent,
+};
+use crate::utils::event_bus::EventBus;
+use crate::utils::exchange_rates::DEFAULT_CURRENCY;
+use crate::utils::upi_psp::SharedUpiProvider;
+use axum::{
+    body::Bytes,
+    extract::{Extension, Json, Query},
+    http::{header, HeaderMap, StatusCode},
+    response::IntoResponse,
+    Json as AxumJson,
+};
+use rust_decimal::Decimal;
+use sea_orm::sea_query::{Condition, Expr};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, IntoActiveModel,
+    QueryFilter, QueryOrder, QuerySelect, Set, TransactionTrait,
+};
+use std::collections::HashMap;
+use std::env;
+use uuid::Uuid;
+
+const DEFAULT_UPI_INTENT_TTL_MINUTES: i64 = 30;
+
+pub async fn create_settlement_handler(
+    Extension(user_id): Extension<Uuid>,
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(bus): Extension<EventBus>,
+    Json(payload): Json<CreateSettlementReq>,
+) -> Result<impl IntoResponse, AppError> {
+    let settlement = record_settlement(&db, &bus, user_id, payload).await?;
+
+    Ok((StatusCode::CREATED, AxumJson(settlement)))
+}
+
+/// Records a settlement from the caller to another member of the group. Plain
+/// settlements and UPI intents both go through here so they are checked alike.
+async fn record_settlement(
+    db: &DatabaseConnection,
+    bus: &EventBus,
+    user_id: Uuid,
+    mut payload: CreateSettlementReq,
+) -> Result<SettlementRes, AppError> {
+    payload.check()?;
+    check_group_exists(db, payload.group_id).await?;
+    check_user_exists_in_group(db, payload.group_id, user_id).await?;
+
+    if payload.receiver_id == user_id {
+        return Err(AppError::ValidationError("You cannot settle up with yourself".into()));
+    }
+    check_user_exists_in_group(db, payload.group_id, payload.receiver_id)
+        .await
+        .map_err(|_| AppError::UserNotInGroup("Receiver is not a member of this group".into()))?;
+
+    if let Some(activity_id) = payload.activity_id {
+        check_activity_exists_in_group(db, activity_id, payload.group_id).await?;
+    }
+    if payload.method == PaymentMethod::Upi {
+        check_group_accepts_upi(db, payload.group_id).await?;
+    }
+
+    let upi_id = match payload.method {
+        PaymentMethod::Upi => match payload.upi_id.take() {
+            Some(upi_id) => Some(upi_id),
+            None => {
+                let receiver = users::Entity::find_by_id(payload.receiver_id)
+                    .one(db)
+                    .await
+                    .map_err(|e| AppError::DatabaseError(e.to_string()))?
+                    .ok_or_else(|| AppError::NotFound("Receiver not found".into()))?;
+                if !is_valid_vpa(&receiver.upi_id) {
+                    return Err(AppError::ValidationError("Receiver does not have a valid UPI id".into()));
+                }
+                Some(receiver.upi_id)
+            }
+        },
+        PaymentMethod::Cash => None,
+    };
+
+    let txn = db
+        .begin()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    let now = Utc::now();
+    let transaction = transactions::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        payer_id: Set(user_id),
+        receiver_id: Set(payload.receiver_id),
+        amount: Set(payload.amount),
+        method: Set(payload.method),
+        time: Set(now.into()),
+        // cash has to be confirmed by the receiver before it counts, UPI by the
+        // PSP through a status refresh or its callback
+        status: Set(TransactionStatus::Pending),
+        group_id: Set(payload.group_id),
+        activity_id: Set(payload.activity_id),
+        batch_id: Set(None),
+        created_at: Set(now.into()),
+        updated_at: Set(now.into()),
+    }
+    .insert(&txn)
+    .await
+    .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    let (upi_payment, cash_transaction) = match upi_id {
+        Some(upi_id) => {
+            let upi_payment = upi_payments::ActiveModel {
+                id: Set(Uuid::new_v4()),
+                transaction_id: Set(transaction.id),
+                upi_id: Set(upi_id),
+                status: Set(UpiPaymentStatus::Initiated),
+                transaction_ref: Set(Some(generate_transaction_ref())),
+                created_at: Set(now.into()),
+                updated_at: Set(now.into()),
+            }
+            .insert(&txn)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            (Some(upi_payment), None)
+        }
+        None => {
+            let cash_transaction = cash_transactions::ActiveModel {
+                id: Set(Uuid::new_v4()),
+                transaction_id: Set(transaction.id),
+                cash_amount: Set(payload.amount),
+                responded_at: Set(None),
+                dispute_reason: Set(None),
+                created_at: Set(now.into()),
+                updated_at: Set(now.into()),
+            }
+            .insert(&txn)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            (None, Some(cash_transaction))
+        }
+    };
+    // a UPI receiver is told once the payment goes through, see apply_upi_status
+    let mut events = match transaction.method {
+        PaymentMethod::Cash => notify_receiver(&txn, &transaction).await?,
+        PaymentMethod::Upi => Vec::new(),
+    };
+    let settlement = SettlementRes::new(transaction, upi_payment, cash_transaction);
+    events.push(
+        record_group_event(&txn, GroupEventType::SettlementRecorded, payload.group_id, &settlement).await?,
+    );
+
+    txn.commit()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    bus.publish(events);
+
+    Ok(settlement)
+}
+
+pub async fn get_settlements_handler(
+    Extension(user_id): Extension<Uuid>,
+    Extension(db): Extension<DatabaseConnection>,
+    Json(payload): Json<GetSettlementsReq>,
+) -> Result<impl IntoResponse, AppError> {
+    payload.check()?;
+    check_group_exists(&db, payload.group_id).await?;
+    check_user_exists_in_group(&db, payload.group_id, user_id).await?;
+
+    let group_transactions = transactions::Entity::find()
+        .filter(transactions::Column::GroupId.eq(payload.group_id))
+        .order_by_desc(transactions::Column::Time)
+        .all(&db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    let settlements = with_payment_details(&db, group_transactions).await?;
+
+    Ok((StatusCode::OK, AxumJson(settlements)))
+}
+
+pub async fn create_upi_intent_handler(
+    Extension(user_id): Extension<Uuid>,
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(bus): Extension<EventBus>,
+    Json(payload): Json<CreateUpiIntentReq>,
+) -> Result<impl IntoResponse, AppError> {
+    payload.check()?;
+    let settlement = record_settlement(&db, &bus, user_id, payload.into()).await?;
+
+    let upi_uri = upi_intent_uri(&db, &settlement).await?;
+    let qr_svg = render_qr_svg(&upi_uri)?;
+
+    Ok((
+        StatusCode::CREATED,
+        AxumJson(UpiIntentRes {
+            settlement,
+            upi_uri,
+            qr_svg,
+        }),
+    ))
+}
+
+// served as a plain image so the desktop UI can point an <img> at it
+pub async fn get_upi_qr_handler(
+    Extension(user_id): Extension<Uuid>,
+    Extension(db): Extension<DatabaseConnection>,
+    Query(payload): Query<GetUpiQrReq>,
+) -> Result<impl IntoResponse, AppError> {
+    payload.check()?;
+
+    let transaction = transactions::Entity::find_by_id(payload.transaction_id)
+        .one(&db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?
+        .ok_or_else(|| AppError::NotFound("Settlement not found".into()))?;
+    check_user_exists_in_group(&db, transaction.group_id, user_id).await?;
+
+    let upi_payment = upi_payments::Entity::find()
+        .filter(upi_payments::Column::TransactionId.eq(transaction.id))
+        .one(&db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?
+        .ok_or_else(|| AppError::NotFound("Settlement has no UPI payment".into()))?;
+
+    let upi_uri = upi_intent_uri(&db, &SettlementRes::new(transaction, Some(upi_payment), None)).await?;
+
+    let (content_type, body) = match payload.format {
+        QrFormat::Png => ("image/png", render_qr_png(&upi_uri)?),
+        QrFormat::Svg => ("image/svg+xml", render_qr_svg(&upi_uri)?.into_bytes()),
+    };
+
+    Ok((StatusCode::OK, [(header::CONTENT_TYPE, content_type)], body))
+}
+
+pub async fn refresh_upi_status_handler(
+    Extension(user_id): Extension<Uuid>,
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(bus): Extension<EventBus>,
+    Extension(provider): Extension<SharedUpiProvider>,
+    Json(payload): Json<RefreshUpiStatusReq>,
+) -> Result<impl IntoResponse, AppError> {
+    payload.check()?;
+
+    let transaction = transactions::Entity::find_by_id(payload.transaction_id)
+        .one(&db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?
+        .ok_or_else(|| AppError::NotFound("Settlement not found".into()))?;
+    check_user_exists_in_group(&db, transaction.group_id, user_id).await?;
+
+    let upi_payment = upi_payments::Entity::find()
+        .filter(upi_payments::Column::TransactionId.eq(transaction.id))
+        .one(&db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?
+        .ok_or_else(|| AppError::NotFound("Settlement has no UPI payment".into()))?;
+
+    if upi_payment.status.is_terminal() {
+        return Ok((StatusCode::OK, AxumJson(SettlementRes::new(transaction, Some(upi_payment), None))));
+    }
+
+    let transaction_ref = upi_payment
+        .transaction_ref
+        .clone()
+        .ok_or_else(|| AppError::ValidationError("Settlement was not created as a UPI intent".into()))?;
+    let mut status = provider.fetch_status(&transaction_ref).await?;
+
+    if !status.is_terminal() && intent_has_expired(&upi_payment)? {
+        status = UpiPaymentStatus::Expired;
+    }
+
+    let (transaction, upi_payment) = apply_upi_status(&db, &bus, transaction, upi_payment, status).await?;
+
+    Ok((StatusCode::OK, AxumJson(SettlementRes::new(transaction, Some(upi_payment), None))))
+}
+
+// called by the payment provider, so it sits outside the cookie auth and is verified by the provider instead
+pub async fn upi_callback_handler(
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(bus): Extension<EventBus>,
+    Extension(provider): Extension<SharedUpiProvider>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<impl IntoResponse, AppError> {
+    let signature = headers
+        .get("X-PSP-Signature")
+        .and_then(|value| value.to_str().ok());
+    if !provider.verify_callback(signature, &body) {
+        return Err(AppError::Unauthorized("Invalid callback signature".into()));
+    }
+
+    let payload: UpiCallbackReq = serde_json::from_slice(&body)
+        .map_err(|e| AppError::ValidationError(e.to_string()))?;
+    payload.check()?;
+
+    let upi_payment = upi_payments::Entity::find()
+        .filter(upi_payments::Column::TransactionRef.eq(payload.transaction_ref.clone()))
+        .one(&db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?
+        .ok_or_else(|| AppError::NotFound("Unknown transaction reference".into()))?;
+
+    let transaction = transactions::Entity::find_by_id(upi_payment.transaction_id)
+        .one(&db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?
+        .ok_or_else(|| AppError::NotFound("Settlement not found".into()))?;
+
+    // providers retry callbacks, so repeating the current status is accepted as a no-op
+    if upi_payment.status == payload.status {
+        return Ok((StatusCode::OK, AxumJson(SettlementRes::new(transaction, Some(upi_payment), None))));
+    }
+
+    let (transaction, upi_payment) = apply_upi_status(&db, &bus, transaction, upi_payment, payload.status).await?;
+
+    Ok((StatusCode::OK, AxumJson(SettlementRes::new(transaction, Some(upi_payment), None))))
+}
+
+pub async fn confirm_cash_settlement_handler(
+    Extension(user_id): Extension<Uuid>,
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(bus): Extension<EventBus>,
+    Json(payload): Json<ConfirmCashSettlementReq>,
+) -> Result<impl IntoResponse, AppError> {
+    payload.check()?;
+
+    let settlement = respond_to_cash_settlement(
+        &db,
+        &bus,
+        user_id,
+        payload.transaction_id,
+        TransactionStatus::Confirmed,
+        None,
+    )
+    .await?;
+
+    Ok((StatusCode::OK, AxumJson(settlement)))
+}
+
+pub async fn dispute_cash_settlement_handler(
+    Extension(user_id): Extension<Uuid>,
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(bus): Extension<EventBus>,
+    Json(mut payload): Json<DisputeCashSettlementReq>,
+) -> Result<impl IntoResponse, AppError> {
+    payload.check()?;
+
+    let settlement = respond_to_cash_settlement(
+        &db,
+        &bus,
+        user_id,
+        payload.transaction_id,
+        TransactionStatus::Disputed,
+        payload.reason,
+    )
+    .await?;
+
+    Ok((StatusCode::OK, AxumJson(settlement)))
+}
+
+/// Settles everything owed to one friend across all shared groups in one go.
+/// Each group gets its own cash transaction so per-group balances stay correct,
+/// and they share a batch id so the friend confirms or disputes them together.
+/// Another batch with the same friend is refused until that one is answered.
+pub async fn settle_all_handler(
+    Extension(user_id): Extension<Uuid>,
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(bus): Extension<EventBus>,
+    Json(payload): Json<SettleAllReq>,
+) -> Result<impl IntoResponse, AppError> {
+    payload.check()?;
+    if payload.friend_id == user_id {
+        return Err(AppError::ValidationError("You cannot settle up with yourself".into()));
+    }
+
+    let group_balances: Vec<(Uuid, Decimal)> = load_friend_balances(&db, user_id)
+        .await?
+        .remove(&payload.friend_id)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(group_id, amount)| (group_id, amount.round_dp(2)))
+        .filter(|(_, amount)| !amount.is_zero())
+        .collect();
+
+    let net: Decimal = group_balances.iter().map(|(_, amount)| *amount).sum();
+    if net >= Decimal::ZERO {
+        return Err(AppError::ValidationError("You do not owe this friend anything".into()));
+    }
+
+    let txn = db
+        .begin()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    // both users are locked in a fixed order so two settle-alls between the
+    // same pair cannot both pass the check below
+    users::Entity::find()
+        .filter(users::Column::Id.is_in([user_id, payload.friend_id]))
+        .order_by_asc(users::Column::Id)
+        .lock_exclusive()
+        .all(&txn)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    let open_batch = transactions::Entity::find()
+        .filter(transactions::Column::BatchId.is_not_null())
+        .filter(transactions::Column::Status.eq(TransactionStatus::Pending))
+        .filter(
+            Condition::any()
+                .add(
+                    Condition::all()
+                        .add(transactions::Column::PayerId.eq(user_id))
+                        .add(transactions::Column::ReceiverId.eq(payload.friend_id)),
+                )
+                .add(
+                    Condition::all()
+                        .add(transactions::Column::PayerId.eq(payload.friend_id))
+                        .add(transactions::Column::ReceiverId.eq(user_id)),
+                ),
+        )
+        .one(&txn)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    if open_batch.is_some() {
+        return Err(AppError::DuplicateError(
+            "A settle up with this friend is still waiting for confirmation".into(),
+        ));
+    }
+
+    let now = Utc::now();
+    let batch_id = Uuid::new_v4();
+    let mut settlements = Vec::with_capacity(group_balances.len());
+    let mut events = Vec::new();
+
+    for (group_id, amount) in group_balances {
+        // a negative amount means the caller owes the friend in this group
+        let (payer_id, receiver_id) = if amount < Decimal::ZERO {
+            (user_id, payload.friend_id)
+        } else {
+            (payload.friend_id, user_id)
+        };
+
+        let transaction = transactions::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            payer_id: Set(payer_id),
+            receiver_id: Set(receiver_id),
+            amount: Set(amount.abs()),
+            method: Set(PaymentMethod::Cash),
+            time: Set(now.into()),
+            status: Set(TransactionStatus::Pending),
+            group_id: Set(group_id),
+            activity_id: Set(None),
+            batch_id: Set(Some(batch_id)),
+            created_at: Set(now.into()),
+            updated_at: Set(now.into()),
+        }
+        .insert(&txn)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let cash_transaction = cash_transactions::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            transaction_id: Set(transaction.id),
+            cash_amount: Set(transaction.amount),
+            responded_at: Set(None),
+            dispute_reason: Set(None),
+            created_at: Set(now.into()),
+            updated_at: Set(now.into()),
+        }
+        .insert(&txn)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        // only the payments the caller makes, the friend is not told they paid
+        if transaction.payer_id == user_id {
+            events.extend(notify_receiver(&txn, &transaction).await?);
+        }
+        let settlement = SettlementRes::new(transaction, None, Some(cash_transaction));
+        events.push(
+            record_group_event(&txn, GroupEventType::SettlementRecorded, group_id, &settlement).await?,
+        );
+        settlements.push(settlement);
+    }
+
+    txn.commit()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    bus.publish(events);
+
+    Ok((StatusCode::CREATED, AxumJson(settlements)))
+}
+
+pub async fn get_pending_confirmations_handler(
+    Extension(user_id): Extension<Uuid>,
+    Extension(db): Extension<DatabaseConnection>,
+) -> Result<impl IntoResponse, AppError> {
+    let pending = transactions::Entity::find()
+        .filter(transactions::Column::ReceiverId.eq(user_id))
+        .filter(transactions::Column::Method.eq(PaymentMethod::Cash))
+        .filter(transactions::Column::Status.eq(TransactionStatus::Pending))
+        .order_by_asc(transactions::Column::Time)
+        .all(&db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    let settlements = with_payment_details(&db, pending).await?;
+
+    Ok((StatusCode::OK, AxumJson(settlements)))
+}
+
+//helper
+/// Lets the receiver of a pending cash settlement accept or reject it. Settlements
+/// created together by "settle all" are answered as one unit by the net receiver.
+/// The answer is published to each group and the other side is told about it.
+async fn respond_to_cash_settlement(
+    db: &DatabaseConnection,
+    bus: &EventBus,
+    user_id: Uuid,
+    transaction_id: Uuid,
+    status: TransactionStatus,
+    dispute_reason: Option<String>,
+) -> Result<SettlementRes, AppError> {
+    let transaction = transactions::Entity::find_by_id(transaction_id)
+        .one(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?
+        .ok_or_else(|| AppError::NotFound("Settlement not found".into()))?;
+
+    let batch = match transaction.batch_id {
+        Some(batch_id) => transactions::Entity::find()
+            .filter(transactions::Column::BatchId.eq(batch_id))
+            .all(db)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?,
+        None => vec![transaction],
+    };
+
+    let received: Decimal = batch
+        .iter()
+        .filter(|t| t.receiver_id == user_id)
+        .map(|t| t.amount)
+        .sum();
+    let paid: Decimal = batch
+        .iter()
+        .filter(|t| t.payer_id == user_id)
+        .map(|t| t.amount)
+        .sum();
+    if received <= paid {
+        return Err(AppError::Unauthorized(
+            "Only the receiver can respond to this settlement".into(),
+        ));
+    }
+
+    for pending in &batch {
+        if pending.method != PaymentMethod::Cash {
+            return Err(AppError::ValidationError("Only cash settlements need confirmation".into()));
+        }
+        if pending.status != TransactionStatus::Pending {
+            return Err(AppError::ValidationError(format!(
+                "Settlement is already {:?}",
+                pending.status
+            )));
+        }
+    }
+
+    let batch_ids: Vec<Uuid> = batch.iter().map(|t| t.id).collect();
+    let cash_rows = cash_transactions::Entity::find()
+        .filter(cash_transactions::Column::TransactionId.is_in(batch_ids.clone()))
+        .all(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    let txn = db
+        .begin()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    let now = Utc::now();
+    // only rows still pending are moved, so an expiry or a second answer that
+    // got in first leaves the whole batch as it is
+    let updated = transactions::Entity::update_many()
+        .col_expr(transactions::Column::Status, Expr::value(status))
+        .col_expr(transactions::Column::UpdatedAt, Expr::value(now))
+        .filter(transactions::Column::Id.is_in(batch_ids))
+        .filter(transactions::Column::Status.eq(TransactionStatus::Pending))
+        .exec(&txn)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    if updated.rows_affected != batch.len() as u64 {
+        return Err(AppError::ValidationError("Settlement is no longer pending".into()));
+    }
+
+    let mut responded_cash = HashMap::new();
+    for cash_transaction in cash_rows {
+        let mut cash_model = cash_transaction.into_active_model();
+        cash_model.responded_at = Set(Some(now.into()));
+        cash_model.dispute_reason = Set(dispute_reason.clone());
+        cash_model.updated_at = Set(now.into());
+        let updated = cash_model
+            .update(&txn)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        responded_cash.insert(updated.transaction_id, updated);
+    }
+
+    // a confirmed payment changes balances, so every client of the group has to hear of it
+    let mut events = Vec::new();
+    let mut responded = None;
+    for answered in batch {
+        let answered = transactions::Model {
+            status,
+            updated_at: now.into(),
+            ..answered
+        };
+        events.extend(notify_of_answer(&txn, user_id, &answered).await?);
+        let cash_transaction = responded_cash.remove(&answered.id);
+        let settlement = SettlementRes::new(answered, None, cash_transaction);
+        events.push(
+            record_group_event(&txn, GroupEventType::SettlementRecorded, settlement.group_id, &settlement).await?,
+        );
+        if settlement.id == transaction_id {
+            responded = Some(settlement);
+        }
+    }
+
+    txn.commit()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    bus.publish(events);
+
+    responded.ok_or(AppError::InternalServerError)
+}
+
+/// Moves a UPI payment to `status` and mirrors final outcomes onto the parent
+/// transaction, which is what the balance ledger reads. A final outcome is
+/// published to the group, and a successful payment is announced to its receiver.
+async fn apply_upi_status(
+    db: &DatabaseConnection,
+    bus: &EventBus,
+    transaction: transactions::Model,
+    upi_payment: upi_payments::Model,
+    status: UpiPaymentStatus,
+) -> Result<(transactions::Model, upi_payments::Model), AppError> {
+    if upi_payment.status == status {
+        return Ok((transaction, upi_payment));
+    }
+    if !upi_payment.status.can_transition_to(status) {
+        return Err(AppError::ValidationError(format!(
+            "UPI payment cannot move from {:?} to {:?}",
+            upi_payment.status, status
+        )));
+    }
+
+    let txn = db
+        .begin()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    let now = Utc::now();
+    let mut upi_model = upi_payment.into_active_model();
+    upi_model.status = Set(status);
+    upi_model.updated_at = Set(now.into());
+    let upi_payment = upi_model
+        .update(&txn)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    let transaction_status = match status {
+        UpiPaymentStatus::Success => Some(TransactionStatus::Completed),
+        UpiPaymentStatus::Failed | UpiPaymentStatus::Expired => Some(TransactionStatus::Failed),
+        UpiPaymentStatus::Initiated | UpiPaymentStatus::Pending => None,
+    };
+
+    let mut events = Vec::new();
+    let transaction = match transaction_status {
+        Some(transaction_status) => {
+            let mut transaction_model = transaction.into_active_model();
+            transaction_model.status = Set(transaction_status);
+            transaction_model.updated_at = Set(now.into());
+            let transaction = transaction_model
+                .update(&txn)
+                .await
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+            if transaction.status == TransactionStatus::Completed {
+                events.extend(notify_receiver(&txn, &transaction).await?);
+            }
+            let settlement = SettlementRes::new(transaction.clone(), Some(upi_payment.clone()), None);
+            events.push(
+                record_group_event(&txn, GroupEventType::SettlementRecorded, transaction.group_id, &settlement)
+                    .await?,
+            );
+            transaction
+        }
+        None => transaction,
+    };
+
+    txn.commit()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    bus.publish(events);
+
+    Ok((transaction, upi_payment))
+}
+
+fn intent_has_expired(upi_payment: &upi_payments::Model) -> Result<bool, AppError> {
+    let ttl_minutes: i64 = match env::var("UPI_INTENT_TTL_MINUTES") {
+        Ok(value) => value
+            .trim()
+            .parse()
+            .map_err(|_| AppError::ConfigError("Invalid UPI_INTENT_TTL_MINUTES value".to_string()))?,
+        Err(_) => DEFAULT_UPI_INTENT_TTL_MINUTES,
+    };
+    let expires_at = upi_payment.created_at + chrono::Duration::minutes(ttl_minutes);
+    Ok(Utc::now() > expires_at)
+}
+
+async fn upi_intent_uri(db: &DatabaseConnection, settlement: &SettlementRes) -> Result<String, AppError> {
+    let upi_payment = settlement
+        .upi_payment
+        .as_ref()
+        .ok_or_else(|| AppError::NotFound("Settlement has no UPI payment".into()))?;
+    let transaction_ref = upi_payment
+        .transaction_ref
+        .as_deref()
+        .ok_or_else(|| AppError::ValidationError("Settlement was not created as a UPI intent".into()))?;
+
+    let receiver = users::Entity::find_by_id(settlement.receiver_id)
+        .one(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?
+        .ok_or_else(|| AppError::NotFound("Receiver not found".into()))?;
+
+    let group = groups::Entity::find_by_id(settlement.group_id)
+        .one(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?
+        .ok_or_else(|| AppError::NotFound("Group not found".into()))?;
+
+    let note = format!("Settle up in {}", group.group_name);
+
+    Ok(UpiIntent {
+        payee_vpa: &upi_payment.upi_id,
+        payee_name: &receiver.username,
+        amount: settlement.amount,
+        note: &note,
+        transaction_ref,
+    }
+    .to_uri())
+}
+
+/// Lets the receiver know a payment to them was recorded. Cash still needs
+/// their confirmation, so the message asks for it.
+async fn notify_receiver<C: ConnectionTrait>(
+    db: &C,
+    transaction: &transactions::Model,
+) -> Result<Vec<group_events::Model>, AppError> {
+    let (actor_name, group) = notification_context(db, transaction.payer_id, transaction.group_id).await?;
+    let message = match transaction.method {
+        PaymentMethod::Cash => format!(
+            "{} recorded a cash payment of {} {} to you in {}, confirm it once you have the money",
+            actor_name, transaction.amount, group.currency, group.group_name
+        ),
+        PaymentMethod::Upi => format!(
+            "{} paid you {} {} via UPI in {}",
+            actor_name, transaction.amount, group.currency, group.group_name
+        ),
+    };
+
+    notify(
+        db,
+        [transaction.receiver_id],
+        NewNotification {
+            type_: NotificationType::SettlementReceived,
+            actor_id: transaction.payer_id,
+            message,
+            group_id: Some(group.id),
+            activity_id: transaction.activity_id,
+        },
+    )
+    .await
+}
+
+/// Tells the other side of a cash settlement that `responder_id` confirmed or
+/// disputed it.
+async fn notify_of_answer<C: ConnectionTrait>(
+    db: &C,
+    responder_id: Uuid,
+    transaction: &transactions::Model,
+) -> Result<Vec<group_events::Model>, AppError> {
+    let (type_, answer) = match transaction.status {
+        TransactionStatus::Confirmed => (NotificationType::SettlementConfirmed, "confirmed"),
+        TransactionStatus::Disputed => (NotificationType::SettlementDisputed, "disputed"),
+        _ => return Ok(Vec::new()),
+    };
+    let other_side = if transaction.receiver_id == responder_id {
+        transaction.payer_id
+    } else {
+        transaction.receiver_id
+    };
+    let (actor_name, group) = notification_context(db, responder_id, transaction.group_id).await?;
+
+    notify(
+        db,
+        [other_side],
+        NewNotification {
+            type_,
+            actor_id: responder_id,
+            message: format!(
+                "{} {} the cash payment of {} {} in {}",
+                actor_name, answer, transaction.amount, group.currency, group.group_name
+            ),
+            group_id: Some(group.id),
+            activity_id: transaction.activity_id,
+        },
+    )
+    .await
+}
+
+// UPI only moves rupees, so groups kept in another currency settle in cash
+async fn check_group_accepts_upi(db: &DatabaseConnection, group_id: Uuid) -> Result<(), AppError> {
+    let group = groups::Entity::find_by_id(group_id)
+        .one(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?
+        .ok_or_else(|| AppError::NotFound("Group not found".into()))?;
+    if group.currency != DEFAULT_CURRENCY {
+        return Err(AppError::ValidationError(
+            "UPI settlements are only available in INR groups".into(),
+        ));
+    }
+    Ok(())
+}
+
+async fn with_payment_details(
+    db: &DatabaseConnection,
+    group_transactions: Vec<transactions::Model>,
+) -> Result<Vec<SettlementRes>, AppError> {
+    let transaction_ids: Vec<Uuid> = group_transactions.iter().map(|t| t.id).collect();
+
+    let mut upi_rows = upi_payments::Entity::find()
+        .filter(upi_payments::Column::TransactionId.is_in(transaction_ids.clone()))
+        .all(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    let mut cash_rows = cash_transactions::Entity::find()
+        .filter(cash_transactions::Column::TransactionId.is_in(transaction_ids))
+        .all(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    Ok(group_transactions
+        .into_iter()
+        .map(|transaction| {
+            let upi_payment = upi_rows
+                .iter()
+                .position(|row| row.transaction_id == transaction.id)
+                .map(|index| upi_rows.swap_remove(index));
+            let cash_transaction = cash_rows
+                .iter()
+                .position(|row| row.transaction_id == transaction.id)
+                .map(|index| cash_rows.swap_remove(index));
+            SettlementRes::new(transaction, upi_payment, cash_transaction)
+        })
+        .collect())
+}
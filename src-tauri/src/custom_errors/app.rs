@@ -35,6 +35,18 @@ pub enum AppError {
     #[error("Unauthorized access: {0}")]
     Unauthorized(String),
     
+    #[error("Storage error: {0}")]
+    StorageError(String),
+
+    #[error("Payload too large: {0}")]
+    PayloadTooLarge(String),
+
+    #[error("Delivery error: {0}")]
+    DeliveryError(String),
+
+    #[error("Identity provider error: {0}")]
+    IdentityProviderError(String),
+
     #[error("Internal server error")]
     InternalServerError,
 }
@@ -51,6 +63,10 @@ impl IntoResponse for AppError {
             AppError::UserNotInGroup(err) => (StatusCode::FORBIDDEN, json!({ "User Not in Group": err })),
             AppError::UserNotAdminOfGroup(err) => (StatusCode::FORBIDDEN, json!({ "User Not Admin of Group": err })),
             AppError::Unauthorized(err) => (StatusCode::UNAUTHORIZED, json!({ "Unauthorized": err })),
+            AppError::StorageError(err) => (StatusCode::INTERNAL_SERVER_ERROR, json!({ "Storage error": err })),
+            AppError::PayloadTooLarge(err) => (StatusCode::PAYLOAD_TOO_LARGE, json!({ "Payload too large": err })),
+            AppError::DeliveryError(err) => (StatusCode::BAD_GATEWAY, json!({ "Delivery error": err })),
+            AppError::IdentityProviderError(err) => (StatusCode::BAD_GATEWAY, json!({ "Identity provider error": err })),
             AppError::InternalServerError => (StatusCode::INTERNAL_SERVER_ERROR, json!({ "Internal server error": "Something went wrong" })),
         };
         (status, Json(error_message)).into_response()
@@ -1,6 +1,7 @@
 use rust_decimal::Decimal;
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
+use super::sea_orm_active_enums::SplitType;
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
 #[sea_orm(table_name = "activities")]
@@ -9,13 +10,32 @@ pub struct Model {
     pub id: Uuid,
     pub description: String,
     pub paid_by_id: Uuid,
+    pub payer_ids: Json,
+    pub payer_amounts: Json,
     pub group_id: Uuid,
     pub time: DateTimeWithTimeZone,
     pub amount: Decimal,
+    pub currency: String,
+    // converts `amount` into the group's currency, fixed when the activity is saved
+    pub exchange_rate: Decimal,
     pub split_members: Json,
     pub split_amounts: Json,
+    pub split_type: SplitType,
+    // percentages, share weights or adjustments the amounts were computed from
+    pub split_values: Option<Json>,
+    // charges spread across the items of an itemized split
+    pub tax: Option<Decimal>,
+    pub service_charge: Option<Decimal>,
+    pub tip: Option<Decimal>,
     pub user_involvement: bool,
     pub expense_logo: Option<String>,
+    pub category_id: Option<Uuid>,
+    // set on activities the scheduler created from a recurring expense
+    pub recurring_expense_id: Option<Uuid>,
+    pub occurrence_at: Option<DateTimeWithTimeZone>,
+    // set while the activity sits in the group's trash, see `jobs::activity_purge`
+    pub deleted_at: Option<DateTimeWithTimeZone>,
+    pub deleted_by: Option<Uuid>,
     pub created_at: DateTimeWithTimeZone,
     pub updated_at: DateTimeWithTimeZone,
 }
@@ -36,6 +56,22 @@ pub enum Relation {
     Group,
     #[sea_orm(has_many = "super::transactions::Entity")]
     Transactions,
+    #[sea_orm(
+        belongs_to = "super::recurring_expenses::Entity",
+        from = "Column::RecurringExpenseId",
+        to = "super::recurring_expenses::Column::Id"
+    )]
+    RecurringExpense,
+    #[sea_orm(has_many = "super::activity_items::Entity")]
+    Items,
+    #[sea_orm(has_many = "super::activity_attachments::Entity")]
+    Attachments,
+    #[sea_orm(
+        belongs_to = "super::categories::Entity",
+        from = "Column::CategoryId",
+        to = "super::categories::Column::Id"
+    )]
+    Category,
 }
 
 impl Related<super::users::Entity> for Entity {
@@ -56,4 +92,28 @@ impl Related<super::transactions::Entity> for Entity {
     }
 }
 
+impl Related<super::recurring_expenses::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::RecurringExpense.def()
+    }
+}
+
+impl Related<super::activity_items::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Items.def()
+    }
+}
+
+impl Related<super::activity_attachments::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Attachments.def()
+    }
+}
+
+impl Related<super::categories::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Category.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,29 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use super::sea_orm_active_enums::HistoryAction;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "activity_history")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: Uuid,
+    // not a relation, the activity may be gone by the time its history is read
+    pub activity_id: Uuid,
+    pub group_id: Uuid,
+    // counts up from 1 per activity
+    pub version: i32,
+    pub action: HistoryAction,
+    pub actor_id: Uuid,
+    // field name -> { "before": .., "after": .. } for every tracked field that changed
+    pub changes: Json,
+    // the activity after this change, or right before it was deleted
+    pub snapshot: Json,
+    pub items: Json,
+    pub reverted_to_version: Option<i32>,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -9,6 +9,8 @@ pub struct Model {
     pub id: Uuid,
     pub transaction_id: Uuid,
     pub cash_amount: Decimal,
+    pub responded_at: Option<DateTimeWithTimeZone>,
+    pub dispute_reason: Option<String>,
     pub created_at: DateTimeWithTimeZone,
     pub updated_at: DateTimeWithTimeZone,
 }
@@ -0,0 +1,33 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "categories")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: Uuid,
+    // `None` for the built-in categories every group can use
+    pub group_id: Option<Uuid>,
+    pub name: String,
+    // used as the activity's `expense_logo` when none is given
+    pub logo: String,
+    // lowercase words or phrases that suggest this category from a description
+    pub keywords: Json,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(has_many = "super::activities::Entity")]
+    Activities,
+}
+
+impl Related<super::activities::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Activities.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,23 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use super::sea_orm_active_enums::GroupEventType;
+
+/// Everything pushed to live clients, kept for a while so reconnecting
+/// clients can catch up from the last id they saw.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "group_events")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub event_type: GroupEventType,
+    // group events go to the group's members, user events to that user only
+    pub group_id: Option<Uuid>,
+    pub user_id: Option<Uuid>,
+    pub payload: Json,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
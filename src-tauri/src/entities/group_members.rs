@@ -1,5 +1,6 @@
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
+use super::sea_orm_active_enums::GroupRole;
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
 #[sea_orm(table_name = "group_members")]
@@ -8,6 +9,7 @@ pub struct Model {
     pub id: Uuid,
     pub group_id: Uuid,
     pub member_id: Uuid,
+    pub role: GroupRole,
     pub joined_at: DateTimeWithTimeZone,
 }
 
@@ -11,6 +11,13 @@ pub struct Model {
     pub group_name: String,
     pub auto_logo: Option<String>,
     pub total_expense: Decimal,
+    pub simplify_debts: bool,
+    pub currency: String,
+    // archived groups are read-only and left out of the default listing
+    pub archived_at: Option<DateTimeWithTimeZone>,
+    // lets anyone who knows it join, so it is only handed out to members who can add people
+    #[serde(skip_serializing)]
+    pub join_code: Option<String>,
     pub created_at: DateTimeWithTimeZone,
     pub updated_at: DateTimeWithTimeZone,
 }
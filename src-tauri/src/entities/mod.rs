@@ -7,7 +7,20 @@ pub mod transactions;
 pub mod upi_payments;
 pub mod cash_transactions;
 pub mod notifications;
+pub mod exchange_rates;
+pub mod recurring_expenses;
+pub mod activity_items;
+pub mod activity_attachments;
+pub mod categories;
+pub mod activity_comments;
+pub mod activity_history;
+pub mod group_invites;
+pub mod group_events;
+pub mod digest_preferences;
+pub mod sea_orm_active_enums;
 
+// not every entity is reached through the prelude
+#[allow(unused_imports)]
 pub mod prelude {
     pub use super::users::Entity as Users;
     pub use super::friend_collections::Entity as FriendCollections;
@@ -18,4 +31,14 @@ pub mod prelude {
     pub use super::upi_payments::Entity as UpiPayments;
     pub use super::cash_transactions::Entity as CashTransactions;
     pub use super::notifications::Entity as Notifications;
+    pub use super::exchange_rates::Entity as ExchangeRates;
+    pub use super::recurring_expenses::Entity as RecurringExpenses;
+    pub use super::activity_items::Entity as ActivityItems;
+    pub use super::activity_attachments::Entity as ActivityAttachments;
+    pub use super::categories::Entity as Categories;
+    pub use super::activity_comments::Entity as ActivityComments;
+    pub use super::activity_history::Entity as ActivityHistory;
+    pub use super::group_invites::Entity as GroupInvites;
+    pub use super::group_events::Entity as GroupEvents;
+    pub use super::digest_preferences::Entity as DigestPreferences;
 }
\ No newline at end of file
@@ -1,5 +1,6 @@
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
+use super::sea_orm_active_enums::NotificationType;
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
 #[sea_orm(table_name = "notifications")]
@@ -7,9 +8,14 @@ pub struct Model {
     #[sea_orm(primary_key)]
     pub id: Uuid,
     pub user_id: Uuid,
-    pub type_: String,
+    #[sea_orm(column_name = "type")]
+    pub type_: NotificationType,
     pub message: String,
     pub read: bool,
+    // who caused it and what it is about, when that applies
+    pub actor_id: Option<Uuid>,
+    pub group_id: Option<Uuid>,
+    pub activity_id: Option<Uuid>,
     pub created_at: DateTimeWithTimeZone,
     pub updated_at: DateTimeWithTimeZone,
 }
@@ -0,0 +1,60 @@
+use rust_decimal::Decimal;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use super::sea_orm_active_enums::{RecurrenceFrequency, SplitType};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "recurring_expenses")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: Uuid,
+    pub group_id: Uuid,
+    pub creator_id: Uuid,
+    pub description: String,
+    pub amount: Decimal,
+    pub currency: String,
+    pub payer_ids: Json,
+    pub payer_amounts: Json,
+    pub split_members: Json,
+    pub split_amounts: Json,
+    pub split_type: SplitType,
+    pub split_values: Option<Json>,
+    pub expense_logo: Option<String>,
+    pub category_id: Option<Uuid>,
+    pub frequency: RecurrenceFrequency,
+    // only set for `RecurrenceFrequency::Cron`
+    pub cron_expression: Option<String>,
+    pub starts_at: DateTimeWithTimeZone,
+    pub ends_at: Option<DateTimeWithTimeZone>,
+    // `None` once the schedule has run past `ends_at`
+    pub next_occurrence_at: Option<DateTimeWithTimeZone>,
+    pub is_active: bool,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::groups::Entity",
+        from = "Column::GroupId",
+        to = "super::groups::Column::Id"
+    )]
+    Group,
+    #[sea_orm(has_many = "super::activities::Entity")]
+    Activities,
+}
+
+impl Related<super::groups::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Group.def()
+    }
+}
+
+impl Related<super::activities::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Activities.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
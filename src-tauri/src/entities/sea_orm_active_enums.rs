@@ -0,0 +1,219 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "String(None)")]
+#[serde(rename_all = "snake_case")]
+pub enum PaymentMethod {
+    #[sea_orm(string_value = "upi")]
+    Upi,
+    #[sea_orm(string_value = "cash")]
+    Cash,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "String(None)")]
+#[serde(rename_all = "snake_case")]
+pub enum TransactionStatus {
+    #[sea_orm(string_value = "pending")]
+    Pending,
+    #[sea_orm(string_value = "completed")]
+    Completed,
+    #[sea_orm(string_value = "failed")]
+    Failed,
+    #[sea_orm(string_value = "confirmed")]
+    Confirmed,
+    #[sea_orm(string_value = "disputed")]
+    Disputed,
+    #[sea_orm(string_value = "expired")]
+    Expired,
+}
+
+impl TransactionStatus {
+    /// Statuses whose money movement is settled and should reduce balances.
+    pub fn settled() -> Vec<Self> {
+        vec![Self::Completed, Self::Confirmed]
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "String(None)")]
+#[serde(rename_all = "snake_case")]
+pub enum UpiPaymentStatus {
+    #[sea_orm(string_value = "initiated")]
+    Initiated,
+    #[sea_orm(string_value = "pending")]
+    Pending,
+    #[sea_orm(string_value = "success")]
+    Success,
+    #[sea_orm(string_value = "failed")]
+    Failed,
+    #[sea_orm(string_value = "expired")]
+    Expired,
+}
+
+impl UpiPaymentStatus {
+    pub fn is_terminal(self) -> bool {
+        matches!(self, Self::Success | Self::Failed | Self::Expired)
+    }
+
+    /// initiated -> pending -> success / failed / expired, where a provider may
+    /// skip straight from initiated to a final state.
+    pub fn can_transition_to(self, next: Self) -> bool {
+        match self {
+            Self::Initiated => next != Self::Initiated,
+            Self::Pending => next.is_terminal(),
+            Self::Success | Self::Failed | Self::Expired => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "String(None)")]
+#[serde(rename_all = "snake_case")]
+pub enum SplitType {
+    #[sea_orm(string_value = "equal")]
+    Equal,
+    #[default]
+    #[sea_orm(string_value = "exact")]
+    Exact,
+    #[sea_orm(string_value = "percentage")]
+    Percentage,
+    #[sea_orm(string_value = "shares")]
+    Shares,
+    #[sea_orm(string_value = "adjustment")]
+    Adjustment,
+    // derived from line items, see `activity_items`
+    #[sea_orm(string_value = "itemized")]
+    Itemized,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "String(None)")]
+#[serde(rename_all = "snake_case")]
+pub enum RecurrenceFrequency {
+    #[sea_orm(string_value = "daily")]
+    Daily,
+    #[sea_orm(string_value = "weekly")]
+    Weekly,
+    #[sea_orm(string_value = "monthly")]
+    Monthly,
+    #[sea_orm(string_value = "cron")]
+    Cron,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "String(None)")]
+#[serde(rename_all = "snake_case")]
+pub enum HistoryAction {
+    #[sea_orm(string_value = "created")]
+    Created,
+    #[sea_orm(string_value = "updated")]
+    Updated,
+    #[sea_orm(string_value = "deleted")]
+    Deleted,
+    #[sea_orm(string_value = "reverted")]
+    Reverted,
+    #[sea_orm(string_value = "restored")]
+    Restored,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "String(None)")]
+#[serde(rename_all = "snake_case")]
+pub enum GroupRole {
+    // exactly one per group, the only one who can delete it or demote admins
+    #[sea_orm(string_value = "owner")]
+    Owner,
+    #[sea_orm(string_value = "admin")]
+    Admin,
+    #[sea_orm(string_value = "member")]
+    Member,
+    // read-only access to the group
+    #[sea_orm(string_value = "viewer")]
+    Viewer,
+}
+
+impl GroupRole {
+    /// Owners and admins manage the group, its members and everyone's content.
+    pub fn is_admin(self) -> bool {
+        matches!(self, Self::Owner | Self::Admin)
+    }
+
+    pub fn can_write(self) -> bool {
+        self != Self::Viewer
+    }
+}
+
+/// State of one direction of a `friend_collections` pair. A friendship is two
+/// accepted rows, a request a single pending row from the sender, and a block
+/// a single row from the blocker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "String(None)")]
+#[serde(rename_all = "snake_case")]
+pub enum FriendStatus {
+    #[sea_orm(string_value = "pending")]
+    Pending,
+    #[sea_orm(string_value = "accepted")]
+    Accepted,
+    #[sea_orm(string_value = "blocked")]
+    Blocked,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "String(None)")]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationType {
+    #[sea_orm(string_value = "expense_added")]
+    ExpenseAdded,
+    #[sea_orm(string_value = "expense_updated")]
+    ExpenseUpdated,
+    #[sea_orm(string_value = "expense_deleted")]
+    ExpenseDeleted,
+    #[sea_orm(string_value = "added_to_group")]
+    AddedToGroup,
+    #[sea_orm(string_value = "settlement_received")]
+    SettlementReceived,
+    #[sea_orm(string_value = "settlement_confirmed")]
+    SettlementConfirmed,
+    #[sea_orm(string_value = "settlement_disputed")]
+    SettlementDisputed,
+    // also sent when ownership is handed over
+    #[sea_orm(string_value = "promoted_to_admin")]
+    PromotedToAdmin,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "String(None)")]
+#[serde(rename_all = "snake_case")]
+pub enum GroupEventType {
+    #[sea_orm(string_value = "activity_created")]
+    ActivityCreated,
+    #[sea_orm(string_value = "activity_updated")]
+    ActivityUpdated,
+    #[sea_orm(string_value = "activity_deleted")]
+    ActivityDeleted,
+    #[sea_orm(string_value = "activity_restored")]
+    ActivityRestored,
+    #[sea_orm(string_value = "member_joined")]
+    MemberJoined,
+    #[sea_orm(string_value = "member_left")]
+    MemberLeft,
+    #[sea_orm(string_value = "settlement_recorded")]
+    SettlementRecorded,
+    // sent to one user rather than a group
+    #[sea_orm(string_value = "notification_created")]
+    NotificationCreated,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "String(None)")]
+#[serde(rename_all = "snake_case")]
+pub enum DigestFrequency {
+    #[sea_orm(string_value = "off")]
+    Off,
+    #[sea_orm(string_value = "weekly")]
+    Weekly,
+    #[sea_orm(string_value = "monthly")]
+    Monthly,
+}
@@ -1,6 +1,7 @@
 use rust_decimal::Decimal;
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
+use super::sea_orm_active_enums::{PaymentMethod, TransactionStatus};
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
 #[sea_orm(table_name = "transactions")]
@@ -10,11 +11,13 @@ pub struct Model {
     pub payer_id: Uuid,
     pub receiver_id: Uuid,
     pub amount: Decimal,
-    pub method: String,
+    pub method: PaymentMethod,
     pub time: DateTimeWithTimeZone,
-    pub status: String,
+    pub status: TransactionStatus,
     pub group_id: Uuid,
-    pub activity_id: Uuid,
+    pub activity_id: Option<Uuid>,
+    // shared by the per-group transactions created by a single "settle all"
+    pub batch_id: Option<Uuid>,
     pub created_at: DateTimeWithTimeZone,
     pub updated_at: DateTimeWithTimeZone,
 }
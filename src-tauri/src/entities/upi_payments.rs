@@ -1,5 +1,6 @@
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
+use super::sea_orm_active_enums::UpiPaymentStatus;
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
 #[sea_orm(table_name = "upi_payments")]
@@ -8,7 +9,9 @@ pub struct Model {
     pub id: Uuid,
     pub transaction_id: Uuid,
     pub upi_id: String,
-    pub status: String,
+    pub status: UpiPaymentStatus,
+    #[sea_orm(unique)]
+    pub transaction_ref: Option<String>,
     pub created_at: DateTimeWithTimeZone,
     pub updated_at: DateTimeWithTimeZone,
 }
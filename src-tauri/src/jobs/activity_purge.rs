@@ -0,0 +1,108 @@
+use chrono::Utc;
+use crate::controllers::activities_controller::delete_items;
+use crate::controllers::attachments_controller::{delete_activity_attachments, release_blobs};
+use crate::controllers::comments_controller::delete_activity_comments;
+use crate::custom_errors::app::AppError;
+use crate::entities::activities;
+use crate::utils::blob_store::SharedBlobStore;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, TransactionTrait};
+use std::env;
+use std::time::Duration;
+use uuid::Uuid;
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+const DEFAULT_RETENTION_DAYS: i64 = 30;
+
+/// Periodically removes activities that have been in the trash longer than the
+/// retention window, together with their items, comments and attachments.
+/// Their history is kept.
+pub fn spawn(db: DatabaseConnection, store: SharedBlobStore) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(CHECK_INTERVAL);
+        loop {
+            interval.tick().await;
+            if let Err(err) = purge_expired(&db, &store).await {
+                eprintln!("Activity purge failed: {}", err);
+            }
+        }
+    });
+}
+
+/// How long a deleted activity can still be restored, `ACTIVITY_RETENTION_DAYS`.
+pub fn retention() -> Result<chrono::Duration, AppError> {
+    parse_retention_days(env::var("ACTIVITY_RETENTION_DAYS").ok().as_deref())
+}
+
+fn parse_retention_days(value: Option<&str>) -> Result<chrono::Duration, AppError> {
+    let days: i64 = match value {
+        Some(value) => value
+            .trim()
+            .parse()
+            .ok()
+            .filter(|days| *days >= 0)
+            .ok_or_else(|| AppError::ConfigError("Invalid ACTIVITY_RETENTION_DAYS value".to_string()))?,
+        None => DEFAULT_RETENTION_DAYS,
+    };
+    Ok(chrono::Duration::days(days))
+}
+
+pub async fn purge_expired(db: &DatabaseConnection, store: &SharedBlobStore) -> Result<u64, AppError> {
+    let cutoff = Utc::now() - retention()?;
+    let expired = activities::Entity::find()
+        .filter(activities::Column::DeletedAt.lt(cutoff))
+        .all(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    let mut purged = 0;
+    for activity in expired {
+        match purge_activity(db, activity.id).await {
+            Ok(attachment_hashes) => {
+                release_blobs(db, store, attachment_hashes).await;
+                purged += 1;
+            }
+            // rolled back and left in the trash, the next pass tries again
+            Err(err) => eprintln!("Purging activity {} failed: {}", activity.id, err),
+        }
+    }
+
+    Ok(purged)
+}
+
+/// Deletes one activity and everything hanging off it in its own transaction,
+/// so a single failure does not hold back the rest. Returns the content hashes
+/// of its attachments to release once committed.
+async fn purge_activity(db: &DatabaseConnection, activity_id: Uuid) -> Result<Vec<String>, AppError> {
+    let txn = db
+        .begin()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    // an early return drops the transaction, which rolls it back
+    delete_items(&txn, activity_id).await?;
+    delete_activity_comments(&txn, activity_id).await?;
+    let attachment_hashes = delete_activity_attachments(&txn, activity_id).await?;
+    activities::Entity::delete_by_id(activity_id)
+        .exec(&txn)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    txn.commit()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    Ok(attachment_hashes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retention_days_are_parsed() {
+        assert_eq!(parse_retention_days(None).unwrap(), chrono::Duration::days(DEFAULT_RETENTION_DAYS));
+        assert_eq!(parse_retention_days(Some(" 7 ")).unwrap(), chrono::Duration::days(7));
+        assert_eq!(parse_retention_days(Some("0")).unwrap(), chrono::Duration::zero());
+
+        assert!(matches!(parse_retention_days(Some("-1")), Err(AppError::ConfigError(_))));
+        assert!(matches!(parse_retention_days(Some("a week")), Err(AppError::ConfigError(_))));
+    }
+}
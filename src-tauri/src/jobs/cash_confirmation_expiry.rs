@@ -0,0 +1,47 @@
+use chrono::Utc;
+use crate::custom_errors::app::AppError;
+use crate::entities::sea_orm_active_enums::{PaymentMethod, TransactionStatus};
+use crate::entities::transactions;
+use sea_orm::{sea_query::Expr, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
+use std::env;
+use std::time::Duration;
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(10 * 60);
+const DEFAULT_TTL_HOURS: i64 = 72;
+
+/// Periodically expires cash settlements the receiver never confirmed or disputed.
+pub fn spawn(db: DatabaseConnection) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(CHECK_INTERVAL);
+        loop {
+            interval.tick().await;
+            if let Err(err) = expire_unconfirmed(&db).await {
+                eprintln!("Cash confirmation expiry failed: {}", err);
+            }
+        }
+    });
+}
+
+pub async fn expire_unconfirmed(db: &DatabaseConnection) -> Result<u64, AppError> {
+    let ttl_hours: i64 = match env::var("CASH_CONFIRMATION_TTL_HOURS") {
+        Ok(value) => value
+            .trim()
+            .parse()
+            .map_err(|_| AppError::ConfigError("Invalid CASH_CONFIRMATION_TTL_HOURS value".to_string()))?,
+        Err(_) => DEFAULT_TTL_HOURS,
+    };
+    let now = Utc::now();
+    let cutoff = now - chrono::Duration::hours(ttl_hours);
+
+    let result = transactions::Entity::update_many()
+        .col_expr(transactions::Column::Status, Expr::value(TransactionStatus::Expired))
+        .col_expr(transactions::Column::UpdatedAt, Expr::value(now))
+        .filter(transactions::Column::Method.eq(PaymentMethod::Cash))
+        .filter(transactions::Column::Status.eq(TransactionStatus::Pending))
+        .filter(transactions::Column::CreatedAt.lt(cutoff))
+        .exec(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    Ok(result.rows_affected)
+}
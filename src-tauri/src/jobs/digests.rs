@@ -0,0 +1,141 @@
+use chrono::{DateTime, Utc};
+use crate::controllers::digests_controller::build_digest;
+use crate::custom_errors::app::AppError;
+use crate::entities::sea_orm_active_enums::DigestFrequency;
+use crate::entities::{digest_preferences, users};
+use crate::utils::digest_sink::SharedDigestSink;
+use crate::utils::digests::period_start;
+use sea_orm::sea_query::Expr;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
+use std::time::Duration;
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Periodically sends weekly and monthly digests to users who opted in. A digest
+/// covers everything since the previous one, so one missed while the server was
+/// down is sent late rather than skipped.
+pub fn spawn(db: DatabaseConnection, sink: SharedDigestSink) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(CHECK_INTERVAL);
+        loop {
+            interval.tick().await;
+            if let Err(err) = send_due(&db, &sink).await {
+                eprintln!("Digest delivery failed: {}", err);
+            }
+        }
+    });
+}
+
+pub async fn send_due(db: &DatabaseConnection, sink: &SharedDigestSink) -> Result<u64, AppError> {
+    let now = Utc::now();
+    let preferences = digest_preferences::Entity::find()
+        .filter(digest_preferences::Column::Frequency.ne(DigestFrequency::Off))
+        .all(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    let mut sent = 0;
+    for preference in preferences {
+        let last_sent_at = preference.last_sent_at.map(DateTime::<Utc>::from);
+        let since = match due_since(preference.frequency, last_sent_at, now) {
+            Some(since) => since,
+            None => continue,
+        };
+
+        let user_id = preference.user_id;
+        match send_digest(db, sink, preference, since, now).await {
+            Ok(true) => sent += 1,
+            Ok(false) => {}
+            // left as it was so the next pass tries again
+            Err(err) => eprintln!("Digest for user {} failed: {}", user_id, err),
+        }
+    }
+    Ok(sent)
+}
+
+/// Where the next digest starts if one is due at `now`, `None` when it is not.
+/// A digest is due once a full period has passed since the last one.
+fn due_since(
+    frequency: DigestFrequency,
+    last_sent_at: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+) -> Option<DateTime<Utc>> {
+    let due_after = period_start(frequency, now)?;
+    match last_sent_at {
+        Some(last_sent_at) if last_sent_at > due_after => None,
+        Some(last_sent_at) => Some(last_sent_at),
+        None => Some(due_after),
+    }
+}
+
+/// Sends one user's digest unless there is nothing to tell them, and starts
+/// their next period either way. Returns whether anything was sent.
+async fn send_digest(
+    db: &DatabaseConnection,
+    sink: &SharedDigestSink,
+    preference: digest_preferences::Model,
+    since: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> Result<bool, AppError> {
+    let user = users::Entity::find_by_id(preference.user_id)
+        .one(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?
+        .ok_or_else(|| AppError::NotFound("User not found".into()))?;
+
+    let digest = build_digest(db, &user, preference.frequency, since, now).await?;
+    let deliver = !digest.is_empty();
+    if deliver {
+        sink.deliver(&digest).await?;
+    }
+
+    digest_preferences::Entity::update_many()
+        .col_expr(digest_preferences::Column::LastSentAt, Expr::value(now))
+        .filter(digest_preferences::Column::UserId.eq(preference.user_id))
+        .exec(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    Ok(deliver)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(timestamp: &str) -> DateTime<Utc> {
+        timestamp.parse().unwrap()
+    }
+
+    #[test]
+    fn nothing_is_due_while_digests_are_off() {
+        let now = at("2025-06-15T09:00:00Z");
+        assert_eq!(due_since(DigestFrequency::Off, None, now), None);
+        assert_eq!(due_since(DigestFrequency::Off, Some(at("2025-01-01T00:00:00Z")), now), None);
+    }
+
+    #[test]
+    fn a_weekly_digest_is_due_a_full_week_after_the_last() {
+        let last = at("2025-06-08T09:00:00Z");
+        assert_eq!(due_since(DigestFrequency::Weekly, Some(last), at("2025-06-15T08:59:59Z")), None);
+        assert_eq!(due_since(DigestFrequency::Weekly, Some(last), at("2025-06-15T09:00:00Z")), Some(last));
+        // a late one still covers everything since the last
+        assert_eq!(due_since(DigestFrequency::Weekly, Some(last), at("2025-06-20T00:00:00Z")), Some(last));
+    }
+
+    #[test]
+    fn a_monthly_digest_follows_calendar_months() {
+        let last = at("2025-02-28T09:00:00Z");
+        assert_eq!(due_since(DigestFrequency::Monthly, Some(last), at("2025-03-28T08:00:00Z")), None);
+        assert_eq!(due_since(DigestFrequency::Monthly, Some(last), at("2025-03-28T09:00:00Z")), Some(last));
+        // one month before the 31st of March is the 28th of February
+        assert_eq!(due_since(DigestFrequency::Monthly, Some(last), at("2025-03-31T09:00:00Z")), Some(last));
+    }
+
+    #[test]
+    fn a_digest_never_sent_covers_one_period() {
+        let now = at("2025-06-15T09:00:00Z");
+        assert_eq!(due_since(DigestFrequency::Weekly, None, now), Some(at("2025-06-08T09:00:00Z")));
+        assert_eq!(due_since(DigestFrequency::Monthly, None, now), Some(at("2025-05-15T09:00:00Z")));
+    }
+}
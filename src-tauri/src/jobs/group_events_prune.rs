@@ -0,0 +1,47 @@
+use chrono::Utc;
+use crate::custom_errors::app::AppError;
+use crate::entities::group_events;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
+use std::env;
+use std::time::Duration;
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+const DEFAULT_RETENTION_HOURS: i64 = 72;
+
+/// Periodically drops old entries from the event log. Clients offline for
+/// longer than the retention window reload their data instead of catching up.
+pub fn spawn(db: DatabaseConnection) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(CHECK_INTERVAL);
+        loop {
+            interval.tick().await;
+            if let Err(err) = prune_expired(&db).await {
+                eprintln!("Group event pruning failed: {}", err);
+            }
+        }
+    });
+}
+
+/// How long events can still be caught up on, `GROUP_EVENT_RETENTION_HOURS`.
+fn retention() -> Result<chrono::Duration, AppError> {
+    let hours: i64 = match env::var("GROUP_EVENT_RETENTION_HOURS") {
+        Ok(value) => value
+            .trim()
+            .parse()
+            .ok()
+            .filter(|hours| *hours > 0)
+            .ok_or_else(|| AppError::ConfigError("Invalid GROUP_EVENT_RETENTION_HOURS value".to_string()))?,
+        Err(_) => DEFAULT_RETENTION_HOURS,
+    };
+    Ok(chrono::Duration::hours(hours))
+}
+
+pub async fn prune_expired(db: &DatabaseConnection) -> Result<u64, AppError> {
+    let cutoff = Utc::now() - retention()?;
+    let res = group_events::Entity::delete_many()
+        .filter(group_events::Column::CreatedAt.lt(cutoff))
+        .exec(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    Ok(res.rows_affected)
+}
@@ -0,0 +1,5 @@
+pub mod cash_confirmation_expiry;
+pub mod recurring_expenses;
+pub mod activity_purge;
+pub mod group_events_prune;
+pub mod digests;
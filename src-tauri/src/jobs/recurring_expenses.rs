@@ -0,0 +1,233 @@
+use chrono::{DateTime, Utc};
+use crate::controllers::activities_controller::{notify_activity_change, update_group_total_expense};
+use crate::controllers::activity_history_controller::record_activity_history;
+use crate::controllers::group_events_controller::record_group_event;
+use crate::custom_errors::app::AppError;
+use crate::entities::sea_orm_active_enums::{GroupEventType, HistoryAction, NotificationType};
+use crate::entities::{activities, groups, recurring_expenses};
+use crate::models::activities::ActivityRes;
+use crate::utils::event_bus::EventBus;
+use crate::utils::exchange_rates::find_rate;
+use crate::utils::recurrence::next_occurrence;
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, IntoActiveModel, QueryFilter,
+    Set, TransactionTrait,
+};
+use std::time::Duration;
+use uuid::Uuid;
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+// caps how far one template catches up per pass, the rest follows on the next tick
+const MAX_OCCURRENCES_PER_RUN: usize = 100;
+
+/// Periodically turns due recurring expenses into activities. Occurrences missed
+/// while the server was down are created on the first pass after it comes back.
+pub fn spawn(db: DatabaseConnection, bus: EventBus) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(CHECK_INTERVAL);
+        loop {
+            interval.tick().await;
+            if let Err(err) = materialize_due(&db, &bus).await {
+                eprintln!("Recurring expense scheduler failed: {}", err);
+            }
+        }
+    });
+}
+
+pub async fn materialize_due(db: &DatabaseConnection, bus: &EventBus) -> Result<u64, AppError> {
+    let now = Utc::now();
+    let due = recurring_expenses::Entity::find()
+        .filter(recurring_expenses::Column::IsActive.eq(true))
+        .filter(recurring_expenses::Column::NextOccurrenceAt.lte(now))
+        .all(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    let mut created = 0;
+    for template in due {
+        let template_id = template.id;
+        match materialize(db, bus, template, now).await {
+            Ok(count) => created += count,
+            // one broken template, e.g. a missing exchange rate, must not hold up the rest
+            Err(err) => eprintln!("Recurring expense {} failed: {}", template_id, err),
+        }
+    }
+    Ok(created)
+}
+
+/// Creates every occurrence of `template` due by `now` and moves its schedule
+/// forward. The unique (recurring_expense_id, occurrence_at) index makes this
+/// safe to run twice for the same occurrence: the second insert is a no-op.
+pub async fn materialize(
+    db: &DatabaseConnection,
+    bus: &EventBus,
+    template: recurring_expenses::Model,
+    now: DateTime<Utc>,
+) -> Result<u64, AppError> {
+    let group = groups::Entity::find_by_id(template.group_id)
+        .one(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?
+        .ok_or(AppError::NotFound("Group not found".to_string()))?;
+    // picked up again, missed occurrences included, if the group is unarchived
+    if group.archived_at.is_some() {
+        return Ok(0);
+    }
+
+    let starts_at = template.starts_at.with_timezone(&Utc);
+    let ends_at = template.ends_at.map(|ends_at| ends_at.with_timezone(&Utc));
+    let mut next = template.next_occurrence_at.map(|next| next.with_timezone(&Utc));
+
+    let txn = db
+        .begin()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    let mut created = 0;
+    let mut processed = 0;
+    let mut events = Vec::new();
+    while let Some(occurrence) = next {
+        if occurrence > now || processed == MAX_OCCURRENCES_PER_RUN {
+            break;
+        }
+        if ends_at.is_some_and(|ends_at| occurrence > ends_at) {
+            next = None;
+            break;
+        }
+
+        let exchange_rate = find_rate(
+            &txn,
+            &template.currency,
+            &group.currency,
+            occurrence.date_naive(),
+        )
+        .await?;
+
+        let activity = activities::Model {
+            id: Uuid::new_v4(),
+            description: template.description.clone(),
+            paid_by_id: primary_payer(&template)?,
+            payer_ids: template.payer_ids.clone(),
+            payer_amounts: template.payer_amounts.clone(),
+            group_id: template.group_id,
+            time: occurrence.into(),
+            amount: template.amount,
+            currency: template.currency.clone(),
+            exchange_rate,
+            split_members: template.split_members.clone(),
+            split_amounts: template.split_amounts.clone(),
+            split_type: template.split_type,
+            split_values: template.split_values.clone(),
+            tax: None,
+            service_charge: None,
+            tip: None,
+            user_involvement: is_split_member(&template)?,
+            category_id: template.category_id,
+            expense_logo: template.expense_logo.clone(),
+            recurring_expense_id: Some(template.id),
+            occurrence_at: Some(occurrence.into()),
+            deleted_at: None,
+            deleted_by: None,
+            created_at: now.into(),
+            updated_at: now.into(),
+        };
+
+        let inserted = activities::Entity::insert(activity.clone().into_active_model())
+            .on_conflict(
+                OnConflict::columns([
+                    activities::Column::RecurringExpenseId,
+                    activities::Column::OccurrenceAt,
+                ])
+                .do_nothing()
+                .to_owned(),
+            )
+            .exec_without_returning(&txn)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        // zero when an earlier run already created this occurrence
+        if inserted > 0 {
+            record_activity_history(
+                &txn,
+                HistoryAction::Created,
+                template.creator_id,
+                None,
+                Some((&activity, &[])),
+                None,
+            )
+            .await?;
+            events.extend(
+                notify_activity_change(
+                    &txn,
+                    NotificationType::ExpenseAdded,
+                    template.creator_id,
+                    &activity,
+                    Vec::new(),
+                )
+                .await?,
+            );
+            events.push(
+                record_group_event(
+                    &txn,
+                    GroupEventType::ActivityCreated,
+                    activity.group_id,
+                    ActivityRes::from(activity.clone()),
+                )
+                .await?,
+            );
+            created += inserted;
+        }
+
+        processed += 1;
+        next = next_occurrence(
+            template.frequency,
+            template.cron_expression.as_deref(),
+            starts_at,
+            occurrence,
+        )?;
+    }
+
+    if let (Some(occurrence), Some(ends_at)) = (next, ends_at) {
+        if occurrence > ends_at {
+            next = None;
+        }
+    }
+
+    let group_id = template.group_id;
+    let mut template_model = template.into_active_model();
+    template_model.next_occurrence_at = Set(next.map(Into::into));
+    template_model.updated_at = Set(now.into());
+    template_model
+        .update(&txn)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    if created > 0 {
+        update_group_total_expense(&txn, group_id).await?;
+    }
+
+    txn.commit()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    bus.publish(events);
+
+    Ok(created)
+}
+
+// the creator stays the primary payer whenever they pay a part
+fn primary_payer(template: &recurring_expenses::Model) -> Result<Uuid, AppError> {
+    let payer_ids: Vec<Uuid> = serde_json::from_value(template.payer_ids.clone())
+        .map_err(|e| AppError::DatabaseError(format!("Malformed payer ids: {}", e)))?;
+    Ok(payer_ids
+        .iter()
+        .copied()
+        .find(|payer_id| *payer_id == template.creator_id)
+        .or_else(|| payer_ids.first().copied())
+        .unwrap_or(template.creator_id))
+}
+
+fn is_split_member(template: &recurring_expenses::Model) -> Result<bool, AppError> {
+    let split_members: Vec<Uuid> = serde_json::from_value(template.split_members.clone())
+        .map_err(|e| AppError::DatabaseError(format!("Malformed split members: {}", e)))?;
+    Ok(split_members.contains(&template.creator_id))
+}
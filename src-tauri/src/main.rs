@@ -6,6 +6,7 @@ mod models;
 mod custom_errors;
 mod utils;
 mod request_verifier;
+mod jobs;
 
 use axum::{Router, Extension};
 
@@ -15,7 +16,32 @@ async fn main() {
     let pool = db::establish_connection()
         .await;
         // .expect("Failed to connect to the database");
-    let app: Router = routes::app_routes().layer(Extension(pool));
+    match utils::exchange_rates::import_rates_from_env(&pool).await {
+        Ok(0) => {}
+        Ok(count) => println!("Imported {} exchange rates", count),
+        Err(e) => eprintln!("Failed to import exchange rates: {}", e),
+    }
+    jobs::cash_confirmation_expiry::spawn(pool.clone());
+    let event_bus = utils::event_bus::EventBus::new();
+    jobs::recurring_expenses::spawn(pool.clone(), event_bus.clone());
+
+    let upi_provider = utils::upi_psp::provider_from_env()
+        .expect("Failed to configure UPI payment provider");
+    let blob_store = utils::blob_store::store_from_env()
+        .expect("Failed to configure attachment storage");
+    let oauth_verifier = utils::oauth::verifier_from_env()
+        .expect("Failed to configure OAuth providers");
+    jobs::activity_purge::spawn(pool.clone(), blob_store.clone());
+    jobs::group_events_prune::spawn(pool.clone());
+    let digest_sink = utils::digest_sink::sink_from_env()
+        .expect("Failed to configure digest delivery");
+    jobs::digests::spawn(pool.clone(), digest_sink);
+    let app: Router = routes::app_routes()
+        .layer(Extension(event_bus))
+        .layer(Extension(upi_provider))
+        .layer(Extension(oauth_verifier))
+        .layer(Extension(blob_store))
+        .layer(Extension(pool));
 
     let addr = "0.0.0.0:3000".parse().unwrap();
     println!("Server running on {}", addr);
@@ -1,39 +1,136 @@
 use crate::custom_errors::app::AppError;
+use crate::entities::sea_orm_active_enums::SplitType;
+use crate::utils::exchange_rates::normalize_currency;
+use crate::entities::activity_items;
+use crate::utils::split_calculator::{compute_itemized_split, compute_split};
 use rust_decimal::Decimal;
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
-use serde_json::json;
 use uuid::Uuid;
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PayerReq {
+    pub payer_id: Uuid,
+    pub amount: Decimal,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActivityItemReq {
+    pub name: String,
+    pub unit_price: Decimal,
+    #[serde(default = "default_quantity")]
+    pub quantity: Decimal,
+    pub assigned_members: Vec<Uuid>,
+}
+
+fn default_quantity() -> Decimal {
+    Decimal::ONE
+}
+
+impl ActivityItemReq {
+    pub fn line_total(&self) -> Decimal {
+        self.unit_price * self.quantity
+    }
+}
+
+/// Line items of an itemized expense and the charges spread across them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Itemization {
+    pub items: Vec<ActivityItemReq>,
+    pub tax: Decimal,
+    pub service_charge: Decimal,
+    pub tip: Decimal,
+}
+
+impl Itemization {
+    pub fn new(
+        items: Option<&[ActivityItemReq]>,
+        tax: Option<Decimal>,
+        service_charge: Option<Decimal>,
+        tip: Option<Decimal>,
+    ) -> Result<Self, AppError> {
+        let items = match items {
+            Some(items) if !items.is_empty() => items.to_vec(),
+            _ => {
+                return Err(AppError::ValidationError(
+                    "Items are required for an itemized split".into(),
+                ))
+            }
+        };
+        for item in &items {
+            if item.name.trim().is_empty() {
+                return Err(AppError::ValidationError("Item name cannot be empty".into()));
+            }
+            if item.unit_price.is_sign_negative() && !item.unit_price.is_zero() {
+                return Err(AppError::ValidationError("Item price cannot be negative".into()));
+            }
+            if item.quantity <= Decimal::ZERO {
+                return Err(AppError::ValidationError(
+                    "Item quantity must be greater than zero".into(),
+                ));
+            }
+            if has_duplicates(&item.assigned_members) {
+                return Err(AppError::ValidationError(
+                    "Item members cannot contain duplicates".into(),
+                ));
+            }
+        }
+
+        let charges = [tax, service_charge, tip];
+        if charges.iter().flatten().any(|charge| *charge < Decimal::ZERO) {
+            return Err(AppError::ValidationError(
+                "Tax, service charge and tip cannot be negative".into(),
+            ));
+        }
+
+        Ok(Self {
+            items,
+            tax: tax.unwrap_or_default(),
+            service_charge: service_charge.unwrap_or_default(),
+            tip: tip.unwrap_or_default(),
+        })
+    }
+
+    /// Split members and amounts derived from the items.
+    pub fn split(&self, amount: Decimal) -> Result<(Vec<Uuid>, Vec<Decimal>), AppError> {
+        let items: Vec<(Decimal, &[Uuid])> = self
+            .items
+            .iter()
+            .map(|item| (item.line_total(), item.assigned_members.as_slice()))
+            .collect();
+        compute_itemized_split(amount, &items, self.tax + self.service_charge + self.tip)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Deserialize)]
 pub struct CreateActivityReq {
     pub description: String,
     pub group_id: Uuid,
     pub amount: Decimal,
+    // defaults to the group's currency
+    pub currency: Option<String>,
+    // derived from the items for itemized splits
+    #[serde(default)]
     pub split_members: Vec<Uuid>,
+    // only read for exact splits, every other split type has its amounts computed here
+    #[serde(default)]
     pub split_amounts: Vec<Decimal>,
+    #[serde(default)]
+    pub split_type: SplitType,
+    pub split_values: Option<Vec<Decimal>>,
+    // defaults to the caller paying the whole amount
+    pub payers: Option<Vec<PayerReq>>,
+    // only valid for itemized splits
+    pub items: Option<Vec<ActivityItemReq>>,
+    pub tax: Option<Decimal>,
+    pub service_charge: Option<Decimal>,
+    pub tip: Option<Decimal>,
+    // suggested from the description when left out
+    pub category_id: Option<Uuid>,
     pub expense_logo: Option<String>,
 }
 
 impl CreateActivityReq {
-    pub fn new(
-        description: String,
-        group_id: Uuid,
-        amount: Decimal,
-        split_members: Vec<Uuid>,
-        split_amounts: Vec<Decimal>,
-        expense_logo: Option<String>,
-    ) -> Self {
-        Self {
-            description,
-            group_id,
-            amount,
-            split_members,
-            split_amounts,
-            expense_logo,
-        }
-    }
-
     pub fn check(&mut self) -> Result<(), AppError> {
         if self.description.trim().is_empty() {
             return Err(AppError::ValidationError(
@@ -51,12 +148,46 @@ impl CreateActivityReq {
             }
         }
 
+        if let Some(currency) = &self.currency {
+            self.currency = Some(normalize_currency(currency)?);
+        }
+
         if self.amount <= Decimal::ZERO {
             return Err(AppError::ValidationError(
                 "Amount must be greater than zero".into(),
             ));
         }
 
+        if has_duplicates(&self.split_members) {
+            return Err(AppError::ValidationError(
+                "Split members cannot contain duplicates".into(),
+            ));
+        }
+
+        if let Some(payers) = &self.payers {
+            check_payers(payers, self.amount)?;
+        }
+
+        if let Some(itemization) = self.itemization()? {
+            let (split_members, split_amounts) = itemization.split(self.amount)?;
+            self.split_members = split_members;
+            self.split_amounts = split_amounts;
+            self.split_values = None;
+            return Ok(());
+        }
+
+        if self.split_type != SplitType::Exact {
+            self.split_amounts = compute_split(
+                self.split_type,
+                self.amount,
+                self.split_members.len(),
+                self.split_values.as_deref(),
+            )?;
+            return Ok(());
+        }
+
+        self.split_values = None;
+
         if self.split_members.is_empty() || self.split_amounts.is_empty() {
             return Err(AppError::ValidationError(
                 "Split members and split amounts cannot be empty".into(),
@@ -77,6 +208,62 @@ impl CreateActivityReq {
 
         Ok(())
     }
+
+    /// The items of an itemized split, `None` for every other split type.
+    pub fn itemization(&self) -> Result<Option<Itemization>, AppError> {
+        itemization_for(
+            self.split_type,
+            self.items.as_deref(),
+            self.tax,
+            self.service_charge,
+            self.tip,
+        )
+    }
+}
+
+fn itemization_for(
+    split_type: SplitType,
+    items: Option<&[ActivityItemReq]>,
+    tax: Option<Decimal>,
+    service_charge: Option<Decimal>,
+    tip: Option<Decimal>,
+) -> Result<Option<Itemization>, AppError> {
+    if split_type == SplitType::Itemized {
+        return Itemization::new(items, tax, service_charge, tip).map(Some);
+    }
+    if items.is_some() || tax.is_some() || service_charge.is_some() || tip.is_some() {
+        return Err(AppError::ValidationError(
+            "Items, tax, service charge and tip are only valid for itemized splits".into(),
+        ));
+    }
+    Ok(None)
+}
+
+fn check_payers(payers: &[PayerReq], amount: Decimal) -> Result<(), AppError> {
+    if payers.is_empty() {
+        return Err(AppError::ValidationError("Payers cannot be empty".into()));
+    }
+    let payer_ids: Vec<Uuid> = payers.iter().map(|payer| payer.payer_id).collect();
+    if has_duplicates(&payer_ids) {
+        return Err(AppError::ValidationError("Payers cannot contain duplicates".into()));
+    }
+    if payers.iter().any(|payer| payer.amount <= Decimal::ZERO) {
+        return Err(AppError::ValidationError(
+            "Each payer must pay more than zero".into(),
+        ));
+    }
+    let total_paid: Decimal = payers.iter().map(|payer| payer.amount).sum();
+    if total_paid != amount {
+        return Err(AppError::AmountsDontAddUp(
+            "Total paid by payers does not match the main amount".into(),
+        ));
+    }
+    Ok(())
+}
+
+fn has_duplicates(members: &[Uuid]) -> bool {
+    let mut seen = std::collections::HashSet::new();
+    members.iter().any(|member| !seen.insert(member))
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize)]
@@ -84,65 +271,86 @@ pub struct ActivityRes {
     pub id: Uuid,
     pub description: String,
     pub paid_by_id: Uuid,
+    pub payer_ids: Json,
+    pub payer_amounts: Json,
     pub group_id: Uuid,
     pub time: DateTimeWithTimeZone,
     pub amount: Decimal,
+    pub currency: String,
+    pub exchange_rate: Decimal,
     pub split_members: Json,
     pub split_amounts: Json,
+    pub split_type: SplitType,
+    pub split_values: Option<Json>,
+    pub tax: Option<Decimal>,
+    pub service_charge: Option<Decimal>,
+    pub tip: Option<Decimal>,
+    // only loaded for itemized splits
+    pub items: Option<Vec<ActivityItemRes>>,
     pub user_involvement: bool,
+    pub category_id: Option<Uuid>,
     pub expense_logo: Option<String>,
     pub created_at: DateTimeWithTimeZone,
     pub updated_at: DateTimeWithTimeZone,
 }
 
 impl ActivityRes {
-    pub fn new(
-        id: Uuid,
-        description: String,
-        paid_by_id: Uuid,
-        group_id: Uuid,
-        time: DateTimeWithTimeZone,
-        amount: Decimal,
-        split_members: Json,
-        split_amounts: Json,
-        user_involvement: bool,
-        expense_logo: Option<String>,
-        created_at: DateTimeWithTimeZone,
-        updated_at: DateTimeWithTimeZone,
-    ) -> Self {
+    pub fn with_items(mut self, items: Vec<activity_items::Model>) -> Self {
+        self.items = Some(items.into_iter().map(ActivityItemRes::from).collect());
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ActivityItemRes {
+    pub id: Uuid,
+    pub name: String,
+    pub unit_price: Decimal,
+    pub quantity: Decimal,
+    pub line_total: Decimal,
+    pub assigned_members: Json,
+}
+
+impl From<activity_items::Model> for ActivityItemRes {
+    fn from(item: activity_items::Model) -> Self {
         Self {
-            id,
-            description,
-            paid_by_id,
-            group_id,
-            time,
-            amount,
-            split_members,
-            split_amounts,
-            user_involvement,
-            expense_logo,
-            created_at,
-            updated_at,
+            id: item.id,
+            name: item.name,
+            unit_price: item.unit_price,
+            quantity: item.quantity,
+            line_total: (item.unit_price * item.quantity).round_dp(2),
+            assigned_members: item.assigned_members,
         }
     }
 }
 
 impl From<crate::entities::activities::Model> for ActivityRes {
     fn from(activity: crate::entities::activities::Model) -> Self {
-        Self::new(
-            activity.id,
-            activity.description,
-            activity.paid_by_id,
-            activity.group_id,
-            activity.time,
-            activity.amount,
-            json!(activity.split_members.clone()),
-            json!(activity.split_amounts.clone()),
-            activity.user_involvement,
-            activity.expense_logo,
-            activity.created_at,
-            activity.updated_at,
-        )
+        Self {
+            id: activity.id,
+            description: activity.description,
+            paid_by_id: activity.paid_by_id,
+            payer_ids: activity.payer_ids,
+            payer_amounts: activity.payer_amounts,
+            group_id: activity.group_id,
+            time: activity.time,
+            amount: activity.amount,
+            currency: activity.currency,
+            exchange_rate: activity.exchange_rate,
+            split_members: activity.split_members,
+            split_amounts: activity.split_amounts,
+            split_type: activity.split_type,
+            split_values: activity.split_values,
+            tax: activity.tax,
+            service_charge: activity.service_charge,
+            tip: activity.tip,
+            items: None,
+            user_involvement: activity.user_involvement,
+            category_id: activity.category_id,
+            expense_logo: activity.expense_logo,
+            created_at: activity.created_at,
+            updated_at: activity.updated_at,
+        }
     }
 }
 
@@ -152,11 +360,33 @@ pub struct UpdateActivityReq {
     pub description: Option<String>,
     pub group_id: Uuid,
     pub amount: Option<Decimal>,
+    pub currency: Option<String>,
     pub split_members: Option<Vec<Uuid>>,
     pub split_amounts: Option<Vec<Decimal>>,
+    pub split_type: Option<SplitType>,
+    pub split_values: Option<Vec<Decimal>>,
+    pub payers: Option<Vec<PayerReq>>,
+    // an itemized split is recomputed from scratch, so its items have to be restated
+    pub items: Option<Vec<ActivityItemReq>>,
+    pub tax: Option<Decimal>,
+    pub service_charge: Option<Decimal>,
+    pub tip: Option<Decimal>,
+    #[serde(default, deserialize_with = "crate::utils::double_option::deserialize")]
+    pub category_id: Option<Option<Uuid>>, // Double Option to handle removing the category
+    #[serde(default, deserialize_with = "crate::utils::double_option::deserialize")]
     pub expense_logo: Option<Option<String>>, // Double Option to handle setting to null
 }
 
+/// The split an activity ends up with once an update is applied on top of it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedSplit {
+    pub split_type: SplitType,
+    pub split_members: Vec<Uuid>,
+    pub split_amounts: Vec<Decimal>,
+    pub split_values: Option<Vec<Decimal>>,
+    pub itemization: Option<Itemization>,
+}
+
 impl UpdateActivityReq {
     pub fn check(&self) -> Result<(), crate::custom_errors::app::AppError> {
         // Manual validation
@@ -182,6 +412,10 @@ impl UpdateActivityReq {
             }
         }
 
+        if let Some(currency) = &self.currency {
+            normalize_currency(currency)?;
+        }
+
         // Validate split_members and split_amounts have same length
         if let (Some(split_members), Some(split_amounts)) =
             (&self.split_members, &self.split_amounts)
@@ -207,6 +441,145 @@ impl UpdateActivityReq {
     }
         Ok(())
     }
+
+    /// Payers after the update, or `None` when they stay as they are. A single
+    /// payer follows a changed amount; several payers have to be restated.
+    pub fn resolve_payers(
+        &self,
+        current: &crate::entities::activities::Model,
+    ) -> Result<Option<Vec<(Uuid, Decimal)>>, AppError> {
+        let amount = self.amount.unwrap_or(current.amount);
+
+        if let Some(payers) = &self.payers {
+            check_payers(payers, amount)?;
+            return Ok(Some(
+                payers.iter().map(|payer| (payer.payer_id, payer.amount)).collect(),
+            ));
+        }
+
+        if self.amount.is_none() {
+            return Ok(None);
+        }
+
+        match parse_payers(current)?.as_slice() {
+            [(payer_id, _)] => Ok(Some(vec![(*payer_id, amount)])),
+            _ => Err(AppError::AmountsDontAddUp(
+                "Payers must be provided when changing the amount of an expense with several payers".into(),
+            )),
+        }
+    }
+
+    pub fn changes_split(&self) -> bool {
+        self.amount.is_some()
+            || self.split_members.is_some()
+            || self.split_amounts.is_some()
+            || self.split_type.is_some()
+            || self.split_values.is_some()
+            || self.items.is_some()
+            || self.tax.is_some()
+            || self.service_charge.is_some()
+            || self.tip.is_some()
+    }
+
+    /// Recomputes the split from the fields in this request, falling back to the
+    /// activity's stored split for anything the request leaves out.
+    pub fn resolve_split(
+        &self,
+        current: &crate::entities::activities::Model,
+    ) -> Result<ResolvedSplit, AppError> {
+        let amount = self.amount.unwrap_or(current.amount);
+        let split_type = self.split_type.unwrap_or(current.split_type);
+        let members_changed = self.split_members.is_some();
+
+        if let Some(itemization) = itemization_for(
+            split_type,
+            self.items.as_deref(),
+            self.tax,
+            self.service_charge,
+            self.tip,
+        )? {
+            let (split_members, split_amounts) = itemization.split(amount)?;
+            return Ok(ResolvedSplit {
+                split_type,
+                split_members,
+                split_amounts,
+                split_values: None,
+                itemization: Some(itemization),
+            });
+        }
+
+        let split_members: Vec<Uuid> = match &self.split_members {
+            Some(split_members) => split_members.clone(),
+            None => serde_json::from_value(current.split_members.clone())
+                .map_err(|e| AppError::DatabaseError(format!("Malformed split members: {}", e)))?,
+        };
+        if has_duplicates(&split_members) {
+            return Err(AppError::ValidationError(
+                "Split members cannot contain duplicates".into(),
+            ));
+        }
+
+        if split_type == SplitType::Exact {
+            let split_amounts: Vec<Decimal> = match &self.split_amounts {
+                Some(split_amounts) => split_amounts.clone(),
+                None if !members_changed && current.split_type == SplitType::Exact => {
+                    serde_json::from_value(current.split_amounts.clone()).map_err(|e| {
+                        AppError::DatabaseError(format!("Malformed split amounts: {}", e))
+                    })?
+                }
+                None => {
+                    return Err(AppError::ValidationError(
+                        "Split amounts are required for an exact split".into(),
+                    ))
+                }
+            };
+            if split_members.len() != split_amounts.len() {
+                return Err(AppError::ValidationError(
+                    "Split amounts must match the number of split members".into(),
+                ));
+            }
+            let total_split: Decimal = split_amounts.iter().sum();
+            if total_split != amount {
+                return Err(AppError::AmountsDontAddUp(
+                    "Total split amount does not match the main amount".into(),
+                ));
+            }
+            return Ok(ResolvedSplit {
+                split_type,
+                split_members,
+                split_amounts,
+                split_values: None,
+                itemization: None,
+            });
+        }
+
+        // stored values only still make sense if they describe the same kind of split for the same people
+        let split_values: Option<Vec<Decimal>> = match &self.split_values {
+            Some(split_values) => Some(split_values.clone()),
+            None if !members_changed && split_type == current.split_type => current
+                .split_values
+                .clone()
+                .map(serde_json::from_value)
+                .transpose()
+                .map_err(|e| AppError::DatabaseError(format!("Malformed split values: {}", e)))?,
+            None => None,
+        };
+
+        let split_amounts = compute_split(
+            split_type,
+            amount,
+            split_members.len(),
+            split_values.as_deref(),
+        )?;
+
+        Ok(ResolvedSplit {
+            split_type,
+            split_members,
+            split_amounts,
+            split_values: if split_type == SplitType::Equal { None } else { split_values },
+            itemization: None,
+        })
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -237,6 +610,8 @@ impl DeleteActivityReq {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct GetActivitiesReq {
     pub group_id: Uuid,
+    // only expenses in this category when set
+    pub category_id: Option<Uuid>,
 }
 
 impl GetActivitiesReq {
@@ -249,4 +624,173 @@ impl GetActivitiesReq {
         
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetTrashReq {
+    pub group_id: Uuid,
+}
+
+impl GetTrashReq {
+    pub fn check(&self) -> Result<(), AppError> {
+        if self.group_id == Uuid::nil() {
+            return Err(AppError::ValidationError("Group ID cannot be empty".to_string()));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RestoreActivityReq {
+    pub activity_id: Uuid,
+    pub group_id: Uuid,
+}
+
+impl RestoreActivityReq {
+    pub fn check(&self) -> Result<(), AppError> {
+        if self.activity_id == Uuid::nil() {
+            return Err(AppError::ValidationError("Activity ID cannot be empty".to_string()));
+        }
+        if self.group_id == Uuid::nil() {
+            return Err(AppError::ValidationError("Group ID cannot be empty".to_string()));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TrashedActivityRes {
+    #[serde(flatten)]
+    pub activity: ActivityRes,
+    pub deleted_at: Option<DateTimeWithTimeZone>,
+    pub deleted_by: Option<Uuid>,
+    // restoring is no longer possible after this
+    pub purge_at: Option<DateTimeWithTimeZone>,
+}
+
+impl TrashedActivityRes {
+    pub fn new(
+        activity: crate::entities::activities::Model,
+        items: Option<Vec<activity_items::Model>>,
+        retention: chrono::Duration,
+    ) -> Self {
+        let deleted_at = activity.deleted_at;
+        let deleted_by = activity.deleted_by;
+        let activity = match items {
+            Some(items) => ActivityRes::from(activity).with_items(items),
+            None => ActivityRes::from(activity),
+        };
+        Self {
+            activity,
+            deleted_at,
+            deleted_by,
+            purge_at: deleted_at.map(|deleted_at| deleted_at + retention),
+        }
+    }
+}
+
+pub fn parse_payers(
+    activity: &crate::entities::activities::Model,
+) -> Result<Vec<(Uuid, Decimal)>, AppError> {
+    let payer_ids: Vec<Uuid> = serde_json::from_value(activity.payer_ids.clone())
+        .map_err(|e| AppError::DatabaseError(format!("Malformed payer ids: {}", e)))?;
+    let payer_amounts: Vec<Decimal> = serde_json::from_value(activity.payer_amounts.clone())
+        .map_err(|e| AppError::DatabaseError(format!("Malformed payer amounts: {}", e)))?;
+    if payer_ids.len() != payer_amounts.len() {
+        return Err(AppError::DatabaseError(format!(
+            "Malformed payers: {} ids but {} amounts",
+            payer_ids.len(),
+            payer_amounts.len()
+        )));
+    }
+    Ok(payer_ids.into_iter().zip(payer_amounts).collect())
+}
+
+pub fn parse_splits(
+    activity: &crate::entities::activities::Model,
+) -> Result<Vec<(Uuid, Decimal)>, AppError> {
+    let split_members: Vec<Uuid> = serde_json::from_value(activity.split_members.clone())
+        .map_err(|e| AppError::DatabaseError(format!("Malformed split members: {}", e)))?;
+    let split_amounts: Vec<Decimal> = serde_json::from_value(activity.split_amounts.clone())
+        .map_err(|e| AppError::DatabaseError(format!("Malformed split amounts: {}", e)))?;
+    if split_members.len() != split_amounts.len() {
+        return Err(AppError::DatabaseError(format!(
+            "Malformed splits: {} members but {} amounts",
+            split_members.len(),
+            split_amounts.len()
+        )));
+    }
+    Ok(split_members.into_iter().zip(split_amounts).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use serde_json::json;
+
+    fn activity(payer_amounts: serde_json::Value, split_amounts: serde_json::Value) -> crate::entities::activities::Model {
+        let member = Uuid::new_v4();
+        let now = Utc::now().into();
+        crate::entities::activities::Model {
+            id: Uuid::new_v4(),
+            description: "Dinner".into(),
+            paid_by_id: member,
+            payer_ids: json!([member]),
+            payer_amounts,
+            group_id: Uuid::new_v4(),
+            time: now,
+            amount: Decimal::from(10),
+            currency: "INR".into(),
+            exchange_rate: Decimal::ONE,
+            split_members: json!([member]),
+            split_amounts,
+            split_type: SplitType::Equal,
+            split_values: None,
+            tax: None,
+            service_charge: None,
+            tip: None,
+            user_involvement: true,
+            expense_logo: None,
+            category_id: None,
+            recurring_expense_id: None,
+            occurrence_at: None,
+            deleted_at: None,
+            deleted_by: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn payers_and_splits_pair_ids_with_amounts() {
+        let activity = activity(json!(["10"]), json!(["10"]));
+        assert_eq!(parse_payers(&activity).unwrap(), vec![(activity.paid_by_id, Decimal::from(10))]);
+        assert_eq!(parse_splits(&activity).unwrap(), vec![(activity.paid_by_id, Decimal::from(10))]);
+    }
+
+    #[test]
+    fn mismatched_lengths_are_rejected() {
+        let activity = activity(json!(["6", "4"]), json!([]));
+        assert!(matches!(parse_payers(&activity), Err(AppError::DatabaseError(_))));
+        assert!(matches!(parse_splits(&activity), Err(AppError::DatabaseError(_))));
+    }
+
+    #[test]
+    fn nullable_update_fields_tell_missing_from_null() {
+        let (id, group_id, category_id) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let update = |body: serde_json::Value| -> UpdateActivityReq { serde_json::from_value(body).unwrap() };
+
+        let untouched = update(json!({ "id": id, "group_id": group_id, "description": "Lunch" }));
+        assert_eq!(untouched.category_id, None);
+        assert_eq!(untouched.expense_logo, None);
+
+        let cleared = update(json!({ "id": id, "group_id": group_id, "category_id": null, "expense_logo": null }));
+        assert_eq!(cleared.category_id, Some(None));
+        assert_eq!(cleared.expense_logo, Some(None));
+
+        let set = update(json!({ "id": id, "group_id": group_id, "category_id": category_id, "expense_logo": "pizza" }));
+        assert_eq!(set.category_id, Some(Some(category_id)));
+        assert_eq!(set.expense_logo, Some(Some("pizza".to_string())));
+    }
+}
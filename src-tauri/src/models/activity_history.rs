@@ -0,0 +1,105 @@
+use crate::custom_errors::app::AppError;
+use crate::entities::activity_history;
+use crate::entities::sea_orm_active_enums::HistoryAction;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct GetActivityHistoryReq {
+    pub group_id: Uuid,
+    pub activity_id: Uuid,
+}
+
+impl GetActivityHistoryReq {
+    pub fn check(&self) -> Result<(), AppError> {
+        if self.group_id == Uuid::nil() {
+            return Err(AppError::ValidationError("Group Id cannot be empty".into()));
+        }
+        if self.activity_id == Uuid::nil() {
+            return Err(AppError::ValidationError("Activity Id cannot be empty".into()));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct GetGroupHistoryReq {
+    pub group_id: Uuid,
+    // zero based, defaults to the most recent page
+    #[serde(default)]
+    pub page: u64,
+}
+
+impl GetGroupHistoryReq {
+    pub fn check(&self) -> Result<(), AppError> {
+        if self.group_id == Uuid::nil() {
+            return Err(AppError::ValidationError("Group Id cannot be empty".into()));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct RevertActivityReq {
+    pub group_id: Uuid,
+    pub activity_id: Uuid,
+    // the version the activity is restored to
+    pub version: i32,
+}
+
+impl RevertActivityReq {
+    pub fn check(&self) -> Result<(), AppError> {
+        if self.group_id == Uuid::nil() {
+            return Err(AppError::ValidationError("Group Id cannot be empty".into()));
+        }
+        if self.activity_id == Uuid::nil() {
+            return Err(AppError::ValidationError("Activity Id cannot be empty".into()));
+        }
+        if self.version < 1 {
+            return Err(AppError::ValidationError("Version must be at least 1".into()));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ActivityHistoryRes {
+    pub id: Uuid,
+    pub activity_id: Uuid,
+    pub group_id: Uuid,
+    pub version: i32,
+    pub action: HistoryAction,
+    pub actor_id: Uuid,
+    pub changes: Json,
+    pub snapshot: Json,
+    pub items: Json,
+    pub reverted_to_version: Option<i32>,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+impl From<activity_history::Model> for ActivityHistoryRes {
+    fn from(entry: activity_history::Model) -> Self {
+        Self {
+            id: entry.id,
+            activity_id: entry.activity_id,
+            group_id: entry.group_id,
+            version: entry.version,
+            action: entry.action,
+            actor_id: entry.actor_id,
+            changes: entry.changes,
+            snapshot: entry.snapshot,
+            items: entry.items,
+            reverted_to_version: entry.reverted_to_version,
+            created_at: entry.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ActivityHistoryPageRes {
+    pub entries: Vec<ActivityHistoryRes>,
+    pub page: u64,
+    pub total_pages: u64,
+    pub total_entries: u64,
+}
@@ -0,0 +1,99 @@
+use crate::custom_errors::app::AppError;
+use crate::entities::activity_attachments;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct UploadAttachmentReq {
+    pub group_id: Uuid,
+    pub activity_id: Uuid,
+    pub file_name: String,
+}
+
+impl UploadAttachmentReq {
+    pub fn check(&mut self) -> Result<(), AppError> {
+        if self.group_id == Uuid::nil() {
+            return Err(AppError::ValidationError("Group Id cannot be empty".into()));
+        }
+        if self.activity_id == Uuid::nil() {
+            return Err(AppError::ValidationError("Activity Id cannot be empty".into()));
+        }
+        // keep only the last path segment of whatever the client sent
+        let file_name = self
+            .file_name
+            .rsplit(['/', '\\'])
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_string();
+        if file_name.is_empty() || file_name.len() > 255 {
+            return Err(AppError::ValidationError(
+                "File name must be between 1 and 255 characters".into(),
+            ));
+        }
+        self.file_name = file_name;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct GetAttachmentsReq {
+    pub group_id: Uuid,
+    pub activity_id: Uuid,
+}
+
+impl GetAttachmentsReq {
+    pub fn check(&self) -> Result<(), AppError> {
+        if self.group_id == Uuid::nil() {
+            return Err(AppError::ValidationError("Group Id cannot be empty".into()));
+        }
+        if self.activity_id == Uuid::nil() {
+            return Err(AppError::ValidationError("Activity Id cannot be empty".into()));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct AttachmentIdReq {
+    pub attachment_id: Uuid,
+}
+
+impl AttachmentIdReq {
+    pub fn check(&self) -> Result<(), AppError> {
+        if self.attachment_id == Uuid::nil() {
+            return Err(AppError::ValidationError("Attachment Id cannot be empty".into()));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AttachmentRes {
+    pub id: Uuid,
+    pub activity_id: Uuid,
+    pub group_id: Uuid,
+    pub uploaded_by: Uuid,
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub content_hash: String,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+impl From<activity_attachments::Model> for AttachmentRes {
+    fn from(attachment: activity_attachments::Model) -> Self {
+        Self {
+            id: attachment.id,
+            activity_id: attachment.activity_id,
+            group_id: attachment.group_id,
+            uploaded_by: attachment.uploaded_by,
+            file_name: attachment.file_name,
+            content_type: attachment.content_type,
+            size_bytes: attachment.size_bytes,
+            content_hash: attachment.content_hash,
+            created_at: attachment.created_at,
+        }
+    }
+}
@@ -3,28 +3,27 @@ use sea_orm::entity::prelude::*;
 use serde::{Serialize, Deserialize};
 use crate::custom_errors::app::AppError;
 
+/// The account's provider id and email come from the verified ID token, never
+/// from the request. Username and UPI id are only needed to create an account.
 #[derive(Debug, Clone, PartialEq,Deserialize)]
 pub struct AuthReq {
     pub oauth_provider: String,
-    pub oauth_id: String,  
-    pub username: String,
-    pub email: String,
-    pub upi_id: String,
+    pub id_token: String,
+    pub username: Option<String>,
+    pub upi_id: Option<String>,
 }
 
 impl AuthReq{
     pub fn new(
         oauth_provider: String,
-        oauth_id: String,
-        username: String,
-        email: String,
-        upi_id: String,
+        id_token: String,
+        username: Option<String>,
+        upi_id: Option<String>,
     ) -> Self {
         Self {
             oauth_provider,
-            oauth_id,
+            id_token,
             username,
-            email,
             upi_id,
         }
     }
@@ -33,19 +32,29 @@ impl AuthReq{
         if self.oauth_provider.trim().is_empty() {
             return Err(AppError::ValidationError("OAuth provider is required".into()));
         }
-        if self.oauth_id.trim().is_empty() {
-            return Err(AppError::ValidationError("OAuth ID is required".into()));
-        }
-        if self.username.trim().len() < 3 {
-            return Err(AppError::ValidationError("Username must not be empty".into()));
+        if self.id_token.trim().is_empty() {
+            return Err(AppError::ValidationError("ID token is required".into()));
         }
-        if !self.email.contains('@') {
-            return Err(AppError::ValidationError("Invalid email format".into()));
+        Ok(())
+    }
+
+    /// Checks the fields a new account needs, `username` falls back to the
+    /// name the provider knows the user by.
+    pub fn check_signup(&self, fallback_username: Option<&str>) -> Result<(String, String), AppError> {
+        let username = self
+            .username
+            .as_deref()
+            .or(fallback_username)
+            .map(|username| username.trim().to_string())
+            .unwrap_or_default();
+        if username.len() < 3 {
+            return Err(AppError::ValidationError("Username must be at least 3 characters".into()));
         }
-        if self.upi_id.trim().len() < 5 {
-            return Err(AppError::ValidationError("UPI ID must Username must not be empty".into()));
+        let upi_id = self.upi_id.as_deref().map(str::trim).unwrap_or_default();
+        if upi_id.len() < 5 {
+            return Err(AppError::ValidationError("UPI ID is required to create an account".into()));
         }
-        Ok(())
+        Ok((username, upi_id.to_string()))
     }
 }
 
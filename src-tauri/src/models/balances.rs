@@ -0,0 +1,162 @@
+use uuid::Uuid;
+use rust_decimal::Decimal;
+use serde::{Serialize, Deserialize};
+use crate::custom_errors::app::AppError;
+use crate::utils::ledger::{Debt, MemberBalance};
+use crate::utils::settlement_planner::SuggestedPayment;
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct GetGroupBalancesReq {
+    pub group_id: Uuid,
+}
+
+impl GetGroupBalancesReq {
+    pub fn check(&self) -> Result<(), AppError> {
+        if self.group_id == Uuid::nil() {
+            return Err(AppError::ValidationError("Group Id cannot be empty".into()));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MemberBalanceRes {
+    pub member_id: Uuid,
+    pub paid: Decimal,
+    pub share: Decimal,
+    pub settled_out: Decimal,
+    pub settled_in: Decimal,
+    pub net: Decimal,
+}
+
+impl From<MemberBalance> for MemberBalanceRes {
+    fn from(balance: MemberBalance) -> Self {
+        Self {
+            member_id: balance.member_id,
+            paid: balance.paid.round_dp(2),
+            share: balance.share.round_dp(2),
+            settled_out: balance.settled_out.round_dp(2),
+            settled_in: balance.settled_in.round_dp(2),
+            net: balance.net.round_dp(2),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DebtRes {
+    pub debtor_id: Uuid,
+    pub creditor_id: Uuid,
+    pub amount: Decimal,
+}
+
+impl From<Debt> for DebtRes {
+    fn from(debt: Debt) -> Self {
+        Self {
+            debtor_id: debt.debtor_id,
+            creditor_id: debt.creditor_id,
+            amount: debt.amount.round_dp(2),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GroupBalancesRes {
+    pub group_id: Uuid,
+    pub balances: Vec<MemberBalanceRes>,
+    pub pairwise: Vec<DebtRes>,
+}
+
+impl GroupBalancesRes {
+    pub fn new(group_id: Uuid, balances: Vec<MemberBalanceRes>, pairwise: Vec<DebtRes>) -> Self {
+        Self {
+            group_id,
+            balances,
+            pairwise,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct GetSettlementPlanReq {
+    pub group_id: Uuid,
+    // overrides the group's `simplify_debts` setting for this request only
+    pub simplify: Option<bool>,
+}
+
+impl GetSettlementPlanReq {
+    pub fn check(&self) -> Result<(), AppError> {
+        if self.group_id == Uuid::nil() {
+            return Err(AppError::ValidationError("Group Id cannot be empty".into()));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SuggestedPaymentRes {
+    pub payer_id: Uuid,
+    pub receiver_id: Uuid,
+    pub amount: Decimal,
+}
+
+impl From<SuggestedPayment> for SuggestedPaymentRes {
+    fn from(payment: SuggestedPayment) -> Self {
+        Self {
+            payer_id: payment.payer_id,
+            receiver_id: payment.receiver_id,
+            amount: payment.amount,
+        }
+    }
+}
+
+impl From<Debt> for SuggestedPaymentRes {
+    fn from(debt: Debt) -> Self {
+        Self {
+            payer_id: debt.debtor_id,
+            receiver_id: debt.creditor_id,
+            amount: debt.amount.round_dp(2),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SettlementPlanRes {
+    pub group_id: Uuid,
+    pub simplified: bool,
+    pub payments: Vec<SuggestedPaymentRes>,
+}
+
+impl SettlementPlanRes {
+    pub fn new(group_id: Uuid, simplified: bool, payments: Vec<SuggestedPaymentRes>) -> Self {
+        Self {
+            group_id,
+            simplified,
+            payments,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FriendGroupBalanceRes {
+    pub group_id: Uuid,
+    // positive when the friend owes you in this group, negative when you owe them
+    pub amount: Decimal,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FriendBalanceRes {
+    pub friend_id: Uuid,
+    pub net: Decimal,
+    pub groups: Vec<FriendGroupBalanceRes>,
+}
+
+impl FriendBalanceRes {
+    pub fn new(friend_id: Uuid, groups: Vec<FriendGroupBalanceRes>) -> Self {
+        let net = groups.iter().map(|group| group.amount).sum();
+        Self {
+            friend_id,
+            net,
+            groups,
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use crate::custom_errors::app::AppError;
+use crate::entities::categories;
+use crate::utils::categorizer::normalize_keywords;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct CreateCategoryReq {
+    pub group_id: Uuid,
+    pub name: String,
+    pub logo: Option<String>,
+    #[serde(default)]
+    pub keywords: Vec<String>,
+}
+
+impl CreateCategoryReq {
+    pub fn check(&mut self) -> Result<(), AppError> {
+        if self.group_id == Uuid::nil() {
+            return Err(AppError::ValidationError("Group Id cannot be empty".into()));
+        }
+        self.name = self.name.trim().to_string();
+        if self.name.is_empty() || self.name.len() > 50 {
+            return Err(AppError::ValidationError(
+                "Category name must be between 1 and 50 characters".into(),
+            ));
+        }
+        if let Some(logo) = &self.logo {
+            if logo.trim().is_empty() {
+                self.logo = None;
+            }
+        }
+        self.keywords = normalize_keywords(&self.keywords);
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct GetCategoriesReq {
+    pub group_id: Uuid,
+}
+
+impl GetCategoriesReq {
+    pub fn check(&self) -> Result<(), AppError> {
+        if self.group_id == Uuid::nil() {
+            return Err(AppError::ValidationError("Group Id cannot be empty".into()));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct DeleteCategoryReq {
+    pub group_id: Uuid,
+    pub category_id: Uuid,
+}
+
+impl DeleteCategoryReq {
+    pub fn check(&self) -> Result<(), AppError> {
+        if self.group_id == Uuid::nil() {
+            return Err(AppError::ValidationError("Group Id cannot be empty".into()));
+        }
+        if self.category_id == Uuid::nil() {
+            return Err(AppError::ValidationError("Category Id cannot be empty".into()));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct SuggestCategoryReq {
+    pub group_id: Uuid,
+    pub description: String,
+}
+
+impl SuggestCategoryReq {
+    pub fn check(&self) -> Result<(), AppError> {
+        if self.group_id == Uuid::nil() {
+            return Err(AppError::ValidationError("Group Id cannot be empty".into()));
+        }
+        if self.description.trim().is_empty() {
+            return Err(AppError::ValidationError("Description cannot be empty".into()));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CategoryRes {
+    pub id: Uuid,
+    pub group_id: Option<Uuid>,
+    pub name: String,
+    pub logo: String,
+    pub keywords: Json,
+    pub built_in: bool,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+impl From<categories::Model> for CategoryRes {
+    fn from(category: categories::Model) -> Self {
+        Self {
+            id: category.id,
+            group_id: category.group_id,
+            built_in: category.group_id.is_none(),
+            name: category.name,
+            logo: category.logo,
+            keywords: category.keywords,
+            created_by: category.created_by,
+            created_at: category.created_at,
+            updated_at: category.updated_at,
+        }
+    }
+}
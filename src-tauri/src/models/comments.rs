@@ -0,0 +1,124 @@
+use crate::custom_errors::app::AppError;
+use crate::entities::activity_comments;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+const MAX_COMMENT_LENGTH: usize = 2000;
+
+fn check_body(body: &str) -> Result<String, AppError> {
+    let body = body.trim();
+    if body.is_empty() || body.chars().count() > MAX_COMMENT_LENGTH {
+        return Err(AppError::ValidationError(format!(
+            "Comment must be between 1 and {} characters",
+            MAX_COMMENT_LENGTH
+        )));
+    }
+    Ok(body.to_string())
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct CreateCommentReq {
+    pub group_id: Uuid,
+    pub activity_id: Uuid,
+    pub body: String,
+}
+
+impl CreateCommentReq {
+    pub fn check(&mut self) -> Result<(), AppError> {
+        if self.group_id == Uuid::nil() {
+            return Err(AppError::ValidationError("Group Id cannot be empty".into()));
+        }
+        if self.activity_id == Uuid::nil() {
+            return Err(AppError::ValidationError("Activity Id cannot be empty".into()));
+        }
+        self.body = check_body(&self.body)?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct GetCommentsReq {
+    pub group_id: Uuid,
+    pub activity_id: Uuid,
+    // zero based, defaults to the first page
+    #[serde(default)]
+    pub page: u64,
+}
+
+impl GetCommentsReq {
+    pub fn check(&self) -> Result<(), AppError> {
+        if self.group_id == Uuid::nil() {
+            return Err(AppError::ValidationError("Group Id cannot be empty".into()));
+        }
+        if self.activity_id == Uuid::nil() {
+            return Err(AppError::ValidationError("Activity Id cannot be empty".into()));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct UpdateCommentReq {
+    pub comment_id: Uuid,
+    pub body: String,
+}
+
+impl UpdateCommentReq {
+    pub fn check(&mut self) -> Result<(), AppError> {
+        if self.comment_id == Uuid::nil() {
+            return Err(AppError::ValidationError("Comment Id cannot be empty".into()));
+        }
+        self.body = check_body(&self.body)?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct DeleteCommentReq {
+    pub comment_id: Uuid,
+}
+
+impl DeleteCommentReq {
+    pub fn check(&self) -> Result<(), AppError> {
+        if self.comment_id == Uuid::nil() {
+            return Err(AppError::ValidationError("Comment Id cannot be empty".into()));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CommentRes {
+    pub id: Uuid,
+    pub activity_id: Uuid,
+    pub group_id: Uuid,
+    pub author_id: Uuid,
+    pub body: String,
+    pub edited_at: Option<DateTimeWithTimeZone>,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+impl From<activity_comments::Model> for CommentRes {
+    fn from(comment: activity_comments::Model) -> Self {
+        Self {
+            id: comment.id,
+            activity_id: comment.activity_id,
+            group_id: comment.group_id,
+            author_id: comment.author_id,
+            body: comment.body,
+            edited_at: comment.edited_at,
+            created_at: comment.created_at,
+            updated_at: comment.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CommentsPageRes {
+    pub comments: Vec<CommentRes>,
+    pub page: u64,
+    pub total_pages: u64,
+    pub total_comments: u64,
+}
@@ -0,0 +1,111 @@
+use crate::custom_errors::app::AppError;
+use crate::entities::digest_preferences;
+use crate::entities::sea_orm_active_enums::{DigestFrequency, PaymentMethod};
+use rust_decimal::Decimal;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct UpdateDigestPreferenceReq {
+    pub frequency: DigestFrequency,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct PreviewDigestReq {
+    // defaults to the caller's own preference
+    pub frequency: Option<DigestFrequency>,
+}
+
+impl PreviewDigestReq {
+    pub fn check(&self) -> Result<(), AppError> {
+        if self.frequency == Some(DigestFrequency::Off) {
+            return Err(AppError::ValidationError("Digest frequency cannot be off".into()));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DigestPreferenceRes {
+    pub frequency: DigestFrequency,
+    pub last_sent_at: Option<DateTimeWithTimeZone>,
+}
+
+impl From<digest_preferences::Model> for DigestPreferenceRes {
+    fn from(preference: digest_preferences::Model) -> Self {
+        Self {
+            frequency: preference.frequency,
+            last_sent_at: preference.last_sent_at,
+        }
+    }
+}
+
+impl Default for DigestPreferenceRes {
+    fn default() -> Self {
+        Self {
+            frequency: DigestFrequency::Off,
+            last_sent_at: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DigestExpenseRes {
+    pub activity_id: Uuid,
+    pub group_id: Uuid,
+    pub group_name: String,
+    pub description: String,
+    pub amount: Decimal,
+    pub currency: String,
+    pub paid_by_id: Uuid,
+    pub paid_by_name: String,
+    // in the expense's own currency, zero when the user is not part of the split
+    pub your_share: Decimal,
+    pub time: DateTimeWithTimeZone,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DigestGroupRes {
+    pub group_id: Uuid,
+    pub group_name: String,
+    pub currency: String,
+    // positive when the group owes the user, negative when the user owes the group
+    pub net: Decimal,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DigestSettlementRes {
+    pub id: Uuid,
+    pub group_id: Uuid,
+    pub group_name: String,
+    pub payer_id: Uuid,
+    pub receiver_id: Uuid,
+    pub counterparty_name: String,
+    pub amount: Decimal,
+    pub method: PaymentMethod,
+    // the user is the receiver and has to confirm or reject it
+    pub awaiting_you: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DigestRes {
+    pub user_id: Uuid,
+    pub username: String,
+    pub email: String,
+    pub frequency: DigestFrequency,
+    pub period_start: DateTimeWithTimeZone,
+    pub period_end: DateTimeWithTimeZone,
+    pub new_expenses: Vec<DigestExpenseRes>,
+    pub groups: Vec<DigestGroupRes>,
+    pub pending_settlements: Vec<DigestSettlementRes>,
+}
+
+impl DigestRes {
+    /// Nothing happened in the period and nothing is outstanding.
+    pub fn is_empty(&self) -> bool {
+        self.new_expenses.is_empty()
+            && self.pending_settlements.is_empty()
+            && self.groups.iter().all(|group| group.net.is_zero())
+    }
+}
@@ -0,0 +1,184 @@
+use crate::custom_errors::app::AppError;
+use crate::entities::{friend_collections, users};
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// The user to befriend, by id, email or username. Exactly one has to be given.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct SendFriendRequestReq {
+    pub friend_id: Option<Uuid>,
+    pub email: Option<String>,
+    pub username: Option<String>,
+}
+
+impl SendFriendRequestReq {
+    pub fn check(&mut self) -> Result<(), AppError> {
+        self.email = self
+            .email
+            .take()
+            .map(|email| email.trim().to_lowercase())
+            .filter(|email| !email.is_empty());
+        self.username = self
+            .username
+            .take()
+            .map(|username| username.trim().to_string())
+            .filter(|username| !username.is_empty());
+        if self.friend_id == Some(Uuid::nil()) {
+            return Err(AppError::ValidationError("Friend Id cannot be empty".into()));
+        }
+
+        let given = [self.friend_id.is_some(), self.email.is_some(), self.username.is_some()]
+            .iter()
+            .filter(|given| **given)
+            .count();
+        if given != 1 {
+            return Err(AppError::ValidationError(
+                "Provide exactly one of friend id, email or username".into(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct RespondFriendRequestReq {
+    // the user who sent the request
+    pub friend_id: Uuid,
+    pub accept: bool,
+}
+
+impl RespondFriendRequestReq {
+    pub fn check(&self) -> Result<(), AppError> {
+        if self.friend_id == Uuid::nil() {
+            return Err(AppError::ValidationError("Friend Id cannot be empty".into()));
+        }
+        Ok(())
+    }
+}
+
+/// Used to unfriend, cancel a request, block and unblock.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct FriendIdReq {
+    pub friend_id: Uuid,
+}
+
+impl FriendIdReq {
+    pub fn check(&self) -> Result<(), AppError> {
+        if self.friend_id == Uuid::nil() {
+            return Err(AppError::ValidationError("Friend Id cannot be empty".into()));
+        }
+        Ok(())
+    }
+}
+
+/// An accepted friend, with the contact details needed to settle up.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FriendRes {
+    pub user_id: Uuid,
+    pub username: String,
+    pub email: String,
+    pub upi_id: String,
+    // when the friendship was last changed
+    pub since: DateTimeWithTimeZone,
+}
+
+impl From<(friend_collections::Model, users::Model)> for FriendRes {
+    fn from((relation, user): (friend_collections::Model, users::Model)) -> Self {
+        Self {
+            user_id: user.id,
+            username: user.username,
+            email: user.email,
+            upi_id: user.upi_id,
+            since: relation.updated_at,
+        }
+    }
+}
+
+/// Someone the caller is not friends with (yet): a request either way or a
+/// block. Contact details stay hidden until a request is accepted.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FriendRequestRes {
+    pub user_id: Uuid,
+    pub username: String,
+    // when the request or block was last changed
+    pub since: DateTimeWithTimeZone,
+}
+
+impl From<(friend_collections::Model, users::Model)> for FriendRequestRes {
+    fn from((relation, user): (friend_collections::Model, users::Model)) -> Self {
+        Self {
+            user_id: user.id,
+            username: user.username,
+            since: relation.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FriendsRes {
+    pub friends: Vec<FriendRes>,
+    pub incoming_requests: Vec<FriendRequestRes>,
+    pub outgoing_requests: Vec<FriendRequestRes>,
+    pub blocked: Vec<FriendRequestRes>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::sea_orm_active_enums::FriendStatus;
+    use chrono::Utc;
+
+    fn pair(status: FriendStatus) -> (friend_collections::Model, users::Model) {
+        let now = Utc::now().into();
+        let user = users::Model {
+            id: Uuid::new_v4(),
+            oauth_provider: "google".into(),
+            oauth_id: "1234".into(),
+            username: "asha".into(),
+            email: "asha@example.com".into(),
+            upi_id: "asha@okbank".into(),
+            created_at: now,
+            updated_at: now,
+        };
+        let relation = friend_collections::Model {
+            id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            friend_id: user.id,
+            status,
+            created_at: now,
+            updated_at: now,
+        };
+        (relation, user)
+    }
+
+    #[test]
+    fn contact_details_are_only_shared_with_friends() {
+        let friend = serde_json::to_value(FriendRes::from(pair(FriendStatus::Accepted))).unwrap();
+        assert_eq!(friend["email"], "asha@example.com");
+        assert_eq!(friend["upi_id"], "asha@okbank");
+
+        let request = serde_json::to_value(FriendRequestRes::from(pair(FriendStatus::Pending))).unwrap();
+        assert_eq!(request["username"], "asha");
+        assert!(request.get("email").is_none());
+        assert!(request.get("upi_id").is_none());
+    }
+
+    #[test]
+    fn a_request_names_exactly_one_user() {
+        let request = |friend_id, email: Option<&str>, username: Option<&str>| SendFriendRequestReq {
+            friend_id,
+            email: email.map(String::from),
+            username: username.map(String::from),
+        };
+
+        let mut by_email = request(None, Some(" Asha@Example.com "), Some("  "));
+        assert!(by_email.check().is_ok());
+        assert_eq!(by_email.email.as_deref(), Some("asha@example.com"));
+        assert_eq!(by_email.username, None);
+
+        assert!(request(None, None, None).check().is_err());
+        assert!(request(Some(Uuid::new_v4()), None, Some("asha")).check().is_err());
+        assert!(request(Some(Uuid::nil()), None, None).check().is_err());
+    }
+}
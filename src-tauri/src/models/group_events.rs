@@ -0,0 +1,23 @@
+use crate::custom_errors::app::AppError;
+use serde::Deserialize;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct StreamEventsReq {
+    // only this group's events, otherwise every group the user is in plus their own notifications
+    pub group_id: Option<Uuid>,
+    // for clients that cannot send the Last-Event-ID header, the header wins when both are set
+    pub last_event_id: Option<i64>,
+}
+
+impl StreamEventsReq {
+    pub fn check(&self) -> Result<(), AppError> {
+        if self.group_id == Some(Uuid::nil()) {
+            return Err(AppError::ValidationError("Group Id cannot be empty".into()));
+        }
+        if matches!(self.last_event_id, Some(id) if id < 0) {
+            return Err(AppError::ValidationError("Last event id cannot be negative".into()));
+        }
+        Ok(())
+    }
+}
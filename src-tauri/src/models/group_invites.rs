@@ -0,0 +1,143 @@
+use crate::custom_errors::app::AppError;
+use crate::entities::group_invites;
+use crate::utils::invites::normalize_join_code;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+const MAX_INVITE_TTL_HOURS: i64 = 90 * 24;
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct CreateInviteReq {
+    pub group_id: Uuid,
+    // defaults to GROUP_INVITE_TTL_HOURS
+    pub expires_in_hours: Option<i64>,
+    // unlimited when not set
+    pub max_uses: Option<i32>,
+}
+
+impl CreateInviteReq {
+    pub fn check(&self) -> Result<(), AppError> {
+        if self.group_id == Uuid::nil() {
+            return Err(AppError::ValidationError("Group Id cannot be empty".into()));
+        }
+        if let Some(hours) = self.expires_in_hours {
+            if hours <= 0 || hours > MAX_INVITE_TTL_HOURS {
+                return Err(AppError::ValidationError(format!(
+                    "Invites must expire within 1 to {} hours",
+                    MAX_INVITE_TTL_HOURS
+                )));
+            }
+        }
+        if let Some(max_uses) = self.max_uses {
+            if max_uses <= 0 {
+                return Err(AppError::ValidationError("Max uses must be positive".into()));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct GetInvitesReq {
+    pub group_id: Uuid,
+}
+
+impl GetInvitesReq {
+    pub fn check(&self) -> Result<(), AppError> {
+        if self.group_id == Uuid::nil() {
+            return Err(AppError::ValidationError("Group Id cannot be empty".into()));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct RevokeInviteReq {
+    pub invite_id: Uuid,
+}
+
+impl RevokeInviteReq {
+    pub fn check(&self) -> Result<(), AppError> {
+        if self.invite_id == Uuid::nil() {
+            return Err(AppError::ValidationError("Invite Id cannot be empty".into()));
+        }
+        Ok(())
+    }
+}
+
+/// Either an invite token or a group's join code, not both.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct JoinGroupReq {
+    pub token: Option<String>,
+    pub join_code: Option<String>,
+}
+
+impl JoinGroupReq {
+    pub fn check(&mut self) -> Result<(), AppError> {
+        self.token = self
+            .token
+            .take()
+            .map(|token| token.trim().to_string())
+            .filter(|token| !token.is_empty());
+        self.join_code = self
+            .join_code
+            .take()
+            .map(|code| normalize_join_code(&code))
+            .filter(|code| !code.is_empty());
+
+        if self.token.is_some() == self.join_code.is_some() {
+            return Err(AppError::ValidationError(
+                "Provide either an invite token or a join code".into(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct JoinCodeReq {
+    pub group_id: Uuid,
+}
+
+impl JoinCodeReq {
+    pub fn check(&self) -> Result<(), AppError> {
+        if self.group_id == Uuid::nil() {
+            return Err(AppError::ValidationError("Group Id cannot be empty".into()));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct InviteRes {
+    pub id: Uuid,
+    pub group_id: Uuid,
+    pub token: String,
+    pub created_by: Uuid,
+    pub expires_at: DateTimeWithTimeZone,
+    pub max_uses: Option<i32>,
+    pub use_count: i32,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+impl From<group_invites::Model> for InviteRes {
+    fn from(invite: group_invites::Model) -> Self {
+        Self {
+            id: invite.id,
+            group_id: invite.group_id,
+            token: invite.token,
+            created_by: invite.created_by,
+            expires_at: invite.expires_at,
+            max_uses: invite.max_uses,
+            use_count: invite.use_count,
+            created_at: invite.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct JoinCodeRes {
+    pub group_id: Uuid,
+    pub join_code: String,
+}
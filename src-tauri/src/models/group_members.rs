@@ -3,6 +3,7 @@ use uuid::Uuid;
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 use crate::{custom_errors::app::AppError, entities::group_members};
+use crate::entities::sea_orm_active_enums::GroupRole;
 
 #[derive(Debug, Clone, PartialEq, Deserialize)]
 pub struct AddGroupMemberReq {
@@ -34,6 +35,7 @@ pub struct GroupMember {
     pub id: Uuid,
     pub group_id: Uuid,
     pub member_id: Uuid,
+    pub role: GroupRole,
     pub joined_at: DateTimeWithTimeZone,
 }
 
@@ -42,12 +44,14 @@ impl GroupMember {
         id: Uuid,
         group_id: Uuid,
         member_id: Uuid,
+        role: GroupRole,
         joined_at: DateTimeWithTimeZone,
     ) -> Self {
         Self {
             id,
             group_id,
             member_id,
+            role,
             joined_at,
         }
     }
@@ -55,7 +59,7 @@ impl GroupMember {
 
 impl From<crate::entities::group_members::Model> for GroupMember {
     fn from(model: crate::entities::group_members::Model) -> Self {
-        Self::new(model.id, model.group_id, model.member_id, model.joined_at)
+        Self::new(model.id, model.group_id, model.member_id, model.role, model.joined_at)
     }
 }
 
@@ -94,3 +98,45 @@ impl RemoveGroupMemberReq{
         Ok(())
     }
 }
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct UpdateMemberRoleReq {
+    pub group_id: Uuid,
+    pub member_id: Uuid,
+    pub role: GroupRole,
+}
+
+impl UpdateMemberRoleReq {
+    pub fn check(&self) -> Result<(), AppError> {
+        if self.group_id == Uuid::nil() {
+            return Err(AppError::ValidationError("Group Id cannot be empty".into()));
+        }
+        if self.member_id == Uuid::nil() {
+            return Err(AppError::ValidationError("Member Id cannot be empty".into()));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roles_grant_what_they_should() {
+        assert!(GroupRole::Owner.is_admin() && GroupRole::Owner.can_write());
+        assert!(GroupRole::Admin.is_admin() && GroupRole::Admin.can_write());
+        assert!(!GroupRole::Member.is_admin() && GroupRole::Member.can_write());
+        assert!(!GroupRole::Viewer.is_admin() && !GroupRole::Viewer.can_write());
+    }
+
+    #[test]
+    fn role_changes_need_a_group_and_a_member() {
+        let (group_id, member_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let change = |group_id, member_id| UpdateMemberRoleReq { group_id, member_id, role: GroupRole::Viewer };
+
+        assert!(change(group_id, member_id).check().is_ok());
+        assert!(change(Uuid::nil(), member_id).check().is_err());
+        assert!(change(group_id, Uuid::nil()).check().is_err());
+    }
+}
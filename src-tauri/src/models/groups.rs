@@ -2,15 +2,18 @@ use uuid::Uuid;
 use sea_orm::entity::prelude::*;
 use serde::{Serialize, Deserialize};
 use crate::custom_errors::app::AppError;
+use crate::utils::exchange_rates::normalize_currency;
 #[derive(Debug, Clone, PartialEq, Deserialize)]
 pub struct CreateGroupReq {
     pub group_name: String,
     pub auto_logo: Option<String>,
+    // base currency for balances, defaults to INR
+    pub currency: Option<String>,
 }
 
 impl CreateGroupReq {
-    pub fn new(group_name: String, auto_logo: Option<String>) -> Self {
-        Self { group_name, auto_logo }
+    pub fn new(group_name: String, auto_logo: Option<String>, currency: Option<String>) -> Self {
+        Self { group_name, auto_logo, currency }
     }
 
     pub fn check(&mut self) -> Result<(), AppError> {
@@ -22,6 +25,9 @@ impl CreateGroupReq {
                 self.auto_logo = None;
             }
         }
+        if let Some(currency) = &self.currency {
+            self.currency = Some(normalize_currency(currency)?);
+        }
         Ok(())
     }
 }
@@ -33,6 +39,9 @@ pub struct GroupRes {
     pub group_name: String,
     pub auto_logo: Option<String>,
     pub total_expense: Decimal,
+    pub simplify_debts: bool,
+    pub currency: String,
+    pub archived_at: Option<DateTimeWithTimeZone>,
     pub created_at: DateTimeWithTimeZone,
     pub updated_at: DateTimeWithTimeZone,
     pub admin_id: Uuid,
@@ -47,6 +56,9 @@ impl GroupRes{
         group_name: String,
         auto_logo: Option<String>,
         total_expense: Decimal,
+        simplify_debts: bool,
+        currency: String,
+        archived_at: Option<DateTimeWithTimeZone>,
         created_at: DateTimeWithTimeZone,
         updated_at: DateTimeWithTimeZone,
         admin_id: Uuid,
@@ -58,6 +70,9 @@ impl GroupRes{
             group_name,
             auto_logo,
             total_expense,
+            simplify_debts,
+            currency,
+            archived_at,
             created_at,
             updated_at,
             admin_id,
@@ -74,10 +89,69 @@ impl From<(crate::entities::groups::Model, crate::entities::group_members::Model
             group_name: group.group_name,
             auto_logo: group.auto_logo,
             total_expense: group.total_expense,
+            simplify_debts: group.simplify_debts,
+            currency: group.currency,
+            archived_at: group.archived_at,
             created_at: group.created_at,
             updated_at: group.updated_at,
             admin_id: admin.id,
             joined_at: admin.joined_at,
         }
     }
-}
\ No newline at end of file
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct UpdateGroupSettingsReq {
+    pub group_id: Uuid,
+    pub simplify_debts: Option<bool>,
+}
+
+impl UpdateGroupSettingsReq {
+    pub fn check(&self) -> Result<(), AppError> {
+        if self.group_id == Uuid::nil() {
+            return Err(AppError::ValidationError("Group Id cannot be empty".into()));
+        }
+        if self.simplify_debts.is_none() {
+            return Err(AppError::ValidationError("No settings provided".into()));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct GetGroupsReq {
+    // archived groups are left out unless asked for
+    #[serde(default)]
+    pub include_archived: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ArchiveGroupReq {
+    pub group_id: Uuid,
+}
+
+impl ArchiveGroupReq {
+    pub fn check(&self) -> Result<(), AppError> {
+        if self.group_id == Uuid::nil() {
+            return Err(AppError::ValidationError("Group Id cannot be empty".into()));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct DeleteGroupReq {
+    pub group_id: Uuid,
+    // deletes the group even though members still owe each other money
+    #[serde(default)]
+    pub force: bool,
+}
+
+impl DeleteGroupReq {
+    pub fn check(&self) -> Result<(), AppError> {
+        if self.group_id == Uuid::nil() {
+            return Err(AppError::ValidationError("Group Id cannot be empty".into()));
+        }
+        Ok(())
+    }
+}
@@ -1,4 +1,16 @@
 pub mod auth;
 pub mod activities;
 pub mod groups;
-pub mod group_members;
\ No newline at end of file
+pub mod group_members;
+pub mod balances;
+pub mod settlements;
+pub mod recurring_expenses;
+pub mod attachments;
+pub mod categories;
+pub mod comments;
+pub mod activity_history;
+pub mod group_invites;
+pub mod friends;
+pub mod notifications;
+pub mod group_events;
+pub mod digests;
\ No newline at end of file
@@ -0,0 +1,83 @@
+use crate::custom_errors::app::AppError;
+use crate::entities::notifications;
+use crate::entities::sea_orm_active_enums::NotificationType;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct GetNotificationsReq {
+    // zero based, defaults to the first page
+    #[serde(default)]
+    pub page: u64,
+    #[serde(default)]
+    pub unread_only: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct MarkNotificationsReadReq {
+    pub notification_ids: Vec<Uuid>,
+}
+
+impl MarkNotificationsReadReq {
+    pub fn check(&self) -> Result<(), AppError> {
+        if self.notification_ids.is_empty() {
+            return Err(AppError::ValidationError("Notification Ids cannot be empty".into()));
+        }
+        if self.notification_ids.contains(&Uuid::nil()) {
+            return Err(AppError::ValidationError("Notification Id cannot be empty".into()));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct DeleteNotificationReq {
+    pub notification_id: Uuid,
+}
+
+impl DeleteNotificationReq {
+    pub fn check(&self) -> Result<(), AppError> {
+        if self.notification_id == Uuid::nil() {
+            return Err(AppError::ValidationError("Notification Id cannot be empty".into()));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct NotificationRes {
+    pub id: Uuid,
+    #[serde(rename = "type")]
+    pub type_: NotificationType,
+    pub message: String,
+    pub read: bool,
+    pub actor_id: Option<Uuid>,
+    pub group_id: Option<Uuid>,
+    pub activity_id: Option<Uuid>,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+impl From<notifications::Model> for NotificationRes {
+    fn from(notification: notifications::Model) -> Self {
+        Self {
+            id: notification.id,
+            type_: notification.type_,
+            message: notification.message,
+            read: notification.read,
+            actor_id: notification.actor_id,
+            group_id: notification.group_id,
+            activity_id: notification.activity_id,
+            created_at: notification.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct NotificationsPageRes {
+    pub notifications: Vec<NotificationRes>,
+    pub page: u64,
+    pub total_pages: u64,
+    pub total_notifications: u64,
+    pub unread_count: u64,
+}
@@ -0,0 +1,217 @@
+use crate::custom_errors::app::AppError;
+use crate::entities::recurring_expenses;
+use crate::entities::sea_orm_active_enums::{RecurrenceFrequency, SplitType};
+use crate::models::activities::CreateActivityReq;
+use crate::utils::recurrence::parse_cron;
+use rust_decimal::Decimal;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct CreateRecurringExpenseReq {
+    // the expense every occurrence is created from, validated like a one-off activity
+    #[serde(flatten)]
+    pub expense: CreateActivityReq,
+    pub frequency: RecurrenceFrequency,
+    pub cron_expression: Option<String>,
+    // defaults to now
+    pub starts_at: Option<DateTimeWithTimeZone>,
+    pub ends_at: Option<DateTimeWithTimeZone>,
+}
+
+impl CreateRecurringExpenseReq {
+    pub fn check(&mut self) -> Result<(), AppError> {
+        if self.expense.split_type == SplitType::Itemized {
+            return Err(AppError::ValidationError(
+                "Itemized expenses cannot be made recurring".into(),
+            ));
+        }
+        self.expense.check()?;
+
+        if let Some(expression) = &self.cron_expression {
+            if expression.trim().is_empty() {
+                self.cron_expression = None;
+            }
+        }
+        match (&self.frequency, &self.cron_expression) {
+            (RecurrenceFrequency::Cron, Some(expression)) => {
+                parse_cron(expression)?;
+            }
+            (RecurrenceFrequency::Cron, None) => {
+                return Err(AppError::ValidationError(
+                    "Cron expression is required for cron schedules".into(),
+                ));
+            }
+            (_, Some(_)) => {
+                return Err(AppError::ValidationError(
+                    "Cron expression is only valid for cron schedules".into(),
+                ));
+            }
+            (_, None) => {}
+        }
+
+        if let (Some(starts_at), Some(ends_at)) = (&self.starts_at, &self.ends_at) {
+            if ends_at < starts_at {
+                return Err(AppError::ValidationError(
+                    "End date cannot be before the start date".into(),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct GetRecurringExpensesReq {
+    pub group_id: Uuid,
+}
+
+impl GetRecurringExpensesReq {
+    pub fn check(&self) -> Result<(), AppError> {
+        if self.group_id == Uuid::nil() {
+            return Err(AppError::ValidationError("Group Id cannot be empty".into()));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct UpdateRecurringExpenseReq {
+    pub id: Uuid,
+    pub group_id: Uuid,
+    pub description: Option<String>,
+    #[serde(default, deserialize_with = "crate::utils::double_option::deserialize")]
+    pub ends_at: Option<Option<DateTimeWithTimeZone>>, // Double Option to handle removing the end date
+    // pausing skips occurrences until the expense is resumed
+    pub is_active: Option<bool>,
+}
+
+impl UpdateRecurringExpenseReq {
+    pub fn check(&self) -> Result<(), AppError> {
+        if self.id == Uuid::nil() {
+            return Err(AppError::ValidationError("Recurring expense Id cannot be empty".into()));
+        }
+        if self.group_id == Uuid::nil() {
+            return Err(AppError::ValidationError("Group Id cannot be empty".into()));
+        }
+        if let Some(description) = &self.description {
+            if description.trim().is_empty() || description.len() > 255 {
+                return Err(AppError::ValidationError(
+                    "Description must be between 1 and 255 characters".into(),
+                ));
+            }
+        }
+        if self.description.is_none() && self.ends_at.is_none() && self.is_active.is_none() {
+            return Err(AppError::ValidationError("No changes provided".into()));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct DeleteRecurringExpenseReq {
+    pub id: Uuid,
+    pub group_id: Uuid,
+}
+
+impl DeleteRecurringExpenseReq {
+    pub fn check(&self) -> Result<(), AppError> {
+        if self.id == Uuid::nil() {
+            return Err(AppError::ValidationError("Recurring expense Id cannot be empty".into()));
+        }
+        if self.group_id == Uuid::nil() {
+            return Err(AppError::ValidationError("Group Id cannot be empty".into()));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RecurringExpenseRes {
+    pub id: Uuid,
+    pub group_id: Uuid,
+    pub creator_id: Uuid,
+    pub description: String,
+    pub amount: Decimal,
+    pub currency: String,
+    pub payer_ids: Json,
+    pub payer_amounts: Json,
+    pub split_members: Json,
+    pub split_amounts: Json,
+    pub split_type: SplitType,
+    pub split_values: Option<Json>,
+    pub category_id: Option<Uuid>,
+    pub expense_logo: Option<String>,
+    pub frequency: RecurrenceFrequency,
+    pub cron_expression: Option<String>,
+    pub starts_at: DateTimeWithTimeZone,
+    pub ends_at: Option<DateTimeWithTimeZone>,
+    pub next_occurrence_at: Option<DateTimeWithTimeZone>,
+    pub is_active: bool,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+impl From<recurring_expenses::Model> for RecurringExpenseRes {
+    fn from(template: recurring_expenses::Model) -> Self {
+        Self {
+            id: template.id,
+            group_id: template.group_id,
+            creator_id: template.creator_id,
+            description: template.description,
+            amount: template.amount,
+            currency: template.currency,
+            payer_ids: template.payer_ids,
+            payer_amounts: template.payer_amounts,
+            split_members: template.split_members,
+            split_amounts: template.split_amounts,
+            split_type: template.split_type,
+            split_values: template.split_values,
+            category_id: template.category_id,
+            expense_logo: template.expense_logo,
+            frequency: template.frequency,
+            cron_expression: template.cron_expression,
+            starts_at: template.starts_at,
+            ends_at: template.ends_at,
+            next_occurrence_at: template.next_occurrence_at,
+            is_active: template.is_active,
+            created_at: template.created_at,
+            updated_at: template.updated_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn update(body: serde_json::Value) -> UpdateRecurringExpenseReq {
+        serde_json::from_value(body).unwrap()
+    }
+
+    #[test]
+    fn ends_at_tells_a_missing_field_from_null() {
+        let (id, group_id) = (Uuid::new_v4(), Uuid::new_v4());
+
+        let untouched = update(json!({ "id": id, "group_id": group_id, "is_active": false }));
+        assert_eq!(untouched.ends_at, None);
+
+        let cleared = update(json!({ "id": id, "group_id": group_id, "ends_at": null }));
+        assert_eq!(cleared.ends_at, Some(None));
+        assert!(cleared.check().is_ok());
+
+        let set = update(json!({ "id": id, "group_id": group_id, "ends_at": "2025-06-30T00:00:00+05:30" }));
+        assert_eq!(
+            set.ends_at,
+            Some(Some("2025-06-30T00:00:00+05:30".parse().unwrap()))
+        );
+    }
+
+    #[test]
+    fn an_update_has_to_change_something() {
+        let nothing = update(json!({ "id": Uuid::new_v4(), "group_id": Uuid::new_v4() }));
+        assert!(matches!(nothing.check(), Err(AppError::ValidationError(_))));
+    }
+}
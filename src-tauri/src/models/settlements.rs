@@ -0,0 +1,311 @@
+use uuid::Uuid;
+use rust_decimal::Decimal;
+use sea_orm::entity::prelude::*;
+use serde::{Serialize, Deserialize};
+use crate::custom_errors::app::AppError;
+use crate::entities::sea_orm_active_enums::{PaymentMethod, TransactionStatus, UpiPaymentStatus};
+use crate::entities::{cash_transactions, transactions, upi_payments};
+use crate::utils::upi::is_valid_vpa;
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct CreateSettlementReq {
+    pub group_id: Uuid,
+    pub receiver_id: Uuid,
+    pub amount: Decimal,
+    pub method: PaymentMethod,
+    pub activity_id: Option<Uuid>,
+    // defaults to the receiver's registered UPI id
+    pub upi_id: Option<String>,
+}
+
+impl CreateSettlementReq {
+    pub fn check(&mut self) -> Result<(), AppError> {
+        if self.group_id == Uuid::nil() {
+            return Err(AppError::ValidationError("Group Id cannot be empty".into()));
+        }
+        if self.receiver_id == Uuid::nil() {
+            return Err(AppError::ValidationError("Receiver Id cannot be empty".into()));
+        }
+        if self.amount <= Decimal::ZERO {
+            return Err(AppError::ValidationError("Amount must be greater than zero".into()));
+        }
+        if self.amount.scale() > 2 {
+            return Err(AppError::ValidationError("Amount cannot have more than two decimal places".into()));
+        }
+        if let Some(upi_id) = &self.upi_id {
+            if upi_id.trim().is_empty() {
+                self.upi_id = None;
+            }
+        }
+        if self.method == PaymentMethod::Cash && self.upi_id.is_some() {
+            return Err(AppError::ValidationError("UPI id is only valid for UPI settlements".into()));
+        }
+        if let Some(upi_id) = &self.upi_id {
+            if !is_valid_vpa(upi_id) {
+                return Err(AppError::ValidationError("UPI id is not a valid VPA".into()));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct GetSettlementsReq {
+    pub group_id: Uuid,
+}
+
+impl GetSettlementsReq {
+    pub fn check(&self) -> Result<(), AppError> {
+        if self.group_id == Uuid::nil() {
+            return Err(AppError::ValidationError("Group Id cannot be empty".into()));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SettlementRes {
+    pub id: Uuid,
+    pub group_id: Uuid,
+    pub payer_id: Uuid,
+    pub receiver_id: Uuid,
+    pub amount: Decimal,
+    pub method: PaymentMethod,
+    pub status: TransactionStatus,
+    pub activity_id: Option<Uuid>,
+    pub batch_id: Option<Uuid>,
+    pub time: DateTimeWithTimeZone,
+    pub upi_payment: Option<upi_payments::Model>,
+    pub cash_transaction: Option<cash_transactions::Model>,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+impl SettlementRes {
+    pub fn new(
+        transaction: transactions::Model,
+        upi_payment: Option<upi_payments::Model>,
+        cash_transaction: Option<cash_transactions::Model>,
+    ) -> Self {
+        Self {
+            id: transaction.id,
+            group_id: transaction.group_id,
+            payer_id: transaction.payer_id,
+            receiver_id: transaction.receiver_id,
+            amount: transaction.amount,
+            method: transaction.method,
+            status: transaction.status,
+            activity_id: transaction.activity_id,
+            batch_id: transaction.batch_id,
+            time: transaction.time,
+            upi_payment,
+            cash_transaction,
+            created_at: transaction.created_at,
+            updated_at: transaction.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct CreateUpiIntentReq {
+    pub group_id: Uuid,
+    pub receiver_id: Uuid,
+    pub amount: Decimal,
+}
+
+impl CreateUpiIntentReq {
+    pub fn check(&self) -> Result<(), AppError> {
+        if self.group_id == Uuid::nil() {
+            return Err(AppError::ValidationError("Group Id cannot be empty".into()));
+        }
+        if self.receiver_id == Uuid::nil() {
+            return Err(AppError::ValidationError("Receiver Id cannot be empty".into()));
+        }
+        if self.amount <= Decimal::ZERO {
+            return Err(AppError::ValidationError("Amount must be greater than zero".into()));
+        }
+        if self.amount.scale() > 2 {
+            return Err(AppError::ValidationError("Amount cannot have more than two decimal places".into()));
+        }
+        Ok(())
+    }
+}
+
+// an intent is a UPI settlement paid to the receiver's registered UPI id
+impl From<CreateUpiIntentReq> for CreateSettlementReq {
+    fn from(intent: CreateUpiIntentReq) -> Self {
+        Self {
+            group_id: intent.group_id,
+            receiver_id: intent.receiver_id,
+            amount: intent.amount,
+            method: PaymentMethod::Upi,
+            activity_id: None,
+            upi_id: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UpiIntentRes {
+    pub settlement: SettlementRes,
+    pub upi_uri: String,
+    pub qr_svg: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QrFormat {
+    #[default]
+    Png,
+    Svg,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct GetUpiQrReq {
+    pub transaction_id: Uuid,
+    #[serde(default)]
+    pub format: QrFormat,
+}
+
+impl GetUpiQrReq {
+    pub fn check(&self) -> Result<(), AppError> {
+        if self.transaction_id == Uuid::nil() {
+            return Err(AppError::ValidationError("Transaction Id cannot be empty".into()));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct RefreshUpiStatusReq {
+    pub transaction_id: Uuid,
+}
+
+impl RefreshUpiStatusReq {
+    pub fn check(&self) -> Result<(), AppError> {
+        if self.transaction_id == Uuid::nil() {
+            return Err(AppError::ValidationError("Transaction Id cannot be empty".into()));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct UpiCallbackReq {
+    pub transaction_ref: String,
+    pub status: UpiPaymentStatus,
+}
+
+impl UpiCallbackReq {
+    pub fn check(&self) -> Result<(), AppError> {
+        if self.transaction_ref.trim().is_empty() {
+            return Err(AppError::ValidationError("Transaction reference cannot be empty".into()));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ConfirmCashSettlementReq {
+    pub transaction_id: Uuid,
+}
+
+impl ConfirmCashSettlementReq {
+    pub fn check(&self) -> Result<(), AppError> {
+        if self.transaction_id == Uuid::nil() {
+            return Err(AppError::ValidationError("Transaction Id cannot be empty".into()));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct DisputeCashSettlementReq {
+    pub transaction_id: Uuid,
+    pub reason: Option<String>,
+}
+
+impl DisputeCashSettlementReq {
+    pub fn check(&mut self) -> Result<(), AppError> {
+        if self.transaction_id == Uuid::nil() {
+            return Err(AppError::ValidationError("Transaction Id cannot be empty".into()));
+        }
+        if let Some(reason) = &self.reason {
+            if reason.trim().is_empty() {
+                self.reason = None;
+            } else if reason.len() > 255 {
+                return Err(AppError::ValidationError("Reason must be at most 255 characters".into()));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct SettleAllReq {
+    pub friend_id: Uuid,
+}
+
+impl SettleAllReq {
+    pub fn check(&self) -> Result<(), AppError> {
+        if self.friend_id == Uuid::nil() {
+            return Err(AppError::ValidationError("Friend Id cannot be empty".into()));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settlement(method: PaymentMethod, upi_id: Option<&str>) -> CreateSettlementReq {
+        CreateSettlementReq {
+            group_id: Uuid::new_v4(),
+            receiver_id: Uuid::new_v4(),
+            amount: Decimal::new(25000, 2),
+            method,
+            activity_id: None,
+            upi_id: upi_id.map(String::from),
+        }
+    }
+
+    #[test]
+    fn upi_ids_must_be_valid_vpas() {
+        assert!(settlement(PaymentMethod::Upi, Some("asha@okbank")).check().is_ok());
+        assert!(matches!(
+            settlement(PaymentMethod::Upi, Some("asha okbank")).check(),
+            Err(AppError::ValidationError(_))
+        ));
+        assert!(matches!(
+            settlement(PaymentMethod::Upi, Some("upi://pay?pa=asha@okbank")).check(),
+            Err(AppError::ValidationError(_))
+        ));
+    }
+
+    #[test]
+    fn blank_upi_ids_fall_back_to_the_receiver() {
+        let mut blank = settlement(PaymentMethod::Upi, Some("  "));
+        blank.check().unwrap();
+        assert_eq!(blank.upi_id, None);
+
+        assert!(matches!(
+            settlement(PaymentMethod::Cash, Some("asha@okbank")).check(),
+            Err(AppError::ValidationError(_))
+        ));
+    }
+
+    #[test]
+    fn intents_are_upi_settlements_to_the_registered_id() {
+        let intent = CreateUpiIntentReq {
+            group_id: Uuid::new_v4(),
+            receiver_id: Uuid::new_v4(),
+            amount: Decimal::new(999, 2),
+        };
+        let settlement = CreateSettlementReq::from(intent.clone());
+        assert_eq!(settlement.method, PaymentMethod::Upi);
+        assert_eq!(settlement.receiver_id, intent.receiver_id);
+        assert_eq!(settlement.amount, intent.amount);
+        assert_eq!(settlement.upi_id, None);
+    }
+}
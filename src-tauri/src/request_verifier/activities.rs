@@ -12,6 +12,8 @@ pub async fn check_activity_exists_in_group (
     let activity = activities::Entity::find()
         .filter(activities::Column::Id.eq(activity_id))
         .filter(activities::Column::GroupId.eq(group_id))
+        // activities in the trash can only be restored
+        .filter(activities::Column::DeletedAt.is_null())
         .one(db)
         .await
         .map_err(|_| AppError::InternalServerError)?;
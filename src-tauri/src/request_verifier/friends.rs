@@ -0,0 +1,27 @@
+use sea_orm::{EntityTrait, ColumnTrait, QueryFilter, DatabaseConnection};
+use crate::entities::friend_collections;
+use crate::entities::sea_orm_active_enums::FriendStatus;
+use crate::custom_errors::app::AppError;
+use uuid::Uuid;
+
+/// Fails when `other_id` has blocked `user_id`. The error does not say so,
+/// users are not told who blocked them.
+pub async fn check_not_blocked_by(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    other_id: Uuid
+) -> Result<(), AppError> {
+    let block = friend_collections::Entity::find()
+        .filter(friend_collections::Column::UserId.eq(other_id))
+        .filter(friend_collections::Column::FriendId.eq(user_id))
+        .filter(friend_collections::Column::Status.eq(FriendStatus::Blocked))
+        .one(db)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+    if block.is_some() {
+        return Err(AppError::Unauthorized("You cannot add or befriend this user".into()));
+    }
+
+    Ok(())
+}
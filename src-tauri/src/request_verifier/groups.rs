@@ -1,5 +1,6 @@
 use sea_orm::{EntityTrait, ColumnTrait, QueryFilter, DatabaseConnection};
 use crate::entities::{groups,group_members};
+use crate::entities::sea_orm_active_enums::GroupRole;
 use crate::custom_errors::app::AppError;
 use uuid::Uuid;
 
@@ -17,39 +18,89 @@ pub async fn check_group_exists(db: &DatabaseConnection, group_id: Uuid) -> Resu
     Ok(())
 }
 
+/// Archived groups are read-only: nothing in them can be added, edited or removed.
+/// Settling up stays possible so members can clear what they still owe.
+pub async fn check_group_not_archived(db: &DatabaseConnection, group_id: Uuid) -> Result<(), AppError> {
+    let archived_group = groups::Entity::find()
+        .filter(groups::Column::Id.eq(group_id))
+        .filter(groups::Column::ArchivedAt.is_not_null())
+        .one(db)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+    if archived_group.is_some() {
+        return Err(AppError::ValidationError("Archived groups are read-only".into()));
+    }
+
+    Ok(())
+}
+
 pub async fn check_user_exists_in_group(
     db: &DatabaseConnection, 
     group_id: Uuid, 
     user_id: Uuid
 ) -> Result<(), AppError> {
-    let is_member = group_members::Entity::find()
-        .filter(group_members::Column::GroupId.eq(group_id))
-        .filter(group_members::Column::MemberId.eq(user_id)) 
-        .one(db)
-        .await
-        .map_err(|_| AppError::InternalServerError)?;
+    find_member_role(db, group_id, user_id).await.map(|_| ())
+}
 
-    if is_member.is_some() {
-        return Ok(());
+/// Viewers can read a group but not add, edit or remove anything in it.
+pub async fn check_user_can_write_in_group(
+    db: &DatabaseConnection,
+    group_id: Uuid,
+    user_id: Uuid
+) -> Result<(), AppError> {
+    if !find_member_role(db, group_id, user_id).await?.can_write() {
+        return Err(AppError::Unauthorized("Viewers cannot make changes in this group".into()));
     }
 
-    check_user_is_admin_in_group(db, group_id, user_id).await
+    Ok(())
 }
+
 pub async fn check_user_is_admin_in_group(
     db: &DatabaseConnection, 
     group_id: Uuid, 
     user_id: Uuid
 ) -> Result<(), AppError> {
-    let is_admin = groups::Entity::find()
-    .filter(groups::Column::Id.eq(group_id))
-    .filter(groups::Column::CreatorId.eq(user_id))
-    .one(db)
-    .await
-    .map_err(|_| AppError::InternalServerError)?;
-
-    if is_admin.is_none() {
+    let role = find_member_role(db, group_id, user_id)
+        .await
+        .map_err(|_| AppError::UserNotAdminOfGroup("User not admin of group".into()))?;
+
+    if !role.is_admin() {
         return Err(AppError::UserNotAdminOfGroup("User not admin of group".into()));
     }
 
     Ok(())
-}
\ No newline at end of file
+}
+
+pub async fn check_user_is_owner_of_group(
+    db: &DatabaseConnection,
+    group_id: Uuid,
+    user_id: Uuid
+) -> Result<(), AppError> {
+    let role = find_member_role(db, group_id, user_id)
+        .await
+        .map_err(|_| AppError::UserNotAdminOfGroup("User not owner of group".into()))?;
+
+    if role != GroupRole::Owner {
+        return Err(AppError::UserNotAdminOfGroup("User not owner of group".into()));
+    }
+
+    Ok(())
+}
+
+pub async fn find_member_role(
+    db: &DatabaseConnection,
+    group_id: Uuid,
+    user_id: Uuid
+) -> Result<GroupRole, AppError> {
+    let membership = group_members::Entity::find()
+        .filter(group_members::Column::GroupId.eq(group_id))
+        .filter(group_members::Column::MemberId.eq(user_id))
+        .one(db)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+    membership
+        .map(|membership| membership.role)
+        .ok_or(AppError::UserNotInGroup("User not in group".into()))
+}
@@ -1,4 +1,5 @@
 pub mod users;
 pub mod groups;
 pub mod group_members;
-pub mod activities;
\ No newline at end of file
+pub mod activities;
+pub mod friends;
\ No newline at end of file
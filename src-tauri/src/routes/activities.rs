@@ -1,5 +1,5 @@
 use axum::{routing::{get, post,patch,delete}, Router, middleware};
-use crate::controllers::activities_controller::{create_activity_handler,update_activity_handler,delete_activity_handler,get_all_activities_handler};
+use crate::controllers::activities_controller::{create_activity_handler,update_activity_handler,delete_activity_handler,get_all_activities_handler,get_trash_handler,restore_activity_handler};
 use crate::request_verifier::users::verify_user;
 pub fn router() -> Router {
     Router::new()
@@ -7,5 +7,7 @@ pub fn router() -> Router {
         .route("/activities/update_activities", patch(update_activity_handler))
         .route("/activities/delete_activities", delete(delete_activity_handler))
         .route("/activities/get_all_activities", get(get_all_activities_handler))
+        .route("/activities/get_trash", get(get_trash_handler))
+        .route("/activities/restore_activity", post(restore_activity_handler))
         .layer(middleware::from_fn(verify_user))
 }
@@ -0,0 +1,13 @@
+use axum::{routing::{get, post}, Router, middleware};
+use crate::controllers::activity_history_controller::{
+    get_activity_history_handler, get_group_history_handler, revert_activity_handler,
+};
+use crate::request_verifier::users::verify_user;
+
+pub fn router() -> Router {
+    Router::new()
+        .route("/activity_history/get_activity_history", get(get_activity_history_handler))
+        .route("/activity_history/get_group_history", get(get_group_history_handler))
+        .route("/activity_history/revert_activity", post(revert_activity_handler))
+        .layer(middleware::from_fn(verify_user))
+}
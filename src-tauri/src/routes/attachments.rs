@@ -0,0 +1,20 @@
+use axum::{extract::DefaultBodyLimit, routing::{get, post, delete}, Router, middleware};
+use crate::controllers::attachments_controller::{
+    delete_attachment_handler, download_attachment_handler, get_attachments_handler,
+    upload_attachment_handler,
+};
+use crate::request_verifier::users::verify_user;
+use crate::utils::attachments::max_attachment_bytes;
+
+pub fn router() -> Router {
+    let max_bytes = max_attachment_bytes().expect("Invalid attachment size limit");
+    Router::new()
+        .route(
+            "/attachments/upload",
+            post(upload_attachment_handler).layer(DefaultBodyLimit::max(max_bytes)),
+        )
+        .route("/attachments/get_attachments", get(get_attachments_handler))
+        .route("/attachments/download", get(download_attachment_handler))
+        .route("/attachments/delete", delete(delete_attachment_handler))
+        .layer(middleware::from_fn(verify_user))
+}
@@ -0,0 +1,11 @@
+use axum::{routing::get, Router, middleware};
+use crate::controllers::balances_controller::{get_group_balances_handler,get_settlement_plan_handler,get_friend_balances_handler};
+use crate::request_verifier::users::verify_user;
+
+pub fn router() -> Router {
+    Router::new()
+        .route("/balances/get_group_balances", get(get_group_balances_handler))
+        .route("/balances/get_settlement_plan", get(get_settlement_plan_handler))
+        .route("/balances/get_friend_balances", get(get_friend_balances_handler))
+        .layer(middleware::from_fn(verify_user))
+}
@@ -0,0 +1,15 @@
+use axum::{routing::{get, post, delete}, Router, middleware};
+use crate::controllers::categories_controller::{
+    create_category_handler, delete_category_handler, get_categories_handler,
+    suggest_category_handler,
+};
+use crate::request_verifier::users::verify_user;
+
+pub fn router() -> Router {
+    Router::new()
+        .route("/categories/create_category", post(create_category_handler))
+        .route("/categories/get_categories", get(get_categories_handler))
+        .route("/categories/delete_category", delete(delete_category_handler))
+        .route("/categories/suggest_category", get(suggest_category_handler))
+        .layer(middleware::from_fn(verify_user))
+}
@@ -0,0 +1,14 @@
+use axum::{routing::{get, post, patch, delete}, Router, middleware};
+use crate::controllers::comments_controller::{
+    create_comment_handler, delete_comment_handler, get_comments_handler, update_comment_handler,
+};
+use crate::request_verifier::users::verify_user;
+
+pub fn router() -> Router {
+    Router::new()
+        .route("/comments/create_comment", post(create_comment_handler))
+        .route("/comments/get_comments", get(get_comments_handler))
+        .route("/comments/update_comment", patch(update_comment_handler))
+        .route("/comments/delete_comment", delete(delete_comment_handler))
+        .layer(middleware::from_fn(verify_user))
+}
@@ -0,0 +1,13 @@
+use axum::{middleware, routing::{get, patch}, Router};
+use crate::controllers::digests_controller::{
+    get_digest_preference_handler, preview_digest_handler, update_digest_preference_handler,
+};
+use crate::request_verifier::users::verify_user;
+
+pub fn router() -> Router {
+    Router::new()
+        .route("/digests/get_preference", get(get_digest_preference_handler))
+        .route("/digests/update_preference", patch(update_digest_preference_handler))
+        .route("/digests/preview", get(preview_digest_handler))
+        .layer(middleware::from_fn(verify_user))
+}
@@ -0,0 +1,17 @@
+use axum::{middleware, routing::{delete, get, post}, Router};
+use crate::controllers::friends_controller::{
+    block_user_handler, get_friends_handler, remove_friend_handler, respond_friend_request_handler,
+    send_friend_request_handler, unblock_user_handler,
+};
+use crate::request_verifier::users::verify_user;
+
+pub fn router() -> Router {
+    Router::new()
+        .route("/friends/send_request", post(send_friend_request_handler))
+        .route("/friends/respond_request", post(respond_friend_request_handler))
+        .route("/friends/get_friends", get(get_friends_handler))
+        .route("/friends/remove_friend", delete(remove_friend_handler))
+        .route("/friends/block_user", post(block_user_handler))
+        .route("/friends/unblock_user", delete(unblock_user_handler))
+        .layer(middleware::from_fn(verify_user))
+}
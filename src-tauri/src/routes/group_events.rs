@@ -0,0 +1,9 @@
+use axum::{middleware, routing::get, Router};
+use crate::controllers::group_events_controller::stream_events_handler;
+use crate::request_verifier::users::verify_user;
+
+pub fn router() -> Router {
+    Router::new()
+        .route("/events/stream", get(stream_events_handler))
+        .layer(middleware::from_fn(verify_user))
+}
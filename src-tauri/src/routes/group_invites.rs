@@ -0,0 +1,17 @@
+use axum::{middleware, routing::{delete, get, patch, post}, Router};
+use crate::controllers::group_invites_controller::{
+    create_invite_handler, get_invites_handler, get_join_code_handler, join_group_handler,
+    reset_join_code_handler, revoke_invite_handler,
+};
+use crate::request_verifier::users::verify_user;
+
+pub fn router() -> Router {
+    Router::new()
+        .route("/group_invites/create_invite", post(create_invite_handler))
+        .route("/group_invites/get_invites", get(get_invites_handler))
+        .route("/group_invites/revoke_invite", delete(revoke_invite_handler))
+        .route("/group_invites/join_group", post(join_group_handler))
+        .route("/group_invites/get_join_code", get(get_join_code_handler))
+        .route("/group_invites/reset_join_code", patch(reset_join_code_handler))
+        .layer(middleware::from_fn(verify_user))
+}
@@ -1,10 +1,17 @@
-use axum::{routing::{get, post}, Router, middleware};
-use crate::controllers::groups_controller::{create_group_handler,get_all_groups_handler};
+use axum::{routing::{get, post, patch, delete}, Router, middleware};
+use crate::controllers::groups_controller::{
+    archive_group_handler, create_group_handler, delete_group_handler, get_all_groups_handler,
+    unarchive_group_handler, update_group_settings_handler,
+};
 use crate::request_verifier::users::verify_user;
 
 pub fn router() -> Router {
     Router::new()
         .route("/groups/create_group", post(create_group_handler))
         .route("/groups/get_groups",get(get_all_groups_handler))
+        .route("/groups/update_settings", patch(update_group_settings_handler))
+        .route("/groups/archive_group", patch(archive_group_handler))
+        .route("/groups/unarchive_group", patch(unarchive_group_handler))
+        .route("/groups/delete_group", delete(delete_group_handler))
         .layer(middleware::from_fn(verify_user))
 }
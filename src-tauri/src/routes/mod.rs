@@ -6,6 +6,18 @@ mod users;
 mod activities;
 mod groups;
 mod group_members;
+mod balances;
+mod settlements;
+mod recurring_expenses;
+mod attachments;
+mod categories;
+mod comments;
+mod activity_history;
+mod group_invites;
+mod friends;
+mod notifications;
+mod group_events;
+mod digests;
 pub fn app_routes() -> Router {
     Router::new()
         .merge(users::router())
@@ -13,4 +25,16 @@ pub fn app_routes() -> Router {
         .merge(activities::router())
         .merge(groups::router())
         .merge(group_members::router())
+        .merge(balances::router())
+        .merge(settlements::router())
+        .merge(recurring_expenses::router())
+        .merge(attachments::router())
+        .merge(categories::router())
+        .merge(comments::router())
+        .merge(activity_history::router())
+        .merge(group_invites::router())
+        .merge(friends::router())
+        .merge(notifications::router())
+        .merge(group_events::router())
+        .merge(digests::router())
 }
@@ -0,0 +1,15 @@
+use axum::{middleware, routing::{delete, get, patch}, Router};
+use crate::controllers::notifications_controller::{
+    delete_notification_handler, get_notifications_handler, mark_all_notifications_read_handler,
+    mark_notifications_read_handler,
+};
+use crate::request_verifier::users::verify_user;
+
+pub fn router() -> Router {
+    Router::new()
+        .route("/notifications/get_notifications", get(get_notifications_handler))
+        .route("/notifications/mark_read", patch(mark_notifications_read_handler))
+        .route("/notifications/mark_all_read", patch(mark_all_notifications_read_handler))
+        .route("/notifications/delete_notification", delete(delete_notification_handler))
+        .layer(middleware::from_fn(verify_user))
+}
@@ -0,0 +1,15 @@
+use axum::{routing::{get, post, patch, delete}, Router, middleware};
+use crate::controllers::recurring_expenses_controller::{
+    create_recurring_expense_handler, delete_recurring_expense_handler,
+    get_recurring_expenses_handler, update_recurring_expense_handler,
+};
+use crate::request_verifier::users::verify_user;
+
+pub fn router() -> Router {
+    Router::new()
+        .route("/recurring_expenses/create_recurring_expense", post(create_recurring_expense_handler))
+        .route("/recurring_expenses/get_recurring_expenses", get(get_recurring_expenses_handler))
+        .route("/recurring_expenses/update_recurring_expense", patch(update_recurring_expense_handler))
+        .route("/recurring_expenses/delete_recurring_expense", delete(delete_recurring_expense_handler))
+        .layer(middleware::from_fn(verify_user))
+}
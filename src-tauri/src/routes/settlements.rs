@@ -0,0 +1,18 @@
+use axum::{routing::{get, post}, Router, middleware};
+use crate::controllers::settlements_controller::{create_settlement_handler,get_settlements_handler,create_upi_intent_handler,get_upi_qr_handler,refresh_upi_status_handler,upi_callback_handler,confirm_cash_settlement_handler,dispute_cash_settlement_handler,get_pending_confirmations_handler,settle_all_handler};
+use crate::request_verifier::users::verify_user;
+
+pub fn router() -> Router {
+    Router::new()
+        .route("/settlements/create_settlement", post(create_settlement_handler))
+        .route("/settlements/get_settlements", get(get_settlements_handler))
+        .route("/settlements/create_upi_intent", post(create_upi_intent_handler))
+        .route("/settlements/upi_qr", get(get_upi_qr_handler))
+        .route("/settlements/refresh_upi_status", post(refresh_upi_status_handler))
+        .route("/settlements/confirm_cash", post(confirm_cash_settlement_handler))
+        .route("/settlements/dispute_cash", post(dispute_cash_settlement_handler))
+        .route("/settlements/pending_confirmations", get(get_pending_confirmations_handler))
+        .route("/settlements/settle_all", post(settle_all_handler))
+        .layer(middleware::from_fn(verify_user))
+        .merge(Router::new().route("/settlements/upi_callback", post(upi_callback_handler)))
+}
@@ -0,0 +1,68 @@
+use crate::entities::{activities, activity_items};
+use serde_json::{json, Map, Value};
+
+/// Activity fields whose changes are recorded, `items` being the line items of
+/// an itemized split. Bookkeeping such as timestamps is left out.
+const TRACKED_FIELDS: [&str; 17] = [
+    "description",
+    "amount",
+    "currency",
+    "exchange_rate",
+    "paid_by_id",
+    "payer_ids",
+    "payer_amounts",
+    "split_members",
+    "split_amounts",
+    "split_type",
+    "split_values",
+    "tax",
+    "service_charge",
+    "tip",
+    "category_id",
+    "expense_logo",
+    "items",
+];
+
+/// The tracked fields that differ between two versions of an activity, as
+/// `{ field: { "before": .., "after": .. } }`. A missing side is a creation or
+/// a deletion and lists every tracked field that is set on the other side.
+pub fn diff(
+    before: Option<(&activities::Model, &[activity_items::Model])>,
+    after: Option<(&activities::Model, &[activity_items::Model])>,
+) -> Value {
+    let before = before.map(flatten);
+    let after = after.map(flatten);
+
+    let mut changes = Map::new();
+    for field in TRACKED_FIELDS {
+        let old = before.as_ref().and_then(|values| values.get(field)).cloned().unwrap_or(Value::Null);
+        let new = after.as_ref().and_then(|values| values.get(field)).cloned().unwrap_or(Value::Null);
+        if old != new {
+            changes.insert(field.to_string(), json!({ "before": old, "after": new }));
+        }
+    }
+    Value::Object(changes)
+}
+
+// items are compared by content, their ids change every time a split is recomputed
+fn flatten((activity, items): (&activities::Model, &[activity_items::Model])) -> Map<String, Value> {
+    let mut values = match serde_json::to_value(activity) {
+        Ok(Value::Object(values)) => values,
+        _ => Map::new(),
+    };
+    let items: Vec<Value> = items
+        .iter()
+        .map(|item| {
+            json!({
+                "name": item.name,
+                "unit_price": item.unit_price,
+                "quantity": item.quantity,
+                "assigned_members": item.assigned_members,
+            })
+        })
+        .collect();
+    if !items.is_empty() {
+        values.insert("items".to_string(), Value::Array(items));
+    }
+    values
+}
@@ -0,0 +1,41 @@
+use crate::custom_errors::app::AppError;
+use sha2::{Digest, Sha256};
+use std::env;
+
+const DEFAULT_MAX_ATTACHMENT_BYTES: usize = 10 * 1024 * 1024;
+
+/// Largest upload accepted, from `ATTACHMENT_MAX_BYTES`.
+pub fn max_attachment_bytes() -> Result<usize, AppError> {
+    match env::var("ATTACHMENT_MAX_BYTES") {
+        Ok(value) => value
+            .trim()
+            .parse()
+            .map_err(|_| AppError::ConfigError("Invalid ATTACHMENT_MAX_BYTES value".to_string())),
+        Err(_) => Ok(DEFAULT_MAX_ATTACHMENT_BYTES),
+    }
+}
+
+/// Identifies a supported file from its leading bytes rather than trusting the
+/// name or the declared content type.
+pub fn detect_content_type(bytes: &[u8]) -> Option<&'static str> {
+    if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        Some("image/jpeg")
+    } else if bytes.starts_with(&[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A]) {
+        Some("image/png")
+    } else if bytes.len() >= 12 && &bytes[..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+        Some("image/webp")
+    } else if bytes.starts_with(b"%PDF-") {
+        Some("application/pdf")
+    } else {
+        None
+    }
+}
+
+pub fn content_hash(bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(bytes))
+}
+
+/// Blobs are addressed by their hash so identical uploads are stored once.
+pub fn storage_key(content_hash: &str) -> String {
+    format!("attachments/{}/{}", &content_hash[..2], content_hash)
+}
@@ -0,0 +1,273 @@
+use crate::custom_errors::app::AppError;
+use async_trait::async_trait;
+use dotenv::dotenv;
+use object_store::aws::AmazonS3Builder;
+use object_store::path::Path as ObjectPath;
+use object_store::{ObjectStore, PutPayload};
+use std::env;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Where attachment contents live. Keys are generated by the server, never
+/// taken from user input.
+#[async_trait]
+pub trait BlobStore: Send + Sync {
+    async fn put(&self, key: &str, bytes: Vec<u8>) -> Result<(), AppError>;
+
+    async fn get(&self, key: &str) -> Result<Vec<u8>, AppError>;
+
+    async fn exists(&self, key: &str) -> Result<bool, AppError>;
+
+    /// Deleting a key that does not exist is not an error.
+    async fn delete(&self, key: &str) -> Result<(), AppError>;
+}
+
+pub type SharedBlobStore = Arc<dyn BlobStore>;
+
+/// Keeps blobs as files under a root directory.
+pub struct LocalBlobStore {
+    root: PathBuf,
+}
+
+impl LocalBlobStore {
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    fn path_for(&self, key: &str) -> PathBuf {
+        self.root.join(key)
+    }
+}
+
+#[async_trait]
+impl BlobStore for LocalBlobStore {
+    async fn put(&self, key: &str, bytes: Vec<u8>) -> Result<(), AppError> {
+        let path = self.path_for(key);
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(|e| AppError::StorageError(e.to_string()))?;
+        }
+        // written next to the target and renamed so readers never see half a file,
+        // each writer gets its own temp file so two uploads of one blob do not mix
+        let partial = path.with_extension(format!("{}.partial", Uuid::new_v4()));
+        tokio::fs::write(&partial, bytes)
+            .await
+            .map_err(|e| AppError::StorageError(e.to_string()))?;
+        tokio::fs::rename(&partial, &path)
+            .await
+            .map_err(|e| AppError::StorageError(e.to_string()))
+    }
+
+    async fn get(&self, key: &str) -> Result<Vec<u8>, AppError> {
+        tokio::fs::read(self.path_for(key)).await.map_err(|e| match e.kind() {
+            ErrorKind::NotFound => AppError::NotFound("Attachment content not found".into()),
+            _ => AppError::StorageError(e.to_string()),
+        })
+    }
+
+    async fn exists(&self, key: &str) -> Result<bool, AppError> {
+        tokio::fs::try_exists(self.path_for(key))
+            .await
+            .map_err(|e| AppError::StorageError(e.to_string()))
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), AppError> {
+        match tokio::fs::remove_file(self.path_for(key)).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(AppError::StorageError(e.to_string())),
+        }
+    }
+}
+
+/// Any S3-compatible object store, including self-hosted ones such as MinIO.
+/// Takes any `ObjectStore` so tests can hand it an in-memory one.
+pub struct S3BlobStore {
+    store: Arc<dyn ObjectStore>,
+}
+
+impl S3BlobStore {
+    pub fn new(store: Arc<dyn ObjectStore>) -> Self {
+        Self { store }
+    }
+}
+
+#[async_trait]
+impl BlobStore for S3BlobStore {
+    async fn put(&self, key: &str, bytes: Vec<u8>) -> Result<(), AppError> {
+        self.store
+            .put(&ObjectPath::from(key), PutPayload::from(bytes))
+            .await
+            .map_err(|e| AppError::StorageError(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> Result<Vec<u8>, AppError> {
+        let result = self
+            .store
+            .get(&ObjectPath::from(key))
+            .await
+            .map_err(|e| match e {
+                object_store::Error::NotFound { .. } => {
+                    AppError::NotFound("Attachment content not found".into())
+                }
+                e => AppError::StorageError(e.to_string()),
+            })?;
+        let bytes = result
+            .bytes()
+            .await
+            .map_err(|e| AppError::StorageError(e.to_string()))?;
+        Ok(bytes.to_vec())
+    }
+
+    async fn exists(&self, key: &str) -> Result<bool, AppError> {
+        match self.store.head(&ObjectPath::from(key)).await {
+            Ok(_) => Ok(true),
+            Err(object_store::Error::NotFound { .. }) => Ok(false),
+            Err(e) => Err(AppError::StorageError(e.to_string())),
+        }
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), AppError> {
+        match self.store.delete(&ObjectPath::from(key)).await {
+            Ok(()) | Err(object_store::Error::NotFound { .. }) => Ok(()),
+            Err(e) => Err(AppError::StorageError(e.to_string())),
+        }
+    }
+}
+
+/// Picks the store named by `BLOB_STORE`, `local` by default.
+///
+/// `local` keeps files under `BLOB_STORE_PATH` (default `./attachments`).
+/// `s3` needs `S3_BUCKET` and reads `S3_ENDPOINT`, `S3_REGION`,
+/// `S3_ACCESS_KEY_ID` and `S3_SECRET_ACCESS_KEY` when set; pointing
+/// `S3_ENDPOINT` at a local MinIO allows plain HTTP and path-style URLs.
+pub fn store_from_env() -> Result<SharedBlobStore, AppError> {
+    dotenv().ok();
+    let store = env::var("BLOB_STORE").unwrap_or_else(|_| "local".to_string());
+
+    match store.trim() {
+        "local" => {
+            let root = env::var("BLOB_STORE_PATH").unwrap_or_else(|_| "./attachments".to_string());
+            Ok(Arc::new(LocalBlobStore::new(PathBuf::from(root))))
+        }
+        "s3" => {
+            let bucket = env::var("S3_BUCKET")
+                .map_err(|_| AppError::ConfigError("S3_BUCKET must be set".to_string()))?;
+            let mut builder = AmazonS3Builder::new()
+                .with_bucket_name(bucket)
+                .with_region(env::var("S3_REGION").unwrap_or_else(|_| "us-east-1".to_string()));
+            if let Ok(endpoint) = env::var("S3_ENDPOINT") {
+                builder = builder
+                    .with_allow_http(endpoint.starts_with("http://"))
+                    .with_virtual_hosted_style_request(false)
+                    .with_endpoint(endpoint);
+            }
+            if let Ok(access_key_id) = env::var("S3_ACCESS_KEY_ID") {
+                builder = builder.with_access_key_id(access_key_id);
+            }
+            if let Ok(secret_access_key) = env::var("S3_SECRET_ACCESS_KEY") {
+                builder = builder.with_secret_access_key(secret_access_key);
+            }
+            let store = builder
+                .build()
+                .map_err(|e| AppError::ConfigError(format!("Invalid S3 configuration: {}", e)))?;
+            Ok(Arc::new(S3BlobStore::new(Arc::new(store))))
+        }
+        other => Err(AppError::ConfigError(format!("Unknown BLOB_STORE value: {}", other))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn local_store_round_trip() {
+        let root = env::temp_dir().join(format!("blobs-{}", Uuid::new_v4()));
+        let store = LocalBlobStore::new(root.clone());
+
+        store.put("ab/abcdef", b"receipt".to_vec()).await.unwrap();
+        assert!(store.exists("ab/abcdef").await.unwrap());
+        assert_eq!(store.get("ab/abcdef").await.unwrap(), b"receipt");
+
+        store.delete("ab/abcdef").await.unwrap();
+        store.delete("ab/abcdef").await.unwrap();
+        assert!(!store.exists("ab/abcdef").await.unwrap());
+        assert!(matches!(store.get("ab/abcdef").await, Err(AppError::NotFound(_))));
+
+        tokio::fs::remove_dir_all(root).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn concurrent_puts_of_one_blob_do_not_clash() {
+        let root = env::temp_dir().join(format!("blobs-{}", Uuid::new_v4()));
+        let store = Arc::new(LocalBlobStore::new(root.clone()));
+
+        let writers: Vec<_> = (0..8)
+            .map(|_| {
+                let store = store.clone();
+                tokio::spawn(async move { store.put("cd/cdef01", vec![7; 64 * 1024]).await })
+            })
+            .collect();
+        for writer in writers {
+            writer.await.unwrap().unwrap();
+        }
+
+        assert_eq!(store.get("cd/cdef01").await.unwrap(), vec![7; 64 * 1024]);
+        let mut entries = tokio::fs::read_dir(root.join("cd")).await.unwrap();
+        let mut names = Vec::new();
+        while let Some(entry) = entries.next_entry().await.unwrap() {
+            names.push(entry.file_name());
+        }
+        assert_eq!(names, vec!["cdef01"]);
+
+        tokio::fs::remove_dir_all(root).await.unwrap();
+    }
+
+    async fn assert_round_trip(store: &dyn BlobStore) {
+        let key = format!("ef/{}", Uuid::new_v4().simple());
+
+        store.put(&key, b"receipt".to_vec()).await.unwrap();
+        assert!(store.exists(&key).await.unwrap());
+        assert_eq!(store.get(&key).await.unwrap(), b"receipt");
+
+        store.delete(&key).await.unwrap();
+        store.delete(&key).await.unwrap();
+        assert!(!store.exists(&key).await.unwrap());
+        assert!(matches!(store.get(&key).await, Err(AppError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn s3_store_round_trip() {
+        let store = S3BlobStore::new(Arc::new(object_store::memory::InMemory::new()));
+        assert_round_trip(&store).await;
+    }
+
+    // needs a local MinIO with an existing bucket, for example
+    //   docker run -p 9000:9000 minio/minio server /data
+    //   mc alias set local http://localhost:9000 minioadmin minioadmin && mc mb local/centiverse-test
+    // then run `cargo test minio -- --ignored`; MINIO_TEST_ENDPOINT and
+    // MINIO_TEST_BUCKET override the defaults below
+    #[tokio::test]
+    #[ignore]
+    async fn s3_store_round_trip_against_minio() {
+        let endpoint = env::var("MINIO_TEST_ENDPOINT").unwrap_or_else(|_| "http://localhost:9000".to_string());
+        let bucket = env::var("MINIO_TEST_BUCKET").unwrap_or_else(|_| "centiverse-test".to_string());
+        let minio = AmazonS3Builder::new()
+            .with_bucket_name(bucket)
+            .with_region("us-east-1")
+            .with_allow_http(endpoint.starts_with("http://"))
+            .with_virtual_hosted_style_request(false)
+            .with_endpoint(endpoint)
+            .with_access_key_id("minioadmin")
+            .with_secret_access_key("minioadmin")
+            .build()
+            .unwrap();
+
+        assert_round_trip(&S3BlobStore::new(Arc::new(minio))).await;
+    }
+}
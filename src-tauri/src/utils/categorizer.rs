@@ -0,0 +1,124 @@
+use crate::entities::categories;
+
+/// Suggests the category whose keywords best match `description`. Keywords
+/// match whole words (or whole phrases) regardless of case, punctuation or a
+/// trailing plural "s". The category with most matching keywords wins; on a tie
+/// a group's own category beats a built-in one.
+pub fn suggest_category<'a>(
+    description: &str,
+    categories: &'a [categories::Model],
+) -> Option<&'a categories::Model> {
+    let words = normalize(description);
+    if words.is_empty() {
+        return None;
+    }
+
+    categories
+        .iter()
+        .map(|category| (score(&words, category), category))
+        .filter(|(score, _)| *score > 0)
+        .max_by(|(score_a, a), (score_b, b)| {
+            score_a
+                .cmp(score_b)
+                .then_with(|| a.group_id.is_some().cmp(&b.group_id.is_some()))
+                // reversed so the alphabetically first name wins a full tie
+                .then_with(|| b.name.cmp(&a.name))
+        })
+        .map(|(_, category)| category)
+}
+
+/// Lowercase keywords with surrounding whitespace removed, duplicates and blanks dropped.
+pub fn normalize_keywords(keywords: &[String]) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for keyword in keywords {
+        let keyword = keyword.trim().to_lowercase();
+        if !keyword.is_empty() && !normalized.contains(&keyword) {
+            normalized.push(keyword);
+        }
+    }
+    normalized
+}
+
+fn score(words: &[String], category: &categories::Model) -> usize {
+    let keywords: Vec<String> = serde_json::from_value(category.keywords.clone()).unwrap_or_default();
+    keywords
+        .iter()
+        .map(|keyword| normalize(keyword))
+        .filter(|phrase| !phrase.is_empty() && contains_phrase(words, phrase))
+        .count()
+}
+
+fn contains_phrase(words: &[String], phrase: &[String]) -> bool {
+    words.windows(phrase.len()).any(|window| window == phrase)
+}
+
+fn normalize(text: &str) -> Vec<String> {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| match word.strip_suffix('s') {
+            Some(singular) if singular.len() >= 3 && !singular.ends_with('s') => singular.to_string(),
+            _ => word.to_string(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use serde_json::json;
+    use uuid::Uuid;
+
+    fn category(name: &str, keywords: &[&str], group_id: Option<Uuid>) -> categories::Model {
+        let now = Utc::now().into();
+        categories::Model {
+            id: Uuid::new_v4(),
+            group_id,
+            name: name.into(),
+            logo: name.to_lowercase(),
+            keywords: json!(keywords),
+            created_by: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn keywords_match_whole_words_and_plurals() {
+        let categories = vec![
+            category("Food", &["pizza", "lunch"], None),
+            category("Travel", &["cab", "train ticket"], None),
+        ];
+
+        let found = suggest_category("Two PIZZAS!", &categories).map(|c| c.name.as_str());
+        assert_eq!(found, Some("Food"));
+        let found = suggest_category("train tickets home", &categories).map(|c| c.name.as_str());
+        assert_eq!(found, Some("Travel"));
+        // "cabin" is not a cab
+        assert!(suggest_category("cabin rent", &categories).is_none());
+        assert!(suggest_category("  ", &categories).is_none());
+    }
+
+    #[test]
+    fn most_matches_win_and_group_categories_break_ties() {
+        let group_id = Some(Uuid::new_v4());
+        let categories = vec![
+            category("Food", &["dinner"], None),
+            category("Party", &["dinner", "drinks"], None),
+            category("Team", &["offsite"], group_id),
+            category("Work", &["offsite"], None),
+        ];
+
+        let found = suggest_category("dinner and drinks", &categories).map(|c| c.name.as_str());
+        assert_eq!(found, Some("Party"));
+        let found = suggest_category("offsite", &categories).map(|c| c.name.as_str());
+        assert_eq!(found, Some("Team"));
+    }
+
+    #[test]
+    fn keywords_are_trimmed_lowercased_and_deduplicated() {
+        let keywords = vec![" Pizza ".to_string(), "pizza".to_string(), "".to_string(), "Lunch".to_string()];
+        assert_eq!(normalize_keywords(&keywords), vec!["pizza", "lunch"]);
+    }
+}
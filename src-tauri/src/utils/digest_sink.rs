@@ -0,0 +1,228 @@
+use crate::custom_errors::app::AppError;
+use crate::models::digests::DigestRes;
+use crate::utils::digests::{render_text, subject};
+use async_trait::async_trait;
+use dotenv::dotenv;
+use lettre::message::header::ContentType;
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncFileTransport, AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use std::env;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Where finished digests go. A sink either hands the digest over or fails,
+/// the scheduler retries failed ones on its next pass.
+#[async_trait]
+pub trait DigestSink: Send + Sync {
+    async fn deliver(&self, digest: &DigestRes) -> Result<(), AppError>;
+}
+
+pub type SharedDigestSink = Arc<dyn DigestSink>;
+
+/// Writes every digest as an `.eml` file into an outbox directory instead of
+/// sending it, for development or for another process to pick up.
+pub struct OutboxDigestSink {
+    root: PathBuf,
+    from: Mailbox,
+}
+
+impl OutboxDigestSink {
+    pub fn new(root: PathBuf, from: Mailbox) -> Self {
+        Self { root, from }
+    }
+}
+
+#[async_trait]
+impl DigestSink for OutboxDigestSink {
+    async fn deliver(&self, digest: &DigestRes) -> Result<(), AppError> {
+        let message = digest_message(&self.from, digest)?;
+        tokio::fs::create_dir_all(&self.root)
+            .await
+            .map_err(|e| AppError::StorageError(e.to_string()))?;
+        AsyncFileTransport::<Tokio1Executor>::new(&self.root)
+            .send(message)
+            .await
+            .map_err(|e| AppError::DeliveryError(e.to_string()))?;
+        Ok(())
+    }
+}
+
+/// Sends digests through an SMTP relay.
+pub struct SmtpDigestSink {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+}
+
+impl SmtpDigestSink {
+    pub fn new(transport: AsyncSmtpTransport<Tokio1Executor>, from: Mailbox) -> Self {
+        Self { transport, from }
+    }
+}
+
+#[async_trait]
+impl DigestSink for SmtpDigestSink {
+    async fn deliver(&self, digest: &DigestRes) -> Result<(), AppError> {
+        let message = digest_message(&self.from, digest)?;
+        self.transport
+            .send(message)
+            .await
+            .map_err(|e| AppError::DeliveryError(e.to_string()))?;
+        Ok(())
+    }
+}
+
+fn digest_message(from: &Mailbox, digest: &DigestRes) -> Result<Message, AppError> {
+    let to = Mailbox::new(
+        Some(digest.username.clone()),
+        digest
+            .email
+            .parse()
+            .map_err(|_| AppError::DeliveryError(format!("Invalid email address: {}", digest.email)))?,
+    );
+    Message::builder()
+        .from(from.clone())
+        .to(to)
+        .subject(subject(digest))
+        .header(ContentType::TEXT_PLAIN)
+        .body(render_text(digest))
+        .map_err(|e| AppError::DeliveryError(e.to_string()))
+}
+
+/// Picks the sink named by `DIGEST_SINK`, `outbox` by default. Digests are sent
+/// from `DIGEST_FROM`.
+///
+/// `outbox` writes files under `DIGEST_OUTBOX_PATH` (default `./digest_outbox`).
+/// `smtp` needs `SMTP_HOST` and reads `SMTP_PORT`, `SMTP_USERNAME` and
+/// `SMTP_PASSWORD` when set. `SMTP_TLS` is `starttls` by default, `tls` for
+/// implicit TLS or `none` for a local catcher such as MailHog or Mailpit.
+pub fn sink_from_env() -> Result<SharedDigestSink, AppError> {
+    dotenv().ok();
+    let from: Mailbox = env::var("DIGEST_FROM")
+        .unwrap_or_else(|_| "CentiVerse <digests@centiverse.local>".to_string())
+        .parse()
+        .map_err(|_| AppError::ConfigError("Invalid DIGEST_FROM value".to_string()))?;
+    let sink = env::var("DIGEST_SINK").unwrap_or_else(|_| "outbox".to_string());
+
+    match sink.trim() {
+        "outbox" => {
+            let root = env::var("DIGEST_OUTBOX_PATH").unwrap_or_else(|_| "./digest_outbox".to_string());
+            Ok(Arc::new(OutboxDigestSink::new(PathBuf::from(root), from)))
+        }
+        "smtp" => {
+            let host = env::var("SMTP_HOST")
+                .map_err(|_| AppError::ConfigError("SMTP_HOST must be set".to_string()))?;
+            let tls = env::var("SMTP_TLS").unwrap_or_else(|_| "starttls".to_string());
+            let mut builder = match tls.trim() {
+                "starttls" => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&host)
+                    .map_err(|e| AppError::ConfigError(format!("Invalid SMTP configuration: {}", e)))?,
+                "tls" => AsyncSmtpTransport::<Tokio1Executor>::relay(&host)
+                    .map_err(|e| AppError::ConfigError(format!("Invalid SMTP configuration: {}", e)))?,
+                "none" => AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&host),
+                other => {
+                    return Err(AppError::ConfigError(format!("Unknown SMTP_TLS value: {}", other)))
+                }
+            };
+            if let Ok(port) = env::var("SMTP_PORT") {
+                let port = port
+                    .trim()
+                    .parse()
+                    .map_err(|_| AppError::ConfigError("Invalid SMTP_PORT value".to_string()))?;
+                builder = builder.port(port);
+            }
+            if let (Ok(username), Ok(password)) = (env::var("SMTP_USERNAME"), env::var("SMTP_PASSWORD")) {
+                builder = builder.credentials(Credentials::new(username, password));
+            }
+            Ok(Arc::new(SmtpDigestSink::new(builder.build(), from)))
+        }
+        other => Err(AppError::ConfigError(format!("Unknown DIGEST_SINK value: {}", other))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::sea_orm_active_enums::DigestFrequency;
+    use crate::models::digests::DigestGroupRes;
+    use chrono::Utc;
+    use rust_decimal::Decimal;
+    use uuid::Uuid;
+
+    fn digest(email: &str) -> DigestRes {
+        let now = Utc::now();
+        DigestRes {
+            user_id: Uuid::new_v4(),
+            username: "Asha".into(),
+            email: email.into(),
+            frequency: DigestFrequency::Weekly,
+            period_start: (now - chrono::Duration::days(7)).into(),
+            period_end: now.into(),
+            new_expenses: Vec::new(),
+            groups: vec![DigestGroupRes {
+                group_id: Uuid::new_v4(),
+                group_name: "Goa trip".into(),
+                currency: "INR".into(),
+                net: Decimal::new(-25000, 2),
+            }],
+            pending_settlements: Vec::new(),
+        }
+    }
+
+    fn from() -> Mailbox {
+        "CentiVerse <digests@centiverse.local>".parse().unwrap()
+    }
+
+    #[tokio::test]
+    async fn outbox_writes_one_email_per_digest() {
+        let root = env::temp_dir().join(format!("digest-outbox-{}", uuid::Uuid::new_v4()));
+        let sink = OutboxDigestSink::new(root.clone(), from());
+
+        sink.deliver(&digest("asha@example.com")).await.unwrap();
+
+        let mut entries = tokio::fs::read_dir(&root).await.unwrap();
+        let mut emails = Vec::new();
+        while let Some(entry) = entries.next_entry().await.unwrap() {
+            emails.push(entry.path());
+        }
+        assert_eq!(emails.len(), 1);
+        assert_eq!(emails[0].extension().and_then(|e| e.to_str()), Some("eml"));
+
+        let email = tokio::fs::read_to_string(&emails[0]).await.unwrap();
+        assert!(email.contains("To: Asha <asha@example.com>"));
+        assert!(email.contains("From: CentiVerse <digests@centiverse.local>"));
+        assert!(email.contains("Subject: Your weekly CentiVerse digest"));
+        assert!(email.contains("Goa trip: you owe 250.00 INR"));
+
+        tokio::fs::remove_dir_all(root).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn an_invalid_address_is_a_delivery_error() {
+        let root = env::temp_dir().join(format!("digest-outbox-{}", uuid::Uuid::new_v4()));
+        let sink = OutboxDigestSink::new(root, from());
+
+        let result = sink.deliver(&digest("not an address")).await;
+        assert!(matches!(result, Err(AppError::DeliveryError(_))));
+    }
+
+    // needs a local mail catcher, for example
+    //   docker run -p 1025:1025 -p 8025:8025 axllent/mailpit
+    // then run `cargo test smtp_hands -- --ignored` and look for the digest at
+    // http://localhost:8025; DIGEST_TEST_SMTP_HOST and DIGEST_TEST_SMTP_PORT
+    // override localhost:1025
+    #[tokio::test]
+    #[ignore]
+    async fn smtp_hands_the_digest_to_the_relay() {
+        let host = env::var("DIGEST_TEST_SMTP_HOST").unwrap_or_else(|_| "localhost".to_string());
+        let port = env::var("DIGEST_TEST_SMTP_PORT")
+            .ok()
+            .and_then(|port| port.trim().parse().ok())
+            .unwrap_or(1025);
+        let transport = AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(host.trim())
+            .port(port)
+            .build();
+        let sink = SmtpDigestSink::new(transport, from());
+
+        sink.deliver(&digest("asha@example.com")).await.unwrap();
+    }
+}
@@ -0,0 +1,93 @@
+use chrono::{DateTime, Duration, Months, Utc};
+use crate::entities::sea_orm_active_enums::DigestFrequency;
+use crate::models::digests::DigestRes;
+use rust_decimal::Decimal;
+use std::fmt::Write;
+
+/// Start of the period a digest sent at `end` covers, `None` when digests are off.
+pub fn period_start(frequency: DigestFrequency, end: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    match frequency {
+        DigestFrequency::Off => None,
+        DigestFrequency::Weekly => Some(end - Duration::days(7)),
+        DigestFrequency::Monthly => end.checked_sub_months(Months::new(1)),
+    }
+}
+
+pub fn subject(digest: &DigestRes) -> String {
+    let period = match digest.frequency {
+        DigestFrequency::Monthly => "monthly",
+        _ => "weekly",
+    };
+    format!("Your {} CentiVerse digest", period)
+}
+
+/// Plain text body of the digest email.
+pub fn render_text(digest: &DigestRes) -> String {
+    let mut body = String::new();
+    // writing into a String cannot fail
+    let _ = writeln!(body, "Hi {},", digest.username);
+    let _ = writeln!(body);
+    let _ = writeln!(
+        body,
+        "Here is what happened between {} and {}.",
+        digest.period_start.format("%d %b %Y"),
+        digest.period_end.format("%d %b %Y"),
+    );
+
+    let _ = writeln!(body);
+    let _ = writeln!(body, "New expenses");
+    if digest.new_expenses.is_empty() {
+        let _ = writeln!(body, "  No new expenses.");
+    }
+    for expense in &digest.new_expenses {
+        let _ = write!(
+            body,
+            "  - {}: {}, {} {} paid by {}",
+            expense.group_name, expense.description, expense.amount, expense.currency, expense.paid_by_name,
+        );
+        if !expense.your_share.is_zero() {
+            let _ = write!(body, " (your share {} {})", expense.your_share, expense.currency);
+        }
+        let _ = writeln!(body);
+    }
+
+    let _ = writeln!(body);
+    let _ = writeln!(body, "Balances");
+    if digest.groups.is_empty() {
+        let _ = writeln!(body, "  You are not in any groups.");
+    }
+    for group in &digest.groups {
+        let status = if group.net > Decimal::ZERO {
+            format!("you are owed {} {}", group.net, group.currency)
+        } else if group.net < Decimal::ZERO {
+            format!("you owe {} {}", -group.net, group.currency)
+        } else {
+            "settled up".to_string()
+        };
+        let _ = writeln!(body, "  - {}: {}", group.group_name, status);
+    }
+
+    if !digest.pending_settlements.is_empty() {
+        let _ = writeln!(body);
+        let _ = writeln!(body, "Pending settlements");
+    }
+    for settlement in &digest.pending_settlements {
+        if settlement.awaiting_you {
+            let _ = writeln!(
+                body,
+                "  - {}: {} says they paid you {}, waiting for your confirmation",
+                settlement.group_name, settlement.counterparty_name, settlement.amount,
+            );
+        } else {
+            let _ = writeln!(
+                body,
+                "  - {}: you paid {} {}, waiting for them to confirm",
+                settlement.group_name, settlement.counterparty_name, settlement.amount,
+            );
+        }
+    }
+
+    let _ = writeln!(body);
+    let _ = writeln!(body, "You can change how often you get this digest in your settings.");
+    body
+}
@@ -0,0 +1,12 @@
+use serde::{Deserialize, Deserializer};
+
+/// Lets an update request tell a field that was left out (`None`) apart from one
+/// sent as `null` (`Some(None)`). Use together with `#[serde(default)]`, which
+/// covers the missing case since this is only called when the field is present.
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
@@ -0,0 +1,36 @@
+use crate::entities::group_events;
+use tokio::sync::broadcast;
+
+// subscribers that fall further behind than this catch up from the database
+const CHANNEL_CAPACITY: usize = 1024;
+
+/// Fans recorded group events out to the open event streams. Events are
+/// published only after the transaction that recorded them has committed.
+#[derive(Clone)]
+pub struct EventBus {
+    sender: broadcast::Sender<group_events::Model>,
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(CHANNEL_CAPACITY);
+        Self { sender }
+    }
+
+    pub fn publish(&self, events: Vec<group_events::Model>) {
+        for event in events {
+            // fails only when nobody is listening, which is fine
+            let _ = self.sender.send(event);
+        }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<group_events::Model> {
+        self.sender.subscribe()
+    }
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
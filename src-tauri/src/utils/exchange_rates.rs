@@ -0,0 +1,301 @@
+use crate::custom_errors::app::AppError;
+use crate::entities::exchange_rates;
+use chrono::{NaiveDate, Utc};
+use dotenv::dotenv;
+use rust_decimal::Decimal;
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, QueryOrder, Set};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::Path;
+use uuid::Uuid;
+
+pub const DEFAULT_CURRENCY: &str = "INR";
+
+// postgres takes at most 65535 bind parameters per statement
+const IMPORT_BATCH_SIZE: usize = 1000;
+
+/// One row of a rates file: one unit of `base_currency` buys `rate` units of
+/// `quote_currency` on `rate_date`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct RateEntry {
+    pub base_currency: String,
+    pub quote_currency: String,
+    pub rate: Decimal,
+    pub rate_date: NaiveDate,
+}
+
+/// Uppercases an ISO 4217 code and rejects anything that is not three letters.
+pub fn normalize_currency(code: &str) -> Result<String, AppError> {
+    let code = code.trim().to_ascii_uppercase();
+    if code.len() != 3 || !code.chars().all(|c| c.is_ascii_alphabetic()) {
+        return Err(AppError::ValidationError(format!(
+            "Invalid currency code: {}",
+            code
+        )));
+    }
+    Ok(code)
+}
+
+/// Reads rates from a `.csv` file with a header row or a `.json` array of objects,
+/// both using the field names of [`RateEntry`].
+pub fn read_rates_file(path: &Path) -> Result<Vec<RateEntry>, AppError> {
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(|extension| extension.to_ascii_lowercase());
+
+    let entries: Vec<RateEntry> = match extension.as_deref() {
+        Some("csv") => {
+            let mut reader = csv::Reader::from_path(path)
+                .map_err(|e| AppError::ConfigError(format!("Cannot read rates file: {}", e)))?;
+            reader
+                .deserialize()
+                .collect::<Result<_, _>>()
+                .map_err(|e| AppError::ConfigError(format!("Malformed rates file: {}", e)))?
+        }
+        Some("json") => {
+            let contents = fs::read_to_string(path)
+                .map_err(|e| AppError::ConfigError(format!("Cannot read rates file: {}", e)))?;
+            serde_json::from_str(&contents)
+                .map_err(|e| AppError::ConfigError(format!("Malformed rates file: {}", e)))?
+        }
+        _ => {
+            return Err(AppError::ConfigError(
+                "Rates file must be a .csv or .json file".into(),
+            ))
+        }
+    };
+
+    entries
+        .into_iter()
+        .map(|entry| {
+            if entry.rate <= Decimal::ZERO {
+                return Err(AppError::ConfigError(format!(
+                    "Rate for {}/{} on {} must be greater than zero",
+                    entry.base_currency, entry.quote_currency, entry.rate_date
+                )));
+            }
+            Ok(RateEntry {
+                base_currency: normalize_currency(&entry.base_currency)
+                    .map_err(|e| AppError::ConfigError(e.to_string()))?,
+                quote_currency: normalize_currency(&entry.quote_currency)
+                    .map_err(|e| AppError::ConfigError(e.to_string()))?,
+                ..entry
+            })
+        })
+        .collect()
+}
+
+/// Stores the given rates, replacing any rate already held for the same pair and date.
+pub async fn import_rates<C: ConnectionTrait>(
+    db: &C,
+    entries: Vec<RateEntry>,
+) -> Result<usize, AppError> {
+    // a pair listed twice for the same date would make postgres refuse the
+    // whole insert
+    let latest = last_rate_per_day(entries);
+    let count = latest.len();
+    if count == 0 {
+        return Ok(0);
+    }
+
+    for batch in latest.chunks(IMPORT_BATCH_SIZE) {
+        let models = batch.iter().map(|entry| exchange_rates::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            base_currency: Set(entry.base_currency.clone()),
+            quote_currency: Set(entry.quote_currency.clone()),
+            rate: Set(entry.rate),
+            rate_date: Set(entry.rate_date),
+            created_at: Set(Utc::now().into()),
+        });
+
+        exchange_rates::Entity::insert_many(models)
+            .on_conflict(
+                OnConflict::columns([
+                    exchange_rates::Column::BaseCurrency,
+                    exchange_rates::Column::QuoteCurrency,
+                    exchange_rates::Column::RateDate,
+                ])
+                .update_columns([exchange_rates::Column::Rate])
+                .to_owned(),
+            )
+            .exec(db)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    }
+
+    Ok(count)
+}
+
+/// Keeps one entry per pair and date, the later one wins as it would across two imports.
+fn last_rate_per_day(entries: Vec<RateEntry>) -> Vec<RateEntry> {
+    let mut latest: Vec<RateEntry> = Vec::with_capacity(entries.len());
+    let mut positions: HashMap<(String, String, NaiveDate), usize> = HashMap::new();
+    for entry in entries {
+        let key = (
+            entry.base_currency.clone(),
+            entry.quote_currency.clone(),
+            entry.rate_date,
+        );
+        match positions.get(&key) {
+            Some(&position) => latest[position] = entry,
+            None => {
+                positions.insert(key, latest.len());
+                latest.push(entry);
+            }
+        }
+    }
+    latest
+}
+
+/// Imports the file named by `EXCHANGE_RATES_FILE`, if one is configured.
+pub async fn import_rates_from_env<C: ConnectionTrait>(db: &C) -> Result<usize, AppError> {
+    dotenv().ok();
+    let path = match env::var("EXCHANGE_RATES_FILE") {
+        Ok(path) if !path.trim().is_empty() => path,
+        _ => return Ok(0),
+    };
+    let entries = read_rates_file(Path::new(path.trim()))?;
+    import_rates(db, entries).await
+}
+
+/// The rate that converts `from` into `to`, using the latest rate dated on or
+/// before `on`. Falls back to the inverse of the opposite pair when only that is stored.
+pub async fn find_rate<C: ConnectionTrait>(
+    db: &C,
+    from: &str,
+    to: &str,
+    on: NaiveDate,
+) -> Result<Decimal, AppError> {
+    if from == to {
+        return Ok(Decimal::ONE);
+    }
+
+    if let Some(direct) = latest_rate(db, from, to, on).await? {
+        return Ok(direct.rate);
+    }
+    if let Some(inverse) = latest_rate(db, to, from, on).await? {
+        return Ok(Decimal::ONE / inverse.rate);
+    }
+
+    Err(AppError::ValidationError(format!(
+        "No exchange rate from {} to {} on or before {}",
+        from, to, on
+    )))
+}
+
+async fn latest_rate<C: ConnectionTrait>(
+    db: &C,
+    base: &str,
+    quote: &str,
+    on: NaiveDate,
+) -> Result<Option<exchange_rates::Model>, AppError> {
+    exchange_rates::Entity::find()
+        .filter(exchange_rates::Column::BaseCurrency.eq(base))
+        .filter(exchange_rates::Column::QuoteCurrency.eq(quote))
+        .filter(exchange_rates::Column::RateDate.lte(on))
+        .order_by_desc(exchange_rates::Column::RateDate)
+        .one(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(base: &str, quote: &str, rate: &str, day: u32) -> RateEntry {
+        RateEntry {
+            base_currency: base.into(),
+            quote_currency: quote.into(),
+            rate: rate.parse().unwrap(),
+            rate_date: NaiveDate::from_ymd_opt(2025, 4, day).unwrap(),
+        }
+    }
+
+    fn write_temp(extension: &str, contents: &str) -> std::path::PathBuf {
+        let path = env::temp_dir().join(format!("rates-{}.{}", Uuid::new_v4(), extension));
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn currency_codes_are_normalized() {
+        assert_eq!(normalize_currency(" usd ").unwrap(), "USD");
+        assert!(normalize_currency("US").is_err());
+        assert!(normalize_currency("US1").is_err());
+        assert!(normalize_currency("EURO").is_err());
+    }
+
+    #[test]
+    fn csv_and_json_files_are_read_alike() {
+        let csv = write_temp(
+            "csv",
+            "base_currency,quote_currency,rate,rate_date\nusd,inr,83.25,2025-04-01\n",
+        );
+        let json = write_temp(
+            "json",
+            r#"[{"base_currency":"usd","quote_currency":"inr","rate":"83.25","rate_date":"2025-04-01"}]"#,
+        );
+
+        let expected = vec![entry("USD", "INR", "83.25", 1)];
+        assert_eq!(read_rates_file(&csv).unwrap(), expected);
+        assert_eq!(read_rates_file(&json).unwrap(), expected);
+        fs::remove_file(csv).unwrap();
+        fs::remove_file(json).unwrap();
+    }
+
+    #[test]
+    fn bad_rates_files_are_refused() {
+        let zero = write_temp("csv", "base_currency,quote_currency,rate,rate_date\nUSD,INR,0,2025-04-01\n");
+        let text = write_temp("txt", "USD,INR,83.25,2025-04-01\n");
+
+        assert!(matches!(read_rates_file(&zero), Err(AppError::ConfigError(_))));
+        assert!(matches!(read_rates_file(&text), Err(AppError::ConfigError(_))));
+        fs::remove_file(zero).unwrap();
+        fs::remove_file(text).unwrap();
+    }
+
+    #[test]
+    fn a_pair_listed_twice_for_a_day_keeps_the_later_rate() {
+        let entries = vec![
+            entry("USD", "INR", "83.00", 1),
+            entry("EUR", "INR", "90.00", 1),
+            entry("USD", "INR", "83.50", 1),
+            entry("USD", "INR", "84.00", 2),
+        ];
+
+        assert_eq!(
+            last_rate_per_day(entries),
+            vec![
+                entry("USD", "INR", "83.50", 1),
+                entry("EUR", "INR", "90.00", 1),
+                entry("USD", "INR", "84.00", 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn large_files_are_deduped_by_pair_and_day() {
+        let start = NaiveDate::from_ymd_opt(2000, 1, 1).unwrap();
+        let entries: Vec<RateEntry> = (0..3 * IMPORT_BATCH_SIZE as u64)
+            .flat_map(|day| {
+                let rate_date = start + chrono::Days::new(day);
+                [Decimal::new(8300, 2), Decimal::new(8350, 2)].map(|rate| RateEntry {
+                    base_currency: "USD".into(),
+                    quote_currency: "INR".into(),
+                    rate,
+                    rate_date,
+                })
+            })
+            .collect();
+
+        let latest = last_rate_per_day(entries);
+        assert_eq!(latest.len(), 3 * IMPORT_BATCH_SIZE);
+        assert!(latest.iter().all(|entry| entry.rate == Decimal::new(8350, 2)));
+        assert!(latest.windows(2).all(|pair| pair[0].rate_date < pair[1].rate_date));
+    }
+}
@@ -0,0 +1,95 @@
+use crate::custom_errors::app::AppError;
+use rand::distributions::{Alphanumeric, Distribution, Slice};
+use rand::Rng;
+use std::env;
+
+const INVITE_TOKEN_LENGTH: usize = 32;
+const JOIN_CODE_LENGTH: usize = 8;
+const DEFAULT_INVITE_TTL_HOURS: i64 = 7 * 24;
+
+// no 0/O or 1/I/L, join codes get read out and typed by hand
+const JOIN_CODE_ALPHABET: &[char] = &[
+    'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'J', 'K', 'M', 'N', 'P', 'Q', 'R', 'S', 'T', 'U',
+    'V', 'W', 'X', 'Y', 'Z', '2', '3', '4', '5', '6', '7', '8', '9',
+];
+
+/// A random, URL safe token for an invite link.
+pub fn generate_invite_token() -> String {
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(INVITE_TOKEN_LENGTH)
+        .map(char::from)
+        .collect()
+}
+
+pub fn generate_join_code() -> String {
+    let alphabet = Slice::new(JOIN_CODE_ALPHABET).expect("alphabet is not empty");
+    alphabet
+        .sample_iter(rand::thread_rng())
+        .take(JOIN_CODE_LENGTH)
+        .collect()
+}
+
+/// Join codes are matched ignoring case, spaces and dashes.
+pub fn normalize_join_code(code: &str) -> String {
+    code.chars()
+        .filter(|c| !c.is_whitespace() && *c != '-')
+        .map(|c| c.to_ascii_uppercase())
+        .collect()
+}
+
+/// How long an invite stays valid when none is asked for, `GROUP_INVITE_TTL_HOURS`.
+pub fn default_invite_ttl() -> Result<chrono::Duration, AppError> {
+    parse_invite_ttl(env::var("GROUP_INVITE_TTL_HOURS").ok().as_deref())
+}
+
+fn parse_invite_ttl(value: Option<&str>) -> Result<chrono::Duration, AppError> {
+    let hours: i64 = match value {
+        Some(value) => value
+            .trim()
+            .parse()
+            .ok()
+            .filter(|hours| *hours > 0)
+            .ok_or_else(|| AppError::ConfigError("Invalid GROUP_INVITE_TTL_HOURS value".to_string()))?,
+        None => DEFAULT_INVITE_TTL_HOURS,
+    };
+    Ok(chrono::Duration::hours(hours))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokens_are_long_and_url_safe() {
+        let token = generate_invite_token();
+        assert_eq!(token.len(), INVITE_TOKEN_LENGTH);
+        assert!(token.chars().all(|c| c.is_ascii_alphanumeric()));
+        assert_ne!(token, generate_invite_token());
+    }
+
+    #[test]
+    fn join_codes_avoid_lookalike_characters() {
+        for _ in 0..50 {
+            let code = generate_join_code();
+            assert_eq!(code.len(), JOIN_CODE_LENGTH);
+            assert!(code.chars().all(|c| JOIN_CODE_ALPHABET.contains(&c)));
+            assert!(!code.contains(['0', 'O', '1', 'I', 'L']));
+        }
+    }
+
+    #[test]
+    fn join_codes_ignore_case_spaces_and_dashes() {
+        assert_eq!(normalize_join_code(" abcd-ef 23 "), "ABCDEF23");
+        assert_eq!(normalize_join_code("ABCDEF23"), "ABCDEF23");
+    }
+
+    #[test]
+    fn invite_ttls_are_parsed() {
+        assert_eq!(parse_invite_ttl(None).unwrap(), chrono::Duration::hours(DEFAULT_INVITE_TTL_HOURS));
+        assert_eq!(parse_invite_ttl(Some(" 12 ")).unwrap(), chrono::Duration::hours(12));
+
+        assert!(matches!(parse_invite_ttl(Some("0")), Err(AppError::ConfigError(_))));
+        assert!(matches!(parse_invite_ttl(Some("a day")), Err(AppError::ConfigError(_))));
+    }
+}
@@ -0,0 +1,224 @@
+use rust_decimal::Decimal;
+use std::collections::{BTreeMap, BTreeSet};
+use uuid::Uuid;
+
+/// Per-member totals derived from a group's expenses and settlements.
+/// A positive `net` means the member is owed money, a negative one means they owe.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MemberBalance {
+    pub member_id: Uuid,
+    pub paid: Decimal,
+    pub share: Decimal,
+    pub settled_out: Decimal,
+    pub settled_in: Decimal,
+    pub net: Decimal,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Debt {
+    pub debtor_id: Uuid,
+    pub creditor_id: Uuid,
+    pub amount: Decimal,
+}
+
+/// In-memory ledger for a single group. It has no database access so the
+/// balance rules can be exercised on plain values.
+#[derive(Debug, Clone, Default)]
+pub struct Ledger {
+    members: BTreeSet<Uuid>,
+    paid: BTreeMap<Uuid, Decimal>,
+    share: BTreeMap<Uuid, Decimal>,
+    settled_out: BTreeMap<Uuid, Decimal>,
+    settled_in: BTreeMap<Uuid, Decimal>,
+    // keyed by (lower id, higher id); a positive value means the lower id owes the higher id
+    debts: BTreeMap<(Uuid, Uuid), Decimal>,
+}
+
+impl Ledger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_member(&mut self, member_id: Uuid) {
+        self.members.insert(member_id);
+    }
+
+    /// Credits each payer with what they put in and debits every split member with
+    /// their share. With several payers a member owes each of them in proportion
+    /// to how much of the bill that payer covered.
+    pub fn record_expense(&mut self, payers: &[(Uuid, Decimal)], splits: &[(Uuid, Decimal)]) {
+        let total_paid: Decimal = payers.iter().map(|(_, paid)| *paid).sum();
+
+        for (payer_id, paid) in payers {
+            self.add_member(*payer_id);
+            *self.paid.entry(*payer_id).or_default() += *paid;
+        }
+
+        for (member_id, member_share) in splits {
+            self.add_member(*member_id);
+            *self.share.entry(*member_id).or_default() += *member_share;
+            if total_paid.is_zero() {
+                continue;
+            }
+            for (payer_id, paid) in payers {
+                self.add_debt(*member_id, *payer_id, *member_share * *paid / total_paid);
+            }
+        }
+    }
+
+    /// Records money moving from `payer_id` to `receiver_id` outside of an expense.
+    pub fn record_settlement(&mut self, payer_id: Uuid, receiver_id: Uuid, amount: Decimal) {
+        self.add_member(payer_id);
+        self.add_member(receiver_id);
+        *self.settled_out.entry(payer_id).or_default() += amount;
+        *self.settled_in.entry(receiver_id).or_default() += amount;
+        self.add_debt(receiver_id, payer_id, amount);
+    }
+
+    pub fn member_balances(&self) -> Vec<MemberBalance> {
+        self.members
+            .iter()
+            .map(|member_id| {
+                let paid = self.paid.get(member_id).copied().unwrap_or_default();
+                let share = self.share.get(member_id).copied().unwrap_or_default();
+                let settled_out = self.settled_out.get(member_id).copied().unwrap_or_default();
+                let settled_in = self.settled_in.get(member_id).copied().unwrap_or_default();
+                MemberBalance {
+                    member_id: *member_id,
+                    paid,
+                    share,
+                    settled_out,
+                    settled_in,
+                    net: paid - share + settled_out - settled_in,
+                }
+            })
+            .collect()
+    }
+
+    /// Direct debts between each pair of members, netted in both directions.
+    pub fn pairwise_debts(&self) -> Vec<Debt> {
+        self.debts
+            .iter()
+            .filter(|(_, amount)| !amount.is_zero())
+            .map(|((low, high), amount)| {
+                if amount.is_sign_positive() {
+                    Debt { debtor_id: *low, creditor_id: *high, amount: *amount }
+                } else {
+                    Debt { debtor_id: *high, creditor_id: *low, amount: -*amount }
+                }
+            })
+            .collect()
+    }
+
+    fn add_debt(&mut self, debtor_id: Uuid, creditor_id: Uuid, amount: Decimal) {
+        if debtor_id == creditor_id {
+            return;
+        }
+        if debtor_id < creditor_id {
+            *self.debts.entry((debtor_id, creditor_id)).or_default() += amount;
+        } else {
+            *self.debts.entry((creditor_id, debtor_id)).or_default() -= amount;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn d(value: &str) -> Decimal {
+        value.parse().unwrap()
+    }
+
+    fn ids() -> (Uuid, Uuid, Uuid) {
+        let mut ids = [Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4()];
+        ids.sort();
+        (ids[0], ids[1], ids[2])
+    }
+
+    fn net_of(ledger: &Ledger, member_id: Uuid) -> Decimal {
+        ledger
+            .member_balances()
+            .into_iter()
+            .find(|balance| balance.member_id == member_id)
+            .map(|balance| balance.net)
+            .unwrap()
+    }
+
+    #[test]
+    fn equal_split_with_one_payer() {
+        let (a, b, c) = ids();
+        let mut ledger = Ledger::new();
+        ledger.record_expense(&[(a, d("90"))], &[(a, d("30")), (b, d("30")), (c, d("30"))]);
+
+        assert_eq!(net_of(&ledger, a), d("60"));
+        assert_eq!(net_of(&ledger, b), d("-30"));
+        assert_eq!(net_of(&ledger, c), d("-30"));
+        let debts = ledger.pairwise_debts();
+        assert_eq!(debts.len(), 2);
+        assert!(debts.iter().all(|debt| debt.creditor_id == a && debt.amount == d("30")));
+    }
+
+    #[test]
+    fn several_payers_are_owed_in_proportion() {
+        let (a, b, c) = ids();
+        let mut ledger = Ledger::new();
+        // a covered three quarters of the bill, b the rest
+        ledger.record_expense(&[(a, d("75")), (b, d("25"))], &[(c, d("100"))]);
+
+        let debts = ledger.pairwise_debts();
+        let owed_to = |creditor_id: Uuid| {
+            debts
+                .iter()
+                .find(|debt| debt.debtor_id == c && debt.creditor_id == creditor_id)
+                .map(|debt| debt.amount)
+        };
+        assert_eq!(owed_to(a), Some(d("75")));
+        assert_eq!(owed_to(b), Some(d("25")));
+    }
+
+    #[test]
+    fn debts_are_netted_in_both_directions() {
+        let (a, b, _) = ids();
+        let mut ledger = Ledger::new();
+        ledger.record_expense(&[(a, d("40"))], &[(b, d("40"))]);
+        ledger.record_expense(&[(b, d("10"))], &[(a, d("10"))]);
+
+        let debts = ledger.pairwise_debts();
+        assert_eq!(debts, vec![Debt { debtor_id: b, creditor_id: a, amount: d("30") }]);
+    }
+
+    #[test]
+    fn settlements_pay_debts_down() {
+        let (a, b, _) = ids();
+        let mut ledger = Ledger::new();
+        ledger.record_expense(&[(a, d("50"))], &[(b, d("50"))]);
+        ledger.record_settlement(b, a, d("50"));
+
+        assert!(ledger.pairwise_debts().is_empty());
+        assert!(net_of(&ledger, a).is_zero());
+        assert!(net_of(&ledger, b).is_zero());
+    }
+
+    #[test]
+    fn nets_always_sum_to_zero() {
+        let (a, b, c) = ids();
+        let mut ledger = Ledger::new();
+        ledger.record_expense(&[(a, d("100"))], &[(a, d("33.34")), (b, d("33.33")), (c, d("33.33"))]);
+        ledger.record_expense(&[(b, d("20")), (c, d("10"))], &[(a, d("15")), (c, d("15"))]);
+        ledger.record_settlement(c, a, d("5"));
+
+        let total: Decimal = ledger.member_balances().iter().map(|balance| balance.net).sum();
+        assert!(total.is_zero());
+    }
+
+    #[test]
+    fn members_without_activity_are_listed_as_settled() {
+        let (a, _, _) = ids();
+        let mut ledger = Ledger::new();
+        ledger.add_member(a);
+
+        assert!(net_of(&ledger, a).is_zero());
+        assert!(ledger.pairwise_debts().is_empty());
+    }
+}
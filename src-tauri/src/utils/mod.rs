@@ -1 +1,18 @@
-pub mod jwt_token;
\ No newline at end of file
+pub mod jwt_token;
+pub mod exchange_rates;
+pub mod ledger;
+pub mod settlement_planner;
+pub mod upi;
+pub mod upi_psp;
+pub mod split_calculator;
+pub mod recurrence;
+pub mod blob_store;
+pub mod attachments;
+pub mod categorizer;
+pub mod activity_history;
+pub mod invites;
+pub mod event_bus;
+pub mod digests;
+pub mod digest_sink;
+pub mod oauth;
+pub mod double_option;
\ No newline at end of file
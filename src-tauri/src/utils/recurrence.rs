@@ -0,0 +1,225 @@
+use crate::custom_errors::app::AppError;
+use crate::entities::sea_orm_active_enums::RecurrenceFrequency;
+use chrono::{DateTime, Datelike, Duration, Months, Utc};
+use cron::Schedule;
+use std::str::FromStr;
+
+// indexed by the Unix day of week, where both 0 and 7 are Sunday
+const WEEKDAYS: [&str; 8] = ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT", "SUN"];
+
+/// Parses a cron expression. The usual five fields (minute to day of week) are
+/// accepted as well as the six or seven field form with seconds and year.
+pub fn parse_cron(expression: &str) -> Result<Schedule, AppError> {
+    let mut fields: Vec<String> = expression.split_whitespace().map(String::from).collect();
+    if fields.len() == 5 {
+        // the cron crate counts Sunday as 1, so Unix day numbers are spelled out
+        fields[4] = unix_weekdays(&fields[4])?;
+        fields.insert(0, "0".into());
+    }
+    Schedule::from_str(&fields.join(" "))
+        .map_err(|e| AppError::ValidationError(format!("Invalid cron expression: {}", e)))
+}
+
+/// Rewrites a Unix day of week field with day names, so `1-5` becomes
+/// `MON,TUE,WED,THU,FRI`. Named days and `*` are left as they are.
+fn unix_weekdays(field: &str) -> Result<String, AppError> {
+    let items = field
+        .split(',')
+        .map(|item| {
+            if !item.chars().any(|c| c.is_ascii_digit()) {
+                return Ok(item.to_string());
+            }
+            let invalid = || {
+                AppError::ValidationError(format!(
+                    "Invalid day of week: {} (use 0-7 or SUN-SAT)",
+                    item
+                ))
+            };
+            let (range, step) = match item.split_once('/') {
+                Some((range, step)) => (range, Some(step.parse::<usize>().map_err(|_| invalid())?)),
+                None => (item, None),
+            };
+            let day = |value: &str| match value.parse::<usize>() {
+                Ok(day) if day < WEEKDAYS.len() => Ok(day),
+                _ => Err(invalid()),
+            };
+            let (first, last) = match (range, range.split_once('-')) {
+                ("*", _) => (0, 6),
+                (_, Some((first, last))) => (day(first)?, day(last)?),
+                (_, None) if step.is_some() => (day(range)?, 7),
+                (_, None) => (day(range)?, day(range)?),
+            };
+            let step = step.unwrap_or(1);
+            if first > last || step == 0 {
+                return Err(invalid());
+            }
+            Ok((first..=last)
+                .step_by(step)
+                .map(|day| WEEKDAYS[day])
+                .collect::<Vec<_>>()
+                .join(","))
+        })
+        .collect::<Result<Vec<_>, AppError>>()?;
+    Ok(items.join(","))
+}
+
+/// The first occurrence of a schedule. Fixed frequencies start exactly at
+/// `starts_at`, cron schedules at their first tick on or after it.
+pub fn first_occurrence(
+    frequency: RecurrenceFrequency,
+    cron_expression: Option<&str>,
+    starts_at: DateTime<Utc>,
+) -> Result<Option<DateTime<Utc>>, AppError> {
+    match frequency {
+        RecurrenceFrequency::Cron => {
+            next_cron_tick(cron_expression, starts_at - Duration::seconds(1))
+        }
+        _ => Ok(Some(starts_at)),
+    }
+}
+
+/// The first occurrence strictly after `after`. Fixed frequencies are always
+/// counted from `starts_at` so a monthly expense on the 31st lands on the last
+/// day of shorter months without drifting to the 28th for the rest of the year.
+pub fn next_occurrence(
+    frequency: RecurrenceFrequency,
+    cron_expression: Option<&str>,
+    starts_at: DateTime<Utc>,
+    after: DateTime<Utc>,
+) -> Result<Option<DateTime<Utc>>, AppError> {
+    if after < starts_at {
+        return first_occurrence(frequency, cron_expression, starts_at);
+    }
+
+    match frequency {
+        RecurrenceFrequency::Daily => Ok(Some(next_fixed(starts_at, after, Duration::days(1)))),
+        RecurrenceFrequency::Weekly => Ok(Some(next_fixed(starts_at, after, Duration::weeks(1)))),
+        RecurrenceFrequency::Monthly => Ok(next_month(starts_at, after)),
+        RecurrenceFrequency::Cron => next_cron_tick(cron_expression, after),
+    }
+}
+
+fn next_fixed(starts_at: DateTime<Utc>, after: DateTime<Utc>, period: Duration) -> DateTime<Utc> {
+    let elapsed = (after - starts_at).num_seconds();
+    let periods = elapsed / period.num_seconds() + 1;
+    starts_at + Duration::seconds(periods * period.num_seconds())
+}
+
+fn next_month(starts_at: DateTime<Utc>, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let months_between = (after.year() - starts_at.year()) * 12 + after.month() as i32
+        - starts_at.month() as i32;
+    let mut months = months_between.max(0) as u32;
+    loop {
+        let candidate = starts_at.checked_add_months(Months::new(months))?;
+        if candidate > after {
+            return Some(candidate);
+        }
+        months += 1;
+    }
+}
+
+fn next_cron_tick(
+    cron_expression: Option<&str>,
+    after: DateTime<Utc>,
+) -> Result<Option<DateTime<Utc>>, AppError> {
+    let expression = cron_expression.ok_or_else(|| {
+        AppError::ValidationError("Cron expression is required for cron schedules".into())
+    })?;
+    Ok(parse_cron(expression)?.after(&after).next())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(timestamp: &str) -> DateTime<Utc> {
+        timestamp.parse().unwrap()
+    }
+
+    #[test]
+    fn five_field_cron_expressions_are_accepted() {
+        assert!(parse_cron("30 9 * * Mon").is_ok());
+        assert!(parse_cron("0 30 9 * * Mon *").is_ok());
+        assert!(matches!(parse_cron("every monday"), Err(AppError::ValidationError(_))));
+    }
+
+    #[test]
+    fn unix_day_numbers_count_from_sunday() {
+        // 2025-03-09 is a Sunday
+        let sunday = at("2025-03-09T12:00:00Z");
+        let next = |expression: &str| {
+            parse_cron(expression).unwrap().after(&sunday).next().unwrap()
+        };
+
+        assert_eq!(next("0 9 * * 1"), at("2025-03-10T09:00:00Z"));
+        assert_eq!(next("0 9 * * 0"), at("2025-03-16T09:00:00Z"));
+        assert_eq!(next("0 9 * * 7"), at("2025-03-16T09:00:00Z"));
+        assert_eq!(next("0 9 * * 6"), at("2025-03-15T09:00:00Z"));
+        assert_eq!(next("0 9 * * Mon"), at("2025-03-10T09:00:00Z"));
+    }
+
+    #[test]
+    fn unix_day_ranges_lists_and_steps_are_spelled_out() {
+        assert_eq!(unix_weekdays("1-5").unwrap(), "MON,TUE,WED,THU,FRI");
+        assert_eq!(unix_weekdays("5-7").unwrap(), "FRI,SAT,SUN");
+        assert_eq!(unix_weekdays("0,3").unwrap(), "SUN,WED");
+        assert_eq!(unix_weekdays("*/2").unwrap(), "SUN,TUE,THU,SAT");
+        assert_eq!(unix_weekdays("1/3").unwrap(), "MON,THU,SUN");
+        assert_eq!(unix_weekdays("MON-FRI").unwrap(), "MON-FRI");
+        assert_eq!(unix_weekdays("*").unwrap(), "*");
+
+        assert!(matches!(unix_weekdays("8"), Err(AppError::ValidationError(_))));
+        assert!(matches!(unix_weekdays("5-1"), Err(AppError::ValidationError(_))));
+        assert!(matches!(parse_cron("0 9 * * 1-8"), Err(AppError::ValidationError(_))));
+    }
+
+    #[test]
+    fn fixed_schedules_start_at_their_start() {
+        let starts_at = at("2025-03-10T09:00:00Z");
+        let first = first_occurrence(RecurrenceFrequency::Weekly, None, starts_at).unwrap();
+        assert_eq!(first, Some(starts_at));
+
+        let before_start = at("2025-03-01T00:00:00Z");
+        let next = next_occurrence(RecurrenceFrequency::Daily, None, starts_at, before_start).unwrap();
+        assert_eq!(next, Some(starts_at));
+    }
+
+    #[test]
+    fn next_occurrence_is_strictly_after() {
+        let starts_at = at("2025-03-10T09:00:00Z");
+        let daily = next_occurrence(RecurrenceFrequency::Daily, None, starts_at, starts_at).unwrap();
+        assert_eq!(daily, Some(at("2025-03-11T09:00:00Z")));
+
+        let weekly = next_occurrence(
+            RecurrenceFrequency::Weekly,
+            None,
+            starts_at,
+            at("2025-03-24T08:59:59Z"),
+        )
+        .unwrap();
+        assert_eq!(weekly, Some(at("2025-03-24T09:00:00Z")));
+    }
+
+    #[test]
+    fn monthly_schedules_keep_the_day_of_month() {
+        let starts_at = at("2025-01-31T12:00:00Z");
+        let february = next_occurrence(RecurrenceFrequency::Monthly, None, starts_at, starts_at).unwrap();
+        assert_eq!(february, Some(at("2025-02-28T12:00:00Z")));
+
+        let march = next_occurrence(RecurrenceFrequency::Monthly, None, starts_at, february.unwrap()).unwrap();
+        assert_eq!(march, Some(at("2025-03-31T12:00:00Z")));
+    }
+
+    #[test]
+    fn cron_schedules_follow_their_ticks() {
+        let starts_at = at("2025-03-10T09:00:00Z");
+        let expression = Some("0 9 * * *");
+
+        let first = first_occurrence(RecurrenceFrequency::Cron, expression, starts_at).unwrap();
+        assert_eq!(first, Some(starts_at));
+        let next = next_occurrence(RecurrenceFrequency::Cron, expression, starts_at, starts_at).unwrap();
+        assert_eq!(next, Some(at("2025-03-11T09:00:00Z")));
+
+        assert!(first_occurrence(RecurrenceFrequency::Cron, None, starts_at).is_err());
+    }
+}
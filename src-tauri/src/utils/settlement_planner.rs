@@ -0,0 +1,169 @@
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SuggestedPayment {
+    pub payer_id: Uuid,
+    pub receiver_id: Uuid,
+    pub amount: Decimal,
+}
+
+/// Reduces a set of net balances (positive = owed, negative = owes) to a short
+/// list of transfers by repeatedly matching the largest debtor with the largest
+/// creditor. Balances are rounded to paise first and any leftover below one
+/// paisa is dropped, so the plan never contains zero-value transfers.
+pub fn simplify_debts(net_balances: &[(Uuid, Decimal)]) -> Vec<SuggestedPayment> {
+    let mut creditors: Vec<(Uuid, Decimal)> = Vec::new();
+    let mut debtors: Vec<(Uuid, Decimal)> = Vec::new();
+
+    for (member_id, net) in net_balances {
+        let net = net.round_dp(2);
+        if net > Decimal::ZERO {
+            creditors.push((*member_id, net));
+        } else if net < Decimal::ZERO {
+            debtors.push((*member_id, -net));
+        }
+    }
+
+    let mut payments = Vec::new();
+
+    loop {
+        sort_largest_first(&mut creditors);
+        sort_largest_first(&mut debtors);
+
+        let (Some(creditor), Some(debtor)) = (creditors.first().copied(), debtors.first().copied()) else {
+            break;
+        };
+
+        let amount = creditor.1.min(debtor.1);
+        payments.push(SuggestedPayment {
+            payer_id: debtor.0,
+            receiver_id: creditor.0,
+            amount,
+        });
+
+        creditors[0].1 -= amount;
+        debtors[0].1 -= amount;
+        creditors.retain(|(_, remaining)| *remaining > Decimal::ZERO);
+        debtors.retain(|(_, remaining)| *remaining > Decimal::ZERO);
+    }
+
+    payments
+}
+
+// ties are broken on the member id so the same balances always give the same plan
+fn sort_largest_first(entries: &mut [(Uuid, Decimal)]) {
+    entries.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::ledger::Ledger;
+    use std::collections::HashMap;
+
+    fn d(value: &str) -> Decimal {
+        value.parse().unwrap()
+    }
+
+    // what each member ends up with once every suggested payment is made
+    fn moved(payments: &[SuggestedPayment]) -> HashMap<Uuid, Decimal> {
+        let mut moved = HashMap::new();
+        for payment in payments {
+            *moved.entry(payment.receiver_id).or_insert(Decimal::ZERO) += payment.amount;
+            *moved.entry(payment.payer_id).or_insert(Decimal::ZERO) -= payment.amount;
+        }
+        moved
+    }
+
+    #[test]
+    fn settled_balances_need_no_transfers() {
+        let balances = vec![(Uuid::new_v4(), Decimal::ZERO), (Uuid::new_v4(), d("0.001"))];
+        assert!(simplify_debts(&balances).is_empty());
+        assert!(simplify_debts(&[]).is_empty());
+    }
+
+    #[test]
+    fn one_creditor_is_paid_by_every_debtor() {
+        let creditor = Uuid::new_v4();
+        let debtors = [Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4()];
+        let mut balances = vec![(creditor, d("60"))];
+        balances.extend(debtors.iter().map(|debtor| (*debtor, d("-20"))));
+
+        let payments = simplify_debts(&balances);
+        assert_eq!(payments.len(), 3);
+        assert!(payments.iter().all(|payment| payment.receiver_id == creditor && payment.amount == d("20")));
+    }
+
+    #[test]
+    fn a_cycle_is_shortened() {
+        // a owes b 10, b owes c 20 and c owes a 5: three debts, two transfers
+        let (a, b, c) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let mut ledger = Ledger::new();
+        ledger.record_expense(&[(b, d("10"))], &[(a, d("10"))]);
+        ledger.record_expense(&[(c, d("20"))], &[(b, d("20"))]);
+        ledger.record_expense(&[(a, d("5"))], &[(c, d("5"))]);
+        assert_eq!(ledger.pairwise_debts().len(), 3);
+        let balances: Vec<(Uuid, Decimal)> = ledger
+            .member_balances()
+            .into_iter()
+            .map(|balance| (balance.member_id, balance.net))
+            .collect();
+
+        let payments = simplify_debts(&balances);
+        assert_eq!(payments.len(), 2);
+        let moved = moved(&payments);
+        assert_eq!(moved[&a], d("-5"));
+        assert_eq!(moved[&b], d("-10"));
+        assert_eq!(moved[&c], d("15"));
+    }
+
+    #[test]
+    fn an_even_cycle_needs_no_transfers() {
+        let (a, b, c) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let mut ledger = Ledger::new();
+        ledger.record_expense(&[(b, d("10"))], &[(a, d("10"))]);
+        ledger.record_expense(&[(c, d("10"))], &[(b, d("10"))]);
+        ledger.record_expense(&[(a, d("10"))], &[(c, d("10"))]);
+        let balances: Vec<(Uuid, Decimal)> = ledger
+            .member_balances()
+            .into_iter()
+            .map(|balance| (balance.member_id, balance.net))
+            .collect();
+
+        assert!(simplify_debts(&balances).is_empty());
+    }
+
+    #[test]
+    fn transfers_add_up_to_the_balances() {
+        let members: Vec<Uuid> = (0..4).map(|_| Uuid::new_v4()).collect();
+        let balances = vec![
+            (members[0], d("45.50")),
+            (members[1], d("-20.25")),
+            (members[2], d("10.00")),
+            (members[3], d("-35.25")),
+        ];
+
+        let payments = simplify_debts(&balances);
+        assert!(payments.len() < balances.len());
+        let moved = moved(&payments);
+        for (member_id, net) in &balances {
+            assert_eq!(moved.get(member_id).copied().unwrap_or_default(), *net);
+        }
+    }
+
+    #[test]
+    fn thirds_are_rounded_to_paise() {
+        let (a, b, c) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let third = Decimal::from(100) / Decimal::from(3);
+        let balances = vec![(a, third * Decimal::from(2)), (b, -third), (c, -third)];
+
+        let payments = simplify_debts(&balances);
+        assert!(payments.iter().all(|payment| payment.amount.scale() <= 2));
+        let moved = moved(&payments);
+        // debtors pay exactly their rounded share, the paisa left over is dropped
+        assert_eq!(moved[&b], d("-33.33"));
+        assert_eq!(moved[&c], d("-33.33"));
+        assert_eq!(moved[&a], d("66.66"));
+    }
+}
@@ -0,0 +1,273 @@
+use crate::custom_errors::app::AppError;
+use crate::entities::sea_orm_active_enums::SplitType;
+use rust_decimal::{Decimal, RoundingStrategy};
+use uuid::Uuid;
+
+const HUNDRED: Decimal = Decimal::ONE_HUNDRED;
+
+/// Turns a split definition into per-member amounts that add up to `amount` exactly.
+///
+/// `values` depends on the split type: exact amounts, percentages, share weights
+/// or per-member adjustments on top of an equal split. Equal splits ignore it.
+pub fn compute_split(
+    split_type: SplitType,
+    amount: Decimal,
+    member_count: usize,
+    values: Option<&[Decimal]>,
+) -> Result<Vec<Decimal>, AppError> {
+    if member_count == 0 {
+        return Err(AppError::ValidationError("Split members cannot be empty".into()));
+    }
+    if amount.scale() > 2 && amount != amount.round_dp(2) {
+        return Err(AppError::ValidationError("Amount cannot have more than two decimal places".into()));
+    }
+
+    let raw_shares: Vec<Decimal> = match split_type {
+        SplitType::Itemized => {
+            return Err(AppError::ValidationError(
+                "Itemized splits are computed from their items".into(),
+            ))
+        }
+        SplitType::Equal => vec![amount / Decimal::from(member_count); member_count],
+        SplitType::Exact => {
+            let values = split_values(split_type, member_count, values)?;
+            let total: Decimal = values.iter().sum();
+            if total != amount {
+                return Err(AppError::AmountsDontAddUp(
+                    "Total split amount does not match the main amount".into(),
+                ));
+            }
+            values.to_vec()
+        }
+        SplitType::Percentage => {
+            let values = split_values(split_type, member_count, values)?;
+            let total: Decimal = values.iter().sum();
+            if total != HUNDRED {
+                return Err(AppError::AmountsDontAddUp("Percentages must add up to 100".into()));
+            }
+            values.iter().map(|percent| amount * percent / HUNDRED).collect()
+        }
+        SplitType::Shares => {
+            let values = split_values(split_type, member_count, values)?;
+            let total: Decimal = values.iter().sum();
+            if total <= Decimal::ZERO {
+                return Err(AppError::ValidationError("Total shares must be greater than zero".into()));
+            }
+            values.iter().map(|weight| amount * weight / total).collect()
+        }
+        SplitType::Adjustment => {
+            let values = split_values(split_type, member_count, values)?;
+            let adjustments: Decimal = values.iter().sum();
+            let base = (amount - adjustments) / Decimal::from(member_count);
+            values.iter().map(|adjustment| base + adjustment).collect()
+        }
+    };
+
+    if raw_shares.iter().any(|share| share.is_sign_negative() && !share.is_zero()) {
+        return Err(AppError::ValidationError("Split amounts cannot be negative".into()));
+    }
+
+    Ok(distribute_paise(amount, &raw_shares))
+}
+
+fn split_values(
+    split_type: SplitType,
+    member_count: usize,
+    values: Option<&[Decimal]>,
+) -> Result<&[Decimal], AppError> {
+    match values {
+        Some(values) if values.len() == member_count => Ok(values),
+        Some(_) => Err(AppError::ValidationError(
+            "Split values must match the number of split members".into(),
+        )),
+        None => Err(AppError::ValidationError(format!(
+            "Split values are required for a {:?} split",
+            split_type
+        ))),
+    }
+}
+
+/// Rounds every share down to whole paise and hands the leftover paise, one at
+/// a time, to the members with the largest remainders. Ties go to whoever comes
+/// first in the member list so the same input always yields the same split.
+fn distribute_paise(amount: Decimal, raw_shares: &[Decimal]) -> Vec<Decimal> {
+    let paisa = Decimal::new(1, 2);
+    let mut shares: Vec<Decimal> = raw_shares
+        .iter()
+        .map(|share| share.round_dp_with_strategy(2, RoundingStrategy::ToZero))
+        .collect();
+
+    let mut order: Vec<usize> = (0..raw_shares.len()).collect();
+    order.sort_by(|a, b| {
+        let remainder_a = raw_shares[*a] - shares[*a];
+        let remainder_b = raw_shares[*b] - shares[*b];
+        remainder_b.cmp(&remainder_a).then_with(|| a.cmp(b))
+    });
+
+    let allocated: Decimal = shares.iter().sum();
+    let mut leftover = ((amount - allocated) / paisa).round();
+    let mut next = order.iter().cycle();
+    while leftover > Decimal::ZERO {
+        if let Some(index) = next.next() {
+            shares[*index] += paisa;
+        }
+        leftover -= Decimal::ONE;
+    }
+
+    shares
+}
+
+/// Splits an itemized bill. Each line total is shared equally by the members
+/// assigned to it, and `charges` (tax, service charge and tip) are spread in
+/// proportion to what each member ordered. Members come back in the order they
+/// first appear in `items`.
+pub fn compute_itemized_split(
+    amount: Decimal,
+    items: &[(Decimal, &[Uuid])],
+    charges: Decimal,
+) -> Result<(Vec<Uuid>, Vec<Decimal>), AppError> {
+    if items.is_empty() {
+        return Err(AppError::ValidationError("Items cannot be empty".into()));
+    }
+
+    let subtotal: Decimal = items.iter().map(|(line_total, _)| *line_total).sum();
+    if subtotal <= Decimal::ZERO {
+        return Err(AppError::ValidationError("Items must add up to more than zero".into()));
+    }
+    if subtotal + charges != amount {
+        return Err(AppError::AmountsDontAddUp(
+            "Items, tax, service charge and tip do not add up to the main amount".into(),
+        ));
+    }
+
+    let mut members: Vec<Uuid> = Vec::new();
+    let mut ordered: Vec<Decimal> = Vec::new();
+    for (line_total, assigned) in items {
+        if assigned.is_empty() {
+            return Err(AppError::ValidationError(
+                "Every item must be assigned to at least one member".into(),
+            ));
+        }
+        let per_member = *line_total / Decimal::from(assigned.len());
+        for member in assigned.iter() {
+            match members.iter().position(|existing| existing == member) {
+                Some(index) => ordered[index] += per_member,
+                None => {
+                    members.push(*member);
+                    ordered.push(per_member);
+                }
+            }
+        }
+    }
+
+    let raw_shares: Vec<Decimal> = ordered
+        .iter()
+        .map(|share| *share * amount / subtotal)
+        .collect();
+
+    Ok((members, distribute_paise(amount, &raw_shares)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn d(value: &str) -> Decimal {
+        value.parse().unwrap()
+    }
+
+    fn ds(values: &[&str]) -> Vec<Decimal> {
+        values.iter().map(|value| d(value)).collect()
+    }
+
+    #[test]
+    fn equal_split_hands_out_the_remainder_paise() {
+        let shares = compute_split(SplitType::Equal, d("100"), 3, None).unwrap();
+        assert_eq!(shares, ds(&["33.34", "33.33", "33.33"]));
+
+        let shares = compute_split(SplitType::Equal, d("0.05"), 3, None).unwrap();
+        assert_eq!(shares, ds(&["0.02", "0.02", "0.01"]));
+    }
+
+    #[test]
+    fn largest_remainders_get_the_leftover_paise() {
+        // 10 split 1:1:1:3 leaves 1.666.., 1.666.., 1.666.., 5
+        let weights = ds(&["1", "1", "1", "3"]);
+        let shares = compute_split(SplitType::Shares, d("10"), 4, Some(&weights)).unwrap();
+        assert_eq!(shares, ds(&["1.67", "1.67", "1.66", "5.00"]));
+        assert_eq!(shares.iter().sum::<Decimal>(), d("10"));
+    }
+
+    #[test]
+    fn percentages_must_add_up_to_a_hundred() {
+        let percentages = ds(&["50", "30", "15"]);
+        let result = compute_split(SplitType::Percentage, d("200"), 3, Some(&percentages));
+        assert!(matches!(result, Err(AppError::AmountsDontAddUp(_))));
+
+        let percentages = ds(&["50", "33.33", "16.67"]);
+        let shares = compute_split(SplitType::Percentage, d("200"), 3, Some(&percentages)).unwrap();
+        assert_eq!(shares, ds(&["100.00", "66.66", "33.34"]));
+    }
+
+    #[test]
+    fn zero_shares_are_rejected() {
+        let weights = ds(&["0", "0"]);
+        let result = compute_split(SplitType::Shares, d("50"), 2, Some(&weights));
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+
+        // one member may sit out as long as someone pays
+        let weights = ds(&["0", "2"]);
+        let shares = compute_split(SplitType::Shares, d("50"), 2, Some(&weights)).unwrap();
+        assert_eq!(shares, ds(&["0", "50"]));
+    }
+
+    #[test]
+    fn exact_amounts_must_match_the_total() {
+        let values = ds(&["10", "20"]);
+        let result = compute_split(SplitType::Exact, d("40"), 2, Some(&values));
+        assert!(matches!(result, Err(AppError::AmountsDontAddUp(_))));
+
+        let shares = compute_split(SplitType::Exact, d("30"), 2, Some(&values)).unwrap();
+        assert_eq!(shares, values);
+    }
+
+    #[test]
+    fn adjustments_sit_on_top_of_an_equal_split() {
+        let adjustments = ds(&["10", "0", "-4"]);
+        let shares = compute_split(SplitType::Adjustment, d("96"), 3, Some(&adjustments)).unwrap();
+        assert_eq!(shares, ds(&["40", "30", "26"]));
+
+        let adjustments = ds(&["100", "0"]);
+        let result = compute_split(SplitType::Adjustment, d("50"), 2, Some(&adjustments));
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+    }
+
+    #[test]
+    fn split_values_are_checked_against_the_members() {
+        let values = ds(&["1", "1"]);
+        for split_type in [SplitType::Exact, SplitType::Percentage, SplitType::Shares, SplitType::Adjustment] {
+            let missing = compute_split(split_type, d("10"), 2, None);
+            assert!(matches!(missing, Err(AppError::ValidationError(_))));
+            let short = compute_split(split_type, d("10"), 3, Some(&values));
+            assert!(matches!(short, Err(AppError::ValidationError(_))));
+        }
+        assert!(compute_split(SplitType::Equal, d("10"), 0, None).is_err());
+        assert!(compute_split(SplitType::Equal, d("10.005"), 2, None).is_err());
+        assert!(compute_split(SplitType::Itemized, d("10"), 2, Some(&values)).is_err());
+    }
+
+    #[test]
+    fn itemized_charges_follow_what_each_member_ordered() {
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        let both = [a, b];
+        let only_a = [a];
+        let items: Vec<(Decimal, &[Uuid])> = vec![(d("60"), &both[..]), (d("40"), &only_a[..])];
+
+        let (members, shares) = compute_itemized_split(d("110"), &items, d("10")).unwrap();
+        assert_eq!(members, vec![a, b]);
+        assert_eq!(shares, ds(&["77.00", "33.00"]));
+
+        let result = compute_itemized_split(d("120"), &items, d("10"));
+        assert!(matches!(result, Err(AppError::AmountsDontAddUp(_))));
+    }
+}
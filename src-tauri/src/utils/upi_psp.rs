@@ -0,0 +1,157 @@
+use crate::custom_errors::app::AppError;
+use crate::entities::sea_orm_active_enums::UpiPaymentStatus;
+use async_trait::async_trait;
+use dotenv::dotenv;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::env;
+use std::sync::{Arc, RwLock};
+
+/// A payment service provider that can report what happened to a UPI intent,
+/// either when polled or by pushing a callback to the server.
+#[async_trait]
+pub trait UpiPaymentProvider: Send + Sync {
+    async fn fetch_status(&self, transaction_ref: &str) -> Result<UpiPaymentStatus, AppError>;
+
+    /// Checks that a callback really came from the provider. Unsigned callbacks
+    /// must be rejected, the callback route has no other authentication.
+    fn verify_callback(&self, signature: Option<&str>, body: &[u8]) -> bool;
+}
+
+pub type SharedUpiProvider = Arc<dyn UpiPaymentProvider>;
+
+/// In-process provider used for local development and tests. Unknown references
+/// are reported as still pending until a signed callback says otherwise.
+#[derive(Default)]
+pub struct MockUpiProvider {
+    statuses: RwLock<HashMap<String, UpiPaymentStatus>>,
+    callback_secret: Option<String>,
+}
+
+impl MockUpiProvider {
+    pub fn new(callback_secret: Option<String>) -> Self {
+        Self {
+            statuses: RwLock::new(HashMap::new()),
+            callback_secret,
+        }
+    }
+
+    #[cfg(test)]
+    pub fn set_status(&self, transaction_ref: &str, status: UpiPaymentStatus) {
+        if let Ok(mut statuses) = self.statuses.write() {
+            statuses.insert(transaction_ref.to_string(), status);
+        }
+    }
+}
+
+#[async_trait]
+impl UpiPaymentProvider for MockUpiProvider {
+    async fn fetch_status(&self, transaction_ref: &str) -> Result<UpiPaymentStatus, AppError> {
+        let statuses = self
+            .statuses
+            .read()
+            .map_err(|_| AppError::InternalServerError)?;
+        Ok(statuses
+            .get(transaction_ref)
+            .copied()
+            .unwrap_or(UpiPaymentStatus::Pending))
+    }
+
+    fn verify_callback(&self, signature: Option<&str>, body: &[u8]) -> bool {
+        match (&self.callback_secret, signature) {
+            (Some(secret), Some(signature)) => signature_matches(secret, signature, body),
+            // without a shared secret nobody can prove where a callback came from
+            _ => false,
+        }
+    }
+}
+
+/// `signature` is the hex encoded HMAC-SHA256 of the raw body keyed with `secret`.
+fn signature_matches(secret: &str, signature: &str, body: &[u8]) -> bool {
+    let signature = match hex::decode(signature.trim()) {
+        Ok(signature) => signature,
+        Err(_) => return false,
+    };
+    let mut mac = match Hmac::<Sha256>::new_from_slice(secret.as_bytes()) {
+        Ok(mac) => mac,
+        Err(_) => return false,
+    };
+    mac.update(body);
+    // compared in constant time
+    mac.verify_slice(&signature).is_ok()
+}
+
+/// Picks the provider named by `UPI_PSP`. Only the mock provider ships today.
+/// Callbacks carry an `X-PSP-Signature` signed with `UPI_PSP_CALLBACK_SECRET`
+/// and are all rejected while it is unset.
+pub fn provider_from_env() -> Result<SharedUpiProvider, AppError> {
+    dotenv().ok();
+    let provider = env::var("UPI_PSP").unwrap_or_else(|_| "mock".to_string());
+    let callback_secret = env::var("UPI_PSP_CALLBACK_SECRET").ok();
+
+    match provider.trim() {
+        "mock" => Ok(Arc::new(MockUpiProvider::new(callback_secret))),
+        other => Err(AppError::ConfigError(format!("Unknown UPI_PSP value: {}", other))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sign(secret: &str, body: &[u8]) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(body);
+        hex::encode(mac.finalize().into_bytes())
+    }
+
+    #[test]
+    fn callbacks_are_rejected_without_a_secret() {
+        let provider = MockUpiProvider::new(None);
+        let body = br#"{"transaction_ref":"ref","status":"success"}"#;
+        assert!(!provider.verify_callback(None, body));
+        assert!(!provider.verify_callback(Some(&sign("anything", body)), body));
+    }
+
+    #[test]
+    fn callbacks_need_a_signature_over_the_body() {
+        let provider = MockUpiProvider::new(Some("secret".into()));
+        let body = br#"{"transaction_ref":"ref","status":"success"}"#;
+
+        assert!(provider.verify_callback(Some(&sign("secret", body)), body));
+        assert!(!provider.verify_callback(None, body));
+        // the secret itself is not a signature
+        assert!(!provider.verify_callback(Some("secret"), body));
+        assert!(!provider.verify_callback(Some(&sign("other", body)), body));
+        assert!(!provider.verify_callback(Some(&sign("secret", body)), br#"{"transaction_ref":"ref","status":"failed"}"#));
+        assert!(!provider.verify_callback(Some("not hex"), body));
+    }
+
+    #[tokio::test]
+    async fn mock_reports_pending_until_told_otherwise() {
+        let provider = MockUpiProvider::new(None);
+        assert_eq!(provider.fetch_status("ref").await.unwrap(), UpiPaymentStatus::Pending);
+
+        provider.set_status("ref", UpiPaymentStatus::Success);
+        assert_eq!(provider.fetch_status("ref").await.unwrap(), UpiPaymentStatus::Success);
+        assert_eq!(provider.fetch_status("other").await.unwrap(), UpiPaymentStatus::Pending);
+    }
+
+    #[test]
+    fn payments_move_forward_only() {
+        use UpiPaymentStatus::*;
+        assert!(Initiated.can_transition_to(Pending));
+        assert!(Initiated.can_transition_to(Success));
+        assert!(Pending.can_transition_to(Failed));
+        assert!(Pending.can_transition_to(Expired));
+        assert!(!Pending.can_transition_to(Initiated));
+        assert!(!Initiated.can_transition_to(Initiated));
+        for terminal in [Success, Failed, Expired] {
+            assert!(terminal.is_terminal());
+            for next in [Initiated, Pending, Success, Failed, Expired] {
+                assert!(!terminal.can_transition_to(next));
+            }
+        }
+    }
+}